@@ -0,0 +1,94 @@
+use std::{fs, io, path::Path};
+
+/// Expected answer of one part on one input of a day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Expected {
+    /// Input file, relative to the day crate, e.g. `examples/example.txt`.
+    pub input: String,
+    pub part: u8,
+    pub answer: String,
+}
+
+/// The `answers.txt` of a day crate: one `<input> <part> <answer>` per line,
+/// blank lines and `#` comments are skipped.
+#[derive(Debug, Default)]
+pub struct Answers(Vec<Expected>);
+
+impl Answers {
+    /// Reads `answers.txt` from `day_dir`, a day without one has no answers.
+    pub fn read(day_dir: &Path) -> miette::Result<Self> {
+        let path = day_dir.join("answers.txt");
+        match fs::read_to_string(&path) {
+            Ok(contents) => {
+                Self::parse(&contents).map_err(|err| miette::miette!("{}: {}", path.display(), err))
+            }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(miette::miette!(
+                "failed to read {}: {}",
+                path.display(),
+                err
+            )),
+        }
+    }
+
+    pub fn parse(contents: &str) -> miette::Result<Self> {
+        contents
+            .lines()
+            .enumerate()
+            .map(|(n, line)| (n + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+            .map(|(n, line)| {
+                let mut fields = line.split_whitespace();
+                match (fields.next(), fields.next(), fields.next(), fields.next()) {
+                    (Some(input), Some(part @ ("1" | "2")), Some(answer), None) => Ok(Expected {
+                        input: input.to_string(),
+                        part: part.parse().expect("part is 1 or 2"),
+                        answer: answer.to_string(),
+                    }),
+                    _ => Err(miette::miette!(
+                        "line {}: expected `<input> <part> <answer>`, got `{}`",
+                        n,
+                        line
+                    )),
+                }
+            })
+            .collect::<miette::Result<_>>()
+            .map(Self)
+    }
+
+    pub fn get(&self, input: &str, part: u8) -> Option<&str> {
+        self.0
+            .iter()
+            .find(|expected| expected.input == input && expected.part == part)
+            .map(|expected| expected.answer.as_str())
+    }
+
+    /// Every input that has an answer for `part`, in file order.
+    pub fn inputs(&self, part: u8) -> impl Iterator<Item = &str> {
+        self.0
+            .iter()
+            .filter(move |expected| expected.part == part)
+            .map(|expected| expected.input.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() -> miette::Result<()> {
+        let answers = Answers::parse(
+            "# comment\n\nexamples/example.txt 1 142\ninput.txt 1 54916\ninput.txt 2 54728\n",
+        )?;
+        assert_eq!(Some("142"), answers.get("examples/example.txt", 1));
+        assert_eq!(None, answers.get("examples/example.txt", 2));
+        assert_eq!(
+            vec!["examples/example.txt", "input.txt"],
+            answers.inputs(1).collect::<Vec<_>>()
+        );
+        assert!(Answers::parse("input.txt 3 1").is_err());
+        assert!(Answers::parse("input.txt 1").is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,105 @@
+/// Registers divan benches for the stages of a solver module on the bundled
+/// input: `parse`, `solve` on the already parsed input, and `total` through
+/// `process`. Extra arguments are passed on to `solve` and `process`.
+///
+/// ```ignore
+/// aoc_common::bench_stages!(day_05::part1_binary_search);
+/// aoc_common::bench_stages!(day_11::part2, 1_000_000);
+/// ```
+#[macro_export]
+macro_rules! bench_stages {
+    ($krate:ident::$variant:ident $(, $arg:expr)*) => {
+        mod $variant {
+            use $krate::{input::read_input, $variant as variant};
+
+            #[divan::bench]
+            fn parse(bencher: divan::Bencher) {
+                let input = read_input(None).unwrap();
+                bencher.bench(|| variant::parse(divan::black_box(&input)).unwrap());
+            }
+
+            #[divan::bench]
+            fn solve(bencher: divan::Bencher) {
+                let input = read_input(None).unwrap();
+                let parsed = variant::parse(&input).unwrap();
+                bencher.bench(|| variant::solve(divan::black_box(&parsed) $(, $arg)*).unwrap());
+            }
+
+            #[divan::bench]
+            fn total(bencher: divan::Bencher) {
+                let input = read_input(None).unwrap();
+                bencher.bench(|| variant::process(divan::black_box(&input) $(, $arg)*).unwrap());
+            }
+        }
+    };
+}
+
+/// Registers divan benches of the throughput of solver modules that can also
+/// stream their input, in bytes of the bundled input per second: `str`
+/// through `process` and `reader` through `process_reader`.
+///
+/// ```ignore
+/// aoc_common::bench_throughput!(day_04::part1, day_04::part2);
+/// ```
+#[macro_export]
+macro_rules! bench_throughput {
+    ($($krate:ident::$variant:ident),+ $(,)?) => {
+        mod throughput {
+            $(mod $variant {
+                use $krate::{input::read_input, $variant as variant};
+
+                #[divan::bench]
+                fn str(bencher: divan::Bencher) {
+                    let input = read_input(None).unwrap();
+                    bencher
+                        .counter(divan::counter::BytesCount::of_str(&input))
+                        .bench(|| variant::process(divan::black_box(&input)).unwrap());
+                }
+
+                #[divan::bench]
+                fn reader(bencher: divan::Bencher) {
+                    let input = read_input(None).unwrap();
+                    bencher
+                        .counter(divan::counter::BytesCount::of_str(&input))
+                        .bench(|| {
+                            variant::process_reader(divan::black_box(input.as_bytes())).unwrap()
+                        });
+                }
+            })+
+        }
+    };
+}
+
+/// Registers divan benches of how solver modules scale with their input:
+/// `process` on `input(n)` for every `n` of a list, under a module named
+/// after what `n` counts. `input` is a function of the bench file, called
+/// once per `n` outside the timing. Extra arguments of a variant go in
+/// parentheses after it. The performance report fits `time ~ n^k` to each
+/// variant and charts its curve on log-log axes.
+///
+/// ```ignore
+/// aoc_common::bench_scaling!(galaxies: [32, 64, 128] => image;
+///     day_11::part1_expand,
+///     day_11::part2(1_000_000),
+/// );
+/// ```
+#[macro_export]
+macro_rules! bench_scaling {
+    (
+        $axis:ident: $args:expr => $input:ident;
+        $($(#[$meta:meta])* $krate:ident::$variant:ident $(($($arg:expr),*))?),+ $(,)?
+    ) => {
+        mod $axis {
+            $(
+                $(#[$meta])*
+                #[divan::bench(args = $args)]
+                fn $variant(bencher: divan::Bencher, n: usize) {
+                    let input = super::$input(n);
+                    bencher.bench(|| {
+                        $krate::$variant::process(divan::black_box(&input) $($(, $arg)*)?).unwrap()
+                    });
+                }
+            )+
+        }
+    };
+}
@@ -0,0 +1,40 @@
+/// Most heap a solver may use on the real input.
+#[derive(Debug, Clone, Copy)]
+pub struct Budget {
+    /// Allocations over the whole run.
+    pub blocks: u64,
+    /// Bytes alive at the peak of the heap.
+    pub max_bytes: usize,
+}
+
+/// Runs `solve` under dhat's testing profiler and fails if it allocates more
+/// than `budget`, saving the heap profile to `dhat-heap-<name>.json` for
+/// `dh_view.html`.
+///
+/// dhat counts every allocation of the process, so the test binary must
+/// install `dhat::Alloc` as its global allocator and run nothing else: keep a
+/// single test per file in `tests/`.
+pub fn assert_within<T>(name: &str, budget: Budget, solve: impl FnOnce() -> T) -> T {
+    let profiler = dhat::Profiler::builder()
+        .testing()
+        .file_name(format!("dhat-heap-{}.json", name))
+        .build();
+    let answer = solve();
+    let stats = dhat::HeapStats::get();
+    dhat::assert!(
+        stats.total_blocks <= budget.blocks,
+        "{} made {} allocations, its budget is {}",
+        name,
+        stats.total_blocks,
+        budget.blocks
+    );
+    dhat::assert!(
+        stats.max_bytes <= budget.max_bytes,
+        "{} peaked at {} bytes, its budget is {}",
+        name,
+        stats.max_bytes,
+        budget.max_bytes
+    );
+    drop(profiler);
+    answer
+}
@@ -0,0 +1,197 @@
+use nom::sequence::terminated;
+
+use crate::{
+    errors::AocError,
+    parse::{end_of_line, into_aoc_error, IResult},
+};
+
+/// Every way an input breaks the structure its puzzle assumes. Unlike
+/// parsing, checking goes on after the first violation.
+#[derive(Debug)]
+pub struct Violations<'a> {
+    input: &'a str,
+    errors: Vec<AocError>,
+}
+
+impl<'a> Violations<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Self {
+            input,
+            errors: Vec::new(),
+        }
+    }
+
+    /// Records that `token`, a slice of the input, breaks an assumption.
+    pub fn report(&mut self, token: &str, message: impl Into<String>) {
+        self.errors
+            .push(AocError::invalid_input(self.input, token, message));
+    }
+
+    /// Records `err` as is, e.g. the error of a solver.
+    pub fn push(&mut self, err: AocError) {
+        self.errors.push(err);
+    }
+
+    /// Parses the whole input with `parser`, recording its error if any.
+    pub fn parse<T>(&mut self, parser: impl FnMut(&'a str) -> IResult<'a, T>) -> Option<T> {
+        crate::parse::finish(self.input, parser)
+            .map_err(|err| self.push(err))
+            .ok()
+    }
+
+    /// Parses every line of the input with `line` on its own, recording the
+    /// error of each line that does not parse.
+    pub fn lines<T>(
+        &mut self,
+        mut line: impl FnMut(&'a str) -> IResult<'a, T>,
+    ) -> Vec<(&'a str, T)> {
+        let parsed = self
+            .input
+            .lines()
+            .filter_map(|text| Some((text, self.line(text, &mut line)?)))
+            .collect::<Vec<_>>();
+        if parsed.is_empty() && self.errors.is_empty() {
+            self.report(self.input, "the input is empty");
+        }
+        parsed
+    }
+
+    /// Parses `text`, a line of the input, whole with `line`, recording its
+    /// error if any.
+    pub fn line<T>(
+        &mut self,
+        text: &'a str,
+        line: impl FnMut(&'a str) -> IResult<'a, T>,
+    ) -> Option<T> {
+        match terminated(line, end_of_line)(text) {
+            Ok((_, parsed)) => Some(parsed),
+            Err(err) => {
+                self.push(into_aoc_error(err, self.input));
+                None
+            }
+        }
+    }
+
+    /// Checks that `rows`, slices of the input, are a non-empty rectangle of
+    /// `cells`, recording every stray cell and every row of the wrong width.
+    pub fn grid(&mut self, rows: &[&str], cells: &str) {
+        for row in rows {
+            for (i, cell) in row.char_indices() {
+                if !cells.contains(cell) {
+                    self.report(
+                        &row[i..i + cell.len_utf8()],
+                        format!("`{}` is not one of `{}`", cell, cells),
+                    );
+                }
+            }
+        }
+        self.rectangle(rows);
+    }
+
+    /// Checks that `rows`, slices of the input, are non-empty and all as wide
+    /// as the first, recording every row that is not.
+    pub fn rectangle(&mut self, rows: &[&str]) {
+        let Some(first) = rows.first() else {
+            self.report(self.input, "the grid is empty");
+            return;
+        };
+        let width = first.chars().count();
+        for row in rows {
+            let row_width = row.chars().count();
+            if row_width != width {
+                self.report(
+                    row,
+                    format!("row is {} wide, the first row is {} wide", row_width, width),
+                );
+            }
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    pub fn into_errors(self) -> Vec<AocError> {
+        self.errors
+    }
+}
+
+/// Fails on the first of `rows`, slices of `input`, that is not as wide as the
+/// first, for solvers that index every row with the width of the first.
+pub fn rectangle(input: &str, rows: &[&str]) -> Result<(), AocError> {
+    let mut violations = Violations::new(input);
+    violations.rectangle(rows);
+    violations
+        .into_errors()
+        .into_iter()
+        .next()
+        .map_or(Ok(()), Err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::parse::{number, numbers, tag};
+    use nom::sequence::{preceded, separated_pair};
+
+    fn messages(violations: Violations) -> Vec<String> {
+        violations
+            .into_errors()
+            .into_iter()
+            .map(|err| err.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_lines() {
+        let input = "Card 1: 41\nCard x: 13\nCard 3: 9 y\n";
+        let mut violations = Violations::new(input);
+        let cards = violations.lines(separated_pair(
+            preceded(tag("Card "), number::<u32>),
+            tag(": "),
+            numbers::<u32>,
+        ));
+        assert_eq!(vec![("Card 1: 41", (1, vec![41]))], cards);
+        assert_eq!(
+            vec![
+                "failed to parse input: expected a number at line 2, column 6",
+                "failed to parse input: expected the end of the line at line 3, column 10",
+            ],
+            messages(violations)
+        );
+    }
+
+    #[test]
+    fn test_grid() {
+        let input = "#.#\n.O.\n##\n";
+        let rows = input.lines().collect::<Vec<_>>();
+        let mut violations = Violations::new(input);
+        violations.grid(&rows, ".#");
+        let errors = violations.into_errors();
+        assert_eq!(2, errors.len());
+        match &errors[..] {
+            [AocError::InvalidInput { message, span, .. }, AocError::InvalidInput { message: width, .. }] =>
+            {
+                assert_eq!("`O` is not one of `.#`", message);
+                assert_eq!((5, 1), (span.offset(), span.len()));
+                assert_eq!("row is 2 wide, the first row is 3 wide", width);
+            }
+            other => panic!("expected two invalid inputs, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_rectangle() {
+        let input = "#.#\n.#\n#\n";
+        let rows = input.lines().collect::<Vec<_>>();
+        assert!(rectangle(input, &rows[..1]).is_ok());
+        match rectangle(input, &rows) {
+            Err(AocError::InvalidInput { message, span, .. }) => {
+                assert_eq!("row is 2 wide, the first row is 3 wide", message);
+                assert_eq!((4, 2), (span.offset(), span.len()));
+            }
+            other => panic!("expected an invalid input, got {:?}", other),
+        }
+    }
+}
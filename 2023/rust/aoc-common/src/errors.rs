@@ -0,0 +1,162 @@
+use miette::{Diagnostic, SourceSpan};
+use thiserror::Error;
+
+#[derive(Error, Diagnostic, Debug)]
+pub enum AocError {
+    #[error(transparent)]
+    #[diagnostic(code(aoc::io_error))]
+    IoError(#[from] std::io::Error),
+
+    /// A token of the input could not be parsed.
+    #[error("failed to parse input: {message}")]
+    #[diagnostic(code(aoc::parse_error))]
+    ParseError {
+        message: String,
+        #[source_code]
+        src: String,
+        #[label("here")]
+        span: SourceSpan,
+    },
+
+    /// The input parsed, but breaks an assumption of the puzzle.
+    #[error("invalid puzzle input: {message}")]
+    #[diagnostic(code(aoc::invalid_input))]
+    InvalidInput {
+        message: String,
+        #[source_code]
+        src: String,
+        #[label("here")]
+        span: SourceSpan,
+    },
+
+    /// The input is well-formed, but has no answer.
+    #[error("no solution: {message}")]
+    #[diagnostic(code(aoc::no_solution))]
+    NoSolution {
+        message: String,
+        #[source_code]
+        src: String,
+        #[label("here")]
+        span: SourceSpan,
+    },
+
+    /// The answer, or a number on the way to it, does not fit in its type.
+    #[error("arithmetic overflow: {0}")]
+    #[diagnostic(code(aoc::overflow))]
+    Overflow(String),
+
+    /// Every puzzle has exactly two parts.
+    #[error("there is no part {0}, only 1 and 2")]
+    #[diagnostic(code(aoc::unknown_part))]
+    UnknownPart(u8),
+}
+
+impl AocError {
+    /// `token` must be a slice of `input`, it is labeled in the diagnostic.
+    pub fn parse_error(input: &str, token: &str, message: impl Into<String>) -> Self {
+        Self::ParseError {
+            message: message.into(),
+            src: input.to_string(),
+            span: span_of(input, token),
+        }
+    }
+
+    /// `token` must be a slice of `input`, it is labeled in the diagnostic.
+    pub fn invalid_input(input: &str, token: &str, message: impl Into<String>) -> Self {
+        Self::InvalidInput {
+            message: message.into(),
+            src: input.to_string(),
+            span: span_of(input, token),
+        }
+    }
+
+    /// `token` must be a slice of `input`, it is labeled in the diagnostic.
+    pub fn no_solution(input: &str, token: &str, message: impl Into<String>) -> Self {
+        Self::NoSolution {
+            message: message.into(),
+            src: input.to_string(),
+            span: span_of(input, token),
+        }
+    }
+
+    pub fn overflow(message: impl Into<String>) -> Self {
+        Self::Overflow(message.into())
+    }
+
+    /// Moves an error raised while processing `part`, a slice of `input`,
+    /// onto the whole `input`, e.g. from a single line onto the file.
+    pub fn within(self, input: &str, part: &str) -> Self {
+        let offset = span_of(input, part).offset();
+        let rebase = |span: SourceSpan| SourceSpan::from((span.offset() + offset, span.len()));
+        match self {
+            Self::IoError(err) => Self::IoError(err),
+            Self::UnknownPart(part) => Self::UnknownPart(part),
+            Self::Overflow(message) => Self::Overflow(message),
+            Self::ParseError { message, span, .. } => Self::ParseError {
+                message,
+                src: input.to_string(),
+                span: rebase(span),
+            },
+            Self::InvalidInput { message, span, .. } => Self::InvalidInput {
+                message,
+                src: input.to_string(),
+                span: rebase(span),
+            },
+            Self::NoSolution { message, span, .. } => Self::NoSolution {
+                message,
+                src: input.to_string(),
+                span: rebase(span),
+            },
+        }
+    }
+}
+
+/// Position of `token` inside `input`, or an empty span at the start if
+/// `token` is not a slice of `input`.
+pub fn span_of(input: &str, token: &str) -> SourceSpan {
+    let start = input.as_ptr() as usize;
+    match (token.as_ptr() as usize).checked_sub(start) {
+        Some(offset) if offset + token.len() <= input.len() => (offset, token.len()).into(),
+        _ => (0, 0).into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_span_of() {
+        let input = "Time: 7 15\nDistance: 9 x0";
+        let token = input.split_whitespace().last().unwrap();
+        assert_eq!(SourceSpan::from((23, 2)), span_of(input, token));
+        assert_eq!(SourceSpan::from((0, 0)), span_of(input, "x0"));
+    }
+
+    #[test]
+    fn test_within() {
+        let input = "1 2\n3 x 5";
+        let line = input.lines().last().unwrap();
+        let err = AocError::parse_error(line, &line[2..3], "expected a number").within(input, line);
+        match err {
+            AocError::ParseError { src, span, .. } => {
+                assert_eq!(input, src);
+                assert_eq!(SourceSpan::from((6, 1)), span);
+            }
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_render_label() {
+        let input = "1 2\n3 x 5";
+        let err = AocError::parse_error(input, &input[6..7], "expected a number");
+        let mut rendered = String::new();
+        miette::GraphicalReportHandler::new_themed(miette::GraphicalTheme::unicode_nocolor())
+            .render_report(&mut rendered, &err)
+            .unwrap();
+        assert!(rendered.contains("failed to parse input: expected a number"));
+        assert!(rendered.contains("2 │ 3 x 5"));
+        assert!(rendered.contains("╰── here"));
+    }
+}
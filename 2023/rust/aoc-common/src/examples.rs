@@ -0,0 +1,109 @@
+use std::{fmt::Display, fs, path::Path};
+
+use crate::{answers::Answers, errors::AocError};
+
+/// Registers an rstest test per solver module, with a case for every
+/// `examples/*.txt` of the day crate, checking the answer of `process` on it
+/// against the one for its part in the crate's `answers.txt`. Extra arguments
+/// of a variant go in parentheses after it.
+///
+/// rstest expands the glob at compile time and `answers.txt` is included in
+/// the test, so a new example is picked up as soon as its answer is added.
+///
+/// ```ignore
+/// aoc_common::example_tests!(
+///     1 => day_11::part1_expand,
+///     #[cfg(feature = "parallel")]
+///     1 => day_11::part1_parallel,
+///     2 => day_11::part2(day_11::Params::default().expansion_factor),
+/// );
+/// ```
+#[macro_export]
+macro_rules! example_tests {
+    (
+        $($(#[$meta:meta])* $part:literal => $krate:ident::$variant:ident $(($($arg:expr),*))?),+
+        $(,)?
+    ) => {
+        // rstest names the type of a `#[files]` argument unqualified
+        use std::path::PathBuf;
+
+        const ANSWERS: &str = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/answers.txt"));
+
+        $(
+            $(#[$meta])*
+            #[rstest::rstest]
+            fn $variant(#[files("examples/*.txt")] path: PathBuf) -> miette::Result<()> {
+                $crate::examples::check_example(
+                    ANSWERS,
+                    env!("CARGO_MANIFEST_DIR").as_ref(),
+                    &path,
+                    $part,
+                    |input| $krate::$variant::process(input $($(, $arg)*)?),
+                )
+            }
+        )+
+    };
+}
+
+/// Runs `process` on the example at `path` and compares its answer to the one
+/// for `part` in `answers`, the `answers.txt` of the day crate at `day_dir`.
+/// A part the example has no answer for is skipped, but an example without
+/// any answer fails, so none is left unchecked.
+pub fn check_example<T: Display>(
+    answers: &str,
+    day_dir: &Path,
+    path: &Path,
+    part: u8,
+    process: impl FnOnce(&str) -> Result<T, AocError>,
+) -> miette::Result<()> {
+    let answers = Answers::parse(answers)?;
+    let input = path
+        .strip_prefix(day_dir)
+        .map_err(|_| miette::miette!("{} is not in {}", path.display(), day_dir.display()))?
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/");
+    let Some(expected) = answers.get(&input, part) else {
+        if answers.get(&input, 3 - part).is_none() {
+            miette::bail!("{} has no answer in answers.txt", input);
+        }
+        return Ok(());
+    };
+
+    let contents = fs::read_to_string(path)
+        .map_err(|err| miette::miette!("failed to read {}: {}", path.display(), err))?;
+    let answer = process(&contents)?.to_string();
+    if answer != expected {
+        miette::bail!(
+            "part {} of {} is {}, expected {}",
+            part,
+            input,
+            answer,
+            expected
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // any file of this crate does as an example, the answer is made up
+    const ANSWERS: &str = "Cargo.toml 1 3\nexamples/b.txt 2 4\n";
+
+    fn check(input: &str, part: u8, answer: u32) -> miette::Result<()> {
+        let day_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+        check_example(ANSWERS, day_dir, &day_dir.join(input), part, |_| Ok(answer))
+    }
+
+    #[test]
+    fn test_check_example() {
+        // the answers are checked before the example is read
+        assert!(check("examples/b.txt", 1, 0).is_ok());
+        assert!(check("examples/c.txt", 1, 0).is_err());
+        assert!(check("Cargo.toml", 1, 3).is_ok());
+        assert!(check("Cargo.toml", 1, 4).is_err());
+    }
+}
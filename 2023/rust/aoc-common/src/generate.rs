@@ -0,0 +1,877 @@
+//! Random, valid puzzle inputs of every day, for cross-checking variants and
+//! benching how they scale. Where the generator builds the input around its
+//! answers, it returns them too.
+
+use std::{
+    collections::HashSet,
+    ops::{Range, RangeInclusive},
+};
+
+use rand::{
+    rngs::StdRng,
+    seq::{index, SliceRandom},
+    Rng, SeedableRng,
+};
+
+use crate::solution::Answer;
+
+/// How big and how full a generated input is.
+#[derive(Debug, Clone, Copy)]
+pub struct Shape {
+    /// About how many lines, or rows of a grid.
+    pub size: usize,
+    /// Share of the cells of a grid that hold something, random if `None`.
+    pub density: Option<f64>,
+    /// Columns of a grid, or length of the seed ranges of day 5, random if
+    /// `None`.
+    pub width: Option<usize>,
+}
+
+impl Shape {
+    pub fn new(size: usize) -> Self {
+        Self {
+            size,
+            density: None,
+            width: None,
+        }
+    }
+
+    fn density(&self, rng: &mut impl Rng, default: Range<f64>) -> f64 {
+        self.density.unwrap_or_else(|| rng.gen_range(default))
+    }
+
+    fn width(&self, rng: &mut impl Rng, default: RangeInclusive<usize>) -> usize {
+        self.width
+            .map_or_else(|| rng.gen_range(default), |width| width.max(1))
+    }
+}
+
+/// A random input and the answers to its parts, where the generator knows
+/// them from how it built the input.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Generated {
+    pub input: String,
+    pub answers: [Option<Answer>; 2],
+}
+
+/// A random, valid puzzle input of `day`, the same for the same state of
+/// `rng`. `None` if the day has no generator.
+pub fn generate(day: u8, shape: Shape, rng: &mut impl Rng) -> Option<Generated> {
+    let shape = Shape {
+        size: shape.size.max(1),
+        ..shape
+    };
+    let generated = match day {
+        1 => calibration_document(shape, rng),
+        2 => games(shape, rng),
+        3 => schematic(shape, rng),
+        4 => scratchcards(shape, rng),
+        5 => almanac(shape, rng),
+        6 => races(shape, rng),
+        7 => hands(shape, rng),
+        8 => network(shape, rng),
+        9 => report(shape, rng),
+        10 => sketch(shape, rng),
+        11 => image(shape, rng),
+        13 => patterns(shape, rng),
+        14 => platform(shape, rng),
+        _ => return None,
+    };
+    Some(generated)
+}
+
+/// Like [`generate`], from a fresh rng seeded with `seed`.
+pub fn generate_seeded(day: u8, shape: Shape, seed: u64) -> Option<Generated> {
+    generate(day, shape, &mut StdRng::seed_from_u64(seed))
+}
+
+const DIGIT_WORDS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+/// Day 1: lines of letters, digits and spelled out digits, each with at
+/// least one real digit. Letters are drawn from the digit words so that
+/// overlapping words like `twone` show up, which leaves part 2 unknown.
+fn calibration_document(shape: Shape, rng: &mut impl Rng) -> Generated {
+    let mut sum = 0;
+    let lines = (0..shape.size)
+        .map(|_| {
+            let mut line = String::new();
+            let mut digits = Vec::new();
+            let digit_at = rng.gen_range(0..8);
+            for i in 0..8 {
+                let digit = match rng.gen_range(0..4) {
+                    _ if i == digit_at => rng.gen_range(1..=9),
+                    0 => {
+                        line.push_str(DIGIT_WORDS.choose(rng).expect("there are digit words"));
+                        continue;
+                    }
+                    1 => rng.gen_range(0..=9),
+                    _ => {
+                        line.push(
+                            *b"efghinorstuvwx".choose(rng).expect("there are letters") as char
+                        );
+                        continue;
+                    }
+                };
+                digits.push(digit);
+                line.push(char::from_digit(digit, 10).expect("digits are below 10"));
+            }
+            sum += digits[0] * 10 + digits[digits.len() - 1];
+            line
+        })
+        .collect::<Vec<_>>();
+    Generated {
+        input: lines.join("\n"),
+        answers: [Some(sum.into()), None],
+    }
+}
+
+/// Day 2: `size` games of up to 5 draws, each showing up to 20 cubes of up
+/// to 3 colors.
+fn games(shape: Shape, rng: &mut impl Rng) -> Generated {
+    let (mut possible, mut power) = (0, 0);
+    let lines = (1..=shape.size)
+        .map(|id| {
+            let mut most = [0; 3];
+            let draws = (0..rng.gen_range(1..=5))
+                .map(|_| {
+                    let mut colors = [0, 1, 2];
+                    colors.shuffle(rng);
+                    colors[..rng.gen_range(1..=3)]
+                        .iter()
+                        .map(|&color| {
+                            let count = rng.gen_range(1..=20);
+                            most[color] = most[color].max(count);
+                            format!("{} {}", count, ["red", "green", "blue"][color])
+                        })
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                })
+                .collect::<Vec<_>>()
+                .join("; ");
+            if most[0] <= 12 && most[1] <= 13 && most[2] <= 14 {
+                possible += id;
+            }
+            power += most[0] * most[1] * most[2];
+            format!("Game {}: {}", id, draws)
+        })
+        .collect::<Vec<_>>();
+    Generated {
+        input: lines.join("\n"),
+        answers: [Some(possible.into()), Some(power.into())],
+    }
+}
+
+/// Day 3: a `size` rows schematic of numbers up to 3 digits and symbols,
+/// `density` of the cells being symbols.
+fn schematic(shape: Shape, rng: &mut impl Rng) -> Generated {
+    let width = shape.width(rng, 1..=shape.size.max(3));
+    let density = shape.density(rng, 0.05..0.2);
+    let rows = (0..shape.size)
+        .map(|_| {
+            let mut row = String::new();
+            while row.len() < width {
+                let digits = rng.gen_range(1..=3);
+                if rng.gen_bool(0.3) && row.len() + digits as usize <= width {
+                    row.push_str(
+                        &rng.gen_range(10u32.pow(digits - 1)..10u32.pow(digits))
+                            .to_string(),
+                    );
+                    if row.len() < width {
+                        row.push('.');
+                    }
+                } else if rng.gen_bool(density) {
+                    row.push(*b"*#+$/@=%&-".choose(rng).expect("there are symbols") as char);
+                } else {
+                    row.push('.');
+                }
+            }
+            row
+        })
+        .collect::<Vec<_>>();
+    Generated {
+        input: rows.join("\n"),
+        answers: [None, None],
+    }
+}
+
+/// Day 4: `size` cards of 5 winning numbers and 8 numbers you have, none of
+/// which wins copies of cards past the end of the pile.
+fn scratchcards(shape: Shape, rng: &mut impl Rng) -> Generated {
+    let mut copies = vec![1; shape.size];
+    let mut points = 0;
+    let lines = (0..shape.size)
+        .map(|i| {
+            let matches = rng.gen_range(0..=5.min(shape.size - 1 - i));
+            let numbers = index::sample(rng, 99, 5 + 8 - matches)
+                .into_iter()
+                .map(|n| n + 1)
+                .collect::<Vec<_>>();
+            let (winning, others) = numbers.split_at(5);
+            let mut have = others.to_vec();
+            have.extend(winning.choose_multiple(rng, matches));
+            have.shuffle(rng);
+
+            if matches > 0 {
+                points += 1 << (matches - 1);
+            }
+            for j in i + 1..=i + matches {
+                copies[j] += copies[i];
+            }
+            let join = |numbers: &[usize]| {
+                numbers
+                    .iter()
+                    .map(|n| format!("{:>2}", n))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            };
+            format!("Card {:>3}: {} | {}", i + 1, join(winning), join(&have))
+        })
+        .collect::<Vec<_>>();
+    Generated {
+        input: lines.join("\n"),
+        answers: [
+            Some(points.into()),
+            Some(copies.iter().sum::<usize>().into()),
+        ],
+    }
+}
+
+const MAP_NAMES: [&str; 7] = [
+    "seed-to-soil",
+    "soil-to-fertilizer",
+    "fertilizer-to-water",
+    "water-to-light",
+    "light-to-temperature",
+    "temperature-to-humidity",
+    "humidity-to-location",
+];
+
+/// Day 5: `size` seed ranges of `width` seeds and 7 maps of up to `size`
+/// non-overlapping source ranges each, all below `10 * size`. The answer to
+/// part 2 maps every seed, so it is only known for up to a million of them.
+fn almanac(shape: Shape, rng: &mut impl Rng) -> Generated {
+    let limit = 10 * shape.size as u64;
+    let seeds = (0..shape.size)
+        .flat_map(|_| [rng.gen_range(0..limit), shape.width(rng, 1..=10) as u64])
+        .collect::<Vec<_>>();
+    let mut almanac = format!(
+        "seeds: {}",
+        seeds
+            .iter()
+            .map(|n| n.to_string())
+            .collect::<Vec<_>>()
+            .join(" ")
+    );
+
+    let mut maps = Vec::new();
+    for name in MAP_NAMES {
+        let mut bounds = (0..2 * rng.gen_range(1..=shape.size))
+            .map(|_| rng.gen_range(0..=limit))
+            .collect::<Vec<_>>();
+        bounds.sort_unstable();
+        bounds.dedup();
+        let mut ranges = bounds
+            .chunks_exact(2)
+            .map(|range| (rng.gen_range(0..limit), range[0], range[1] - range[0]))
+            .collect::<Vec<_>>();
+        ranges.shuffle(rng);
+        almanac.push_str(&format!("\n\n{} map:", name));
+        for (dst, src, len) in &ranges {
+            almanac.push_str(&format!("\n{} {} {}", dst, src, len));
+        }
+        maps.push(ranges);
+    }
+
+    let location = |seed: u64| {
+        maps.iter().fold(seed, |n, ranges| {
+            ranges
+                .iter()
+                .find(|&&(_, src, len)| src <= n && n < src + len)
+                .map_or(n, |&(dst, src, _)| dst + (n - src))
+        })
+    };
+    let part1 = seeds.iter().map(|&seed| location(seed)).min();
+    let part2 = match seeds.chunks_exact(2).map(|range| range[1]).sum::<u64>() {
+        0..=1_000_000 => seeds
+            .chunks_exact(2)
+            .flat_map(|range| range[0]..range[0] + range[1])
+            .map(location)
+            .min(),
+        _ => None,
+    };
+    Generated {
+        input: almanac,
+        answers: [part1.map(Answer::from), part2.map(Answer::from)],
+    }
+}
+
+/// Day 6: up to 4 races of up to 60 milliseconds, each with a record that
+/// can be beaten.
+fn races(shape: Shape, rng: &mut impl Rng) -> Generated {
+    let races = (0..shape.size.min(4))
+        .map(|_| {
+            let time = rng.gen_range(2..=60u64);
+            (time, rng.gen_range(0..time * time / 4))
+        })
+        .collect::<Vec<_>>();
+    let row = |label: &str, values: Vec<u64>| {
+        let values = values
+            .iter()
+            .map(|n| format!("{:>4}", n))
+            .collect::<String>();
+        format!("{:<9}{}", label, values)
+    };
+    let kerned = |values: Vec<u64>| {
+        values
+            .iter()
+            .map(|n| n.to_string())
+            .collect::<String>()
+            .parse::<u64>()
+            .expect("up to 4 numbers of 4 digits fit")
+    };
+    let times = races.iter().map(|&(time, _)| time).collect::<Vec<_>>();
+    let records = races.iter().map(|&(_, record)| record).collect::<Vec<_>>();
+    let part1 = races
+        .iter()
+        .map(|&(time, record)| ways_to_win(time, record))
+        .product::<u64>();
+    let part2 = ways_to_win(kerned(times.clone()), kerned(records.clone()));
+    Generated {
+        input: format!("{}\n{}", row("Time:", times), row("Distance:", records)),
+        answers: [Some(part1.into()), Some(part2.into())],
+    }
+}
+
+/// How many ways of holding the button for a whole number of milliseconds
+/// of `time` beat `record`: those between the shortest winning hold and as
+/// much short of `time`.
+fn ways_to_win(time: u64, record: u64) -> u64 {
+    let (mut lo, mut hi) = (0, time / 2 + 1);
+    while lo < hi {
+        let mid = (lo + hi) / 2;
+        match mid * (time - mid) > record {
+            true => hi = mid,
+            false => lo = mid + 1,
+        }
+    }
+    match lo > time / 2 {
+        true => 0,
+        false => time - 2 * lo + 1,
+    }
+}
+
+/// Day 7: `size` distinct hands with bids up to 1000.
+fn hands(shape: Shape, rng: &mut impl Rng) -> Generated {
+    let mut seen = HashSet::new();
+    let mut lines = Vec::new();
+    while lines.len() < shape.size {
+        let hand = (0..5)
+            .map(|_| *b"AKQJT98765432".choose(rng).expect("there are cards") as char)
+            .collect::<String>();
+        if seen.insert(hand.clone()) {
+            lines.push(format!("{} {}", hand, rng.gen_range(1..=1000)));
+        }
+    }
+    Generated {
+        input: lines.join("\n"),
+        answers: [None, None],
+    }
+}
+
+/// Day 8: up to 4 ghosts, the first from `AAA` to `ZZZ`, each walking a path
+/// of its own that ends on its `Z` node after a prime number of rounds of
+/// the instructions, and starts over from there. The ghosts all end on a `Z`
+/// node together after the product of the primes.
+fn network(shape: Shape, rng: &mut impl Rng) -> Generated {
+    let instructions = (0..rng.gen_range(2..=8))
+        .map(|_| if rng.gen_bool(0.5) { 'L' } else { 'R' })
+        .collect::<Vec<_>>();
+    let mut primes = [2u64, 3, 5, 7, 11, 13];
+    primes.shuffle(rng);
+    let ghosts = (shape.size / 8).clamp(1, 4);
+
+    let mut names = HashSet::from(["AAA".to_string(), "ZZZ".to_string()]);
+    let mut name = |rng: &mut _, last: &[u8]| loop {
+        let name = [
+            *b"ABCDEFGHIJKLMNOPQRSTUVWXYZ"
+                .choose(rng)
+                .expect("there are letters"),
+            *b"ABCDEFGHIJKLMNOPQRSTUVWXYZ"
+                .choose(rng)
+                .expect("there are letters"),
+            *last.choose(rng).expect("there are letters"),
+        ];
+        let name = String::from_utf8(name.to_vec()).expect("names are ASCII");
+        if names.insert(name.clone()) {
+            return name;
+        }
+    };
+
+    let mut nodes = Vec::new();
+    for (ghost, &prime) in primes[..ghosts].iter().enumerate() {
+        let (start, end) = match ghost {
+            0 => ("AAA".to_string(), "ZZZ".to_string()),
+            _ => (name(rng, b"A"), name(rng, b"Z")),
+        };
+        let steps = instructions.len() * prime as usize;
+        let mut path = vec![start];
+        path.extend((1..steps).map(|_| name(rng, b"BCDEFGHIJKLMNOPQRSTUVWXY")));
+        path.push(end);
+
+        let mut next = Vec::new();
+        for (i, instruction) in instructions.iter().cycle().take(steps).enumerate() {
+            let other = path.choose(rng).expect("paths are not empty").clone();
+            next.push(match instruction {
+                'L' => (path[i + 1].clone(), other),
+                _ => (other, path[i + 1].clone()),
+            });
+        }
+        // the end goes on like the start, so the path is a cycle
+        next.push(next[0].clone());
+        nodes.extend(path.into_iter().zip(next));
+    }
+    nodes.shuffle(rng);
+
+    let lines = nodes
+        .iter()
+        .map(|(node, (left, right))| format!("{} = ({}, {})", node, left, right))
+        .collect::<Vec<_>>();
+    let rounds = instructions.len() as u64;
+    Generated {
+        input: format!(
+            "{}\n\n{}",
+            instructions.iter().collect::<String>(),
+            lines.join("\n")
+        ),
+        answers: [
+            Some((rounds * primes[0]).into()),
+            Some((rounds * primes[..ghosts].iter().product::<u64>()).into()),
+        ],
+    }
+}
+
+/// Day 9: `size` histories, each the values of a polynomial of degree up to
+/// 3 with small coefficients, at `0, 1, ...`.
+fn report(shape: Shape, rng: &mut impl Rng) -> Generated {
+    let (mut next, mut previous) = (0, 0);
+    let lines = (0..shape.size)
+        .map(|_| {
+            let degree = rng.gen_range(0..=3);
+            let coefficients = (0..=degree)
+                .map(|_| rng.gen_range(-3..=3))
+                .collect::<Vec<i64>>();
+            let value = |x: i64| {
+                coefficients
+                    .iter()
+                    .rev()
+                    .fold(0, |value, coefficient| value * x + coefficient)
+            };
+            let len = rng.gen_range(degree + 2..=21);
+            next += value(len);
+            previous += value(-1);
+            (0..len)
+                .map(|x| value(x).to_string())
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect::<Vec<_>>();
+    Generated {
+        input: lines.join("\n"),
+        answers: [Some(next.into()), Some(previous.into())],
+    }
+}
+
+/// Day 10: a `size` rows sketch whose loop runs around a random polyomino
+/// of up to `density` of the cells between the tiles, with junk pipes on
+/// some of the other tiles.
+///
+/// The polyomino has no holes and none of its cells only touch at a corner,
+/// so its boundary is a single loop through the tiles. A loop of `b` tiles
+/// around `a` cells encloses `a - b / 2 + 1` tiles, by Pick's theorem.
+fn sketch(shape: Shape, rng: &mut impl Rng) -> Generated {
+    let rows = shape.size.max(2) - 1;
+    let cols = shape.width(rng, 1..=rows.max(2));
+    let target = ((shape.density(rng, 0.2..0.7) * (rows * cols) as f64) as usize).max(1);
+    let mut cells = vec![vec![false; cols]; rows];
+    cells[rng.gen_range(0..rows)][rng.gen_range(0..cols)] = true;
+    let mut area = 1;
+    for _ in 0..4 * target {
+        if area == target {
+            break;
+        }
+        let frontier = (0..rows)
+            .flat_map(|r| (0..cols).map(move |c| (r, c)))
+            .filter(|&(r, c)| !cells[r][c] && touches(&cells, r, c))
+            .collect::<Vec<_>>();
+        let Some(&(r, c)) = frontier.choose(rng) else {
+            break;
+        };
+        cells[r][c] = true;
+        if has_pinch(&cells, r, c) || has_hole(&cells) {
+            cells[r][c] = false;
+        } else {
+            area += 1;
+        }
+    }
+
+    // tile (r, c) is the corner above and left of cell (r, c)
+    let inside = |r: usize, c: usize| {
+        (1..=rows).contains(&r) && (1..=cols).contains(&c) && cells[r - 1][c - 1]
+    };
+    let mut tiles = vec![vec!['.'; cols + 1]; rows + 1];
+    let mut looop = Vec::new();
+    for (r, row) in tiles.iter_mut().enumerate() {
+        for (c, tile) in row.iter_mut().enumerate() {
+            let (r, c) = (r + 1, c + 1);
+            let up = inside(r - 1, c - 1) != inside(r - 1, c);
+            let down = inside(r, c - 1) != inside(r, c);
+            let left = inside(r - 1, c - 1) != inside(r, c - 1);
+            let right = inside(r - 1, c) != inside(r, c);
+            *tile = match (up, down, left, right) {
+                (true, true, false, false) => '|',
+                (false, false, true, true) => '-',
+                (true, false, false, true) => 'L',
+                (true, false, true, false) => 'J',
+                (false, true, true, false) => '7',
+                (false, true, false, true) => 'F',
+                _ => continue,
+            };
+            looop.push((r - 1, c - 1));
+        }
+    }
+    let junk = rng.gen_range(0.0..0.7);
+    for row in tiles.iter_mut() {
+        for tile in row.iter_mut().filter(|tile| **tile == '.') {
+            if rng.gen_bool(junk) {
+                *tile = *b"|-LJ7F".choose(rng).expect("there are pipes") as char;
+            }
+        }
+    }
+
+    // no junk pipe may lead into the start
+    let &(r, c) = looop.choose(rng).expect("the polyomino has a boundary");
+    tiles[r][c] = 'S';
+    let neighbors = [(r.wrapping_sub(1), c, "|7F"), (r + 1, c, "|LJ")];
+    let neighbors = neighbors
+        .into_iter()
+        .chain([(r, c.wrapping_sub(1), "-LF"), (r, c + 1, "-J7")]);
+    for (r, c, into_start) in neighbors {
+        if let Some(tile) = tiles.get_mut(r).and_then(|row| row.get_mut(c)) {
+            if !looop.contains(&(r, c)) && into_start.contains(*tile) {
+                *tile = '.';
+            }
+        }
+    }
+
+    let input = tiles
+        .iter()
+        .map(|row| row.iter().collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n");
+    Generated {
+        input,
+        answers: [
+            Some((looop.len() / 2).into()),
+            Some((area + 1 - looop.len() / 2).into()),
+        ],
+    }
+}
+
+/// Whether cell `(r, c)` shares a side with a cell of the polyomino.
+fn touches(cells: &[Vec<bool>], r: usize, c: usize) -> bool {
+    [
+        (r.wrapping_sub(1), c),
+        (r + 1, c),
+        (r, c.wrapping_sub(1)),
+        (r, c + 1),
+    ]
+    .iter()
+    .any(|&(r, c)| cells.get(r).and_then(|row| row.get(c)) == Some(&true))
+}
+
+/// Whether a 2x2 block around cell `(r, c)` holds cells of the polyomino
+/// that only touch at a corner.
+fn has_pinch(cells: &[Vec<bool>], r: usize, c: usize) -> bool {
+    let at = |r: usize, c: usize| cells.get(r).and_then(|row| row.get(c)) == Some(&true);
+    [
+        (r.wrapping_sub(1), c.wrapping_sub(1)),
+        (r.wrapping_sub(1), c),
+        (r, c.wrapping_sub(1)),
+        (r, c),
+    ]
+    .iter()
+    .any(|&(r, c)| {
+        let block = [
+            at(r, c),
+            at(r, c.wrapping_add(1)),
+            at(r.wrapping_add(1), c),
+            at(r.wrapping_add(1), c.wrapping_add(1)),
+        ];
+        block == [true, false, false, true] || block == [false, true, true, false]
+    })
+}
+
+/// Whether some cell outside the polyomino is cut off from the border.
+fn has_hole(cells: &[Vec<bool>]) -> bool {
+    let (rows, cols) = (cells.len(), cells[0].len());
+    // a frame of outside cells around the grid
+    let mut seen = vec![vec![false; cols + 2]; rows + 2];
+    let mut stack = vec![(0usize, 0usize)];
+    seen[0][0] = true;
+    while let Some((r, c)) = stack.pop() {
+        for (r, c) in [
+            (r.wrapping_sub(1), c),
+            (r + 1, c),
+            (r, c.wrapping_sub(1)),
+            (r, c + 1),
+        ] {
+            if r > rows + 1 || c > cols + 1 || seen[r][c] {
+                continue;
+            }
+            let inside = (1..=rows).contains(&r) && (1..=cols).contains(&c) && cells[r - 1][c - 1];
+            if !inside {
+                seen[r][c] = true;
+                stack.push((r, c));
+            }
+        }
+    }
+    (0..rows).any(|r| (0..cols).any(|c| !cells[r][c] && !seen[r + 1][c + 1]))
+}
+
+/// Day 11: a `size` rows image `width` wide, `density` of it galaxies.
+/// The answers sum the distances of every pair once empty rows and columns
+/// are expanded.
+fn image(shape: Shape, rng: &mut impl Rng) -> Generated {
+    let width = shape.width(rng, 1..=shape.size);
+    let density = shape.density(rng, 0.05..0.3);
+    let rows = (0..shape.size)
+        .map(|_| {
+            (0..width)
+                .map(|_| rng.gen_bool(density))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let galaxies = (0..shape.size)
+        .flat_map(|r| (0..width).map(move |c| (r, c)))
+        .filter(|&(r, c)| rows[r][c])
+        .collect::<Vec<_>>();
+    let empty_rows = (0..shape.size)
+        .filter(|&r| !rows[r].contains(&true))
+        .collect::<Vec<_>>();
+    let empty_cols = (0..width)
+        .filter(|&c| rows.iter().all(|row| !row[c]))
+        .collect::<Vec<_>>();
+    let distances = |factor: usize| {
+        let expand =
+            |n: usize, empty: &[usize]| n + (factor - 1) * empty.partition_point(|&e| e < n);
+        let galaxies = galaxies
+            .iter()
+            .map(|&(r, c)| (expand(r, &empty_rows), expand(c, &empty_cols)))
+            .collect::<Vec<_>>();
+        galaxies
+            .iter()
+            .enumerate()
+            .flat_map(|(i, p)| galaxies[i + 1..].iter().map(move |q| (p, q)))
+            .map(|(p, q)| p.0.abs_diff(q.0) + p.1.abs_diff(q.1))
+            .sum::<usize>()
+    };
+
+    let input = rows
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|&galaxy| if galaxy { '#' } else { '.' })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    Generated {
+        input,
+        answers: [Some(distances(2).into()), Some(distances(1_000_000).into())],
+    }
+}
+
+/// Day 13: a pattern for every 8 lines of `size`, each with a single line of
+/// reflection and a single other line that is one smudge away from one.
+///
+/// The rows are mirrored across the perfect line near the top, and every row
+/// across the smudged line between the columns. The smudge is in a row below
+/// the rows mirrored across the perfect line, so it only breaks the other.
+/// Half of the patterns are transposed.
+fn patterns(shape: Shape, rng: &mut impl Rng) -> Generated {
+    let mut answers = [0, 0];
+    let patterns = (0..(shape.size / 8).max(1))
+        .map(|_| loop {
+            let (height, width) = (rng.gen_range(5..=15), rng.gen_range(5..=15));
+            let (perfect, smudged) = (rng.gen_range(1..=(height - 1) / 2), rng.gen_range(1..width));
+            let span = smudged.min(width - smudged);
+            let mut rows = (0..height)
+                .map(|_| {
+                    let mut row = (0..width).map(|_| rng.gen_bool(0.5)).collect::<Vec<_>>();
+                    for c in 0..span {
+                        row[smudged + c] = row[smudged - 1 - c];
+                    }
+                    row
+                })
+                .collect::<Vec<_>>();
+            for r in 0..perfect {
+                rows[perfect - 1 - r] = rows[perfect + r].clone();
+            }
+            let (r, c) = (
+                rng.gen_range(2 * perfect..height),
+                rng.gen_range(smudged - span..smudged + span),
+            );
+            rows[r][c] = !rows[r][c];
+
+            let transposed = rng.gen_bool(0.5);
+            if transposed {
+                rows = (0..width)
+                    .map(|c| rows.iter().map(|row| row[c]).collect())
+                    .collect();
+            }
+            let lines = mirror_lines(&rows);
+            if lines != [vec![(perfect, transposed)], vec![(smudged, !transposed)]] {
+                continue;
+            }
+            for (answer, (line, across_columns)) in
+                answers.iter_mut().zip([lines[0][0], lines[1][0]])
+            {
+                *answer += if across_columns { line } else { 100 * line };
+            }
+            break rows
+                .iter()
+                .map(|row| {
+                    row.iter()
+                        .map(|&rock| if rock { '#' } else { '.' })
+                        .collect::<String>()
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+        })
+        .collect::<Vec<_>>();
+    Generated {
+        input: patterns.join("\n\n"),
+        answers: answers.map(|answer| Some(answer.into())),
+    }
+}
+
+/// The lines of `rows` that mirror it perfectly, and those that are one
+/// difference away from it, each as how many rows or columns come before it
+/// and whether it runs between columns.
+fn mirror_lines(rows: &[Vec<bool>]) -> [Vec<(usize, bool)>; 2] {
+    let columns = (0..rows[0].len())
+        .map(|c| rows.iter().map(|row| row[c]).collect())
+        .collect::<Vec<Vec<bool>>>();
+    let lines = differences(rows)
+        .map(|(line, count)| (line, false, count))
+        .chain(differences(&columns).map(|(line, count)| (line, true, count)))
+        .collect::<Vec<_>>();
+    [0, 1].map(|wanted| {
+        lines
+            .iter()
+            .filter(|&&(_, _, count)| count == wanted)
+            .map(|&(line, across_columns, _)| (line, across_columns))
+            .collect()
+    })
+}
+
+/// How many cells differ from their mirror image across each line between
+/// `rows`, with the number of rows above it.
+fn differences(rows: &[Vec<bool>]) -> impl Iterator<Item = (usize, usize)> + '_ {
+    (1..rows.len()).map(|line| {
+        let count = rows[..line]
+            .iter()
+            .rev()
+            .zip(&rows[line..])
+            .map(|(a, b)| a.iter().zip(b).filter(|(a, b)| a != b).count())
+            .sum();
+        (line, count)
+    })
+}
+
+/// Day 14: a `size` rows platform `width` wide, `density` of its cells
+/// holding rocks, more of them rounded than cube-shaped. The answer to part 1
+/// is the load once every rounded rock rolled north.
+fn platform(shape: Shape, rng: &mut impl Rng) -> Generated {
+    let width = shape.width(rng, 1..=shape.size);
+    let density = shape.density(rng, 0.2..0.6);
+    let rows = (0..shape.size)
+        .map(|_| {
+            (0..width)
+                .map(|_| match rng.gen::<f64>() {
+                    p if p < 0.6 * density => 'O',
+                    p if p < density => '#',
+                    _ => '.',
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let mut load = 0;
+    for c in 0..width {
+        let mut free = 0;
+        for (r, row) in rows.iter().enumerate() {
+            match row[c] {
+                '#' => free = r + 1,
+                'O' => {
+                    load += shape.size - free;
+                    free += 1;
+                }
+                _ => {}
+            }
+        }
+    }
+    let input = rows
+        .iter()
+        .map(|row| row.iter().collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n");
+    Generated {
+        input,
+        answers: [Some(load.into()), None],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_density() {
+        let shape = Shape {
+            density: Some(0.0),
+            ..Shape::new(16)
+        };
+        let generated = generate_seeded(11, shape, 1).unwrap();
+        assert!(!generated.input.contains('#'));
+        assert_eq!(Some(Answer::from(0usize)), generated.answers[0]);
+    }
+
+    #[test]
+    fn test_width() {
+        let shape = Shape {
+            width: Some(7),
+            ..Shape::new(4)
+        };
+        let image = generate_seeded(11, shape, 1).unwrap();
+        assert!(image.input.lines().all(|row| row.len() == 7));
+
+        let almanac = generate_seeded(5, shape, 1).unwrap();
+        let seeds = almanac.input.lines().next().unwrap();
+        assert!(seeds.split(' ').skip(2).step_by(2).all(|len| len == "7"));
+    }
+
+    #[test]
+    fn test_unknown_answers() {
+        let shape = Shape {
+            width: Some(1_000_000),
+            ..Shape::new(2)
+        };
+        let almanac = generate_seeded(5, shape, 1).unwrap();
+        assert!(almanac.answers[0].is_some());
+        assert_eq!(None, almanac.answers[1]);
+    }
+}
@@ -0,0 +1,36 @@
+use std::{env, fs, io::Read, path::Path};
+
+use crate::errors::AocError;
+
+/// Environment variable naming the input file to use instead of `input.txt`.
+pub const INPUT_ENV: &str = "AOC_INPUT";
+
+/// Reads the puzzle input from `path` (`-` for stdin), falling back to
+/// `$AOC_INPUT` and then to the `bundled` input of a day crate.
+pub fn read_input(path: Option<String>, bundled: &Path) -> miette::Result<String, AocError> {
+    let path = path
+        .or_else(|| env::var(INPUT_ENV).ok())
+        .unwrap_or_else(|| bundled.display().to_string());
+
+    if path == "-" {
+        let mut input = String::new();
+        std::io::stdin().read_to_string(&mut input)?;
+        Ok(input)
+    } else {
+        Ok(fs::read_to_string(path)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_missing_input() {
+        let result = read_input(
+            Some("does-not-exist.txt".to_string()),
+            Path::new("input.txt"),
+        );
+        assert!(matches!(result, Err(AocError::IoError(_))));
+    }
+}
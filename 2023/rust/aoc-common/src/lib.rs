@@ -0,0 +1,15 @@
+pub mod answers;
+pub mod bench;
+pub mod budget;
+pub mod check;
+pub mod errors;
+pub mod examples;
+#[cfg(feature = "generate")]
+pub mod generate;
+pub mod input;
+#[cfg(feature = "parallel")]
+pub mod parallel;
+pub mod parse;
+pub mod solution;
+pub mod stream;
+pub mod trace;
@@ -0,0 +1,54 @@
+use rayon::prelude::*;
+
+use crate::{
+    errors::AocError,
+    parse::{finish, lines, IResult},
+};
+
+/// Like [`finish`] with [`lines`], but parses every line on its own on the
+/// rayon thread pool. If a line does not parse, the whole input is parsed
+/// again with [`lines`] for the same diagnostic.
+pub fn finish_lines<'a, T: Send>(
+    input: &'a str,
+    line: impl Fn(&'a str) -> IResult<'a, T> + Sync,
+) -> miette::Result<Vec<T>, AocError> {
+    let parsed = input
+        .trim_end()
+        .lines()
+        .collect::<Vec<_>>()
+        .into_par_iter()
+        .map(|text| match line(text) {
+            Ok(("", parsed)) => Some(parsed),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>();
+    match parsed {
+        Some(parsed) if !parsed.is_empty() => Ok(parsed),
+        _ => finish(input, lines(&line)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::parse::numbers;
+
+    #[test]
+    fn test_finish_lines() -> miette::Result<()> {
+        let input = "1 2\n3\n4 5 6\n\n";
+        assert_eq!(
+            finish(input, lines(numbers::<u32>))?,
+            finish_lines(input, numbers::<u32>)?
+        );
+        for input in ["", "1 2\nx\n3 y\n", "1\n\n2\n"] {
+            assert_eq!(
+                finish(input, lines(numbers::<u32>))
+                    .unwrap_err()
+                    .to_string(),
+                finish_lines(input, numbers::<u32>).unwrap_err().to_string()
+            );
+        }
+        Ok(())
+    }
+}
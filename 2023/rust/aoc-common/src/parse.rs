@@ -0,0 +1,429 @@
+use std::{any::type_name, fmt, str::FromStr};
+
+use nom::{
+    character::complete::{char, digit1, line_ending, space1},
+    combinator::{all_consuming, cut, opt, recognize},
+    error::{ContextError, ErrorKind, ParseError},
+    multi::separated_list1,
+    sequence::{pair, terminated},
+    Finish,
+};
+
+use crate::errors::{span_of, AocError};
+
+/// Result of the nom parsers of the puzzle grammars.
+pub type IResult<'a, T> = nom::IResult<&'a str, T, Error<'a>>;
+
+/// Where a parser failed and what it would have accepted there.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Error<'a> {
+    /// The input left at the failure.
+    pub rest: &'a str,
+    expected: Expected,
+    /// What else `alt` would have accepted at the same spot.
+    alternatives: Vec<Expected>,
+}
+
+/// What a parser expected, kept cheap since most errors are backtracked over.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Expected {
+    Kind(ErrorKind),
+    Char(char),
+    Tag(&'static str),
+    OneOf(&'static str),
+    /// A number that fits in the named type.
+    Fits(&'static str),
+    /// A description, e.g. from `nom::error::context`.
+    Text(&'static str),
+}
+
+impl fmt::Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expected::Kind(ErrorKind::Digit) => write!(f, "a number"),
+            Expected::Kind(ErrorKind::Alpha) => write!(f, "a letter"),
+            Expected::Kind(ErrorKind::AlphaNumeric) => write!(f, "a letter or digit"),
+            Expected::Kind(ErrorKind::Space) => write!(f, "a space"),
+            Expected::Kind(ErrorKind::MultiSpace) => write!(f, "whitespace"),
+            Expected::Kind(ErrorKind::CrLf) => write!(f, "a line break"),
+            Expected::Kind(ErrorKind::Eof) => write!(f, "the end of the input"),
+            Expected::Kind(kind) => write!(f, "{}", kind.description().to_lowercase()),
+            Expected::Char(ch) => write!(f, "`{}`", ch),
+            Expected::Tag(tag) => write!(f, "`{}`", tag),
+            Expected::OneOf(chars) => write!(f, "one of `{}`", chars),
+            Expected::Fits(ty) => write!(f, "a number that fits in {}", ty),
+            Expected::Text(text) => write!(f, "{}", text),
+        }
+    }
+}
+
+impl<'a> Error<'a> {
+    pub fn new(rest: &'a str, expected: Expected) -> Self {
+        Self {
+            rest,
+            expected,
+            alternatives: Vec::new(),
+        }
+    }
+
+    /// Everything that would have been accepted at the failure.
+    pub fn expected(&self) -> impl Iterator<Item = Expected> + '_ {
+        std::iter::once(self.expected).chain(self.alternatives.iter().copied())
+    }
+
+    /// 1-based line and column of the failure in `input`, which `rest` must
+    /// be a slice of.
+    pub fn location(&self, input: &str) -> (usize, usize) {
+        let before = &input[..span_of(input, self.rest).offset()];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        (
+            before.matches('\n').count() + 1,
+            before[line_start..].chars().count() + 1,
+        )
+    }
+
+    /// The diagnostic of the failure, labeling the word it happened at.
+    pub fn into_aoc_error(self, input: &str) -> AocError {
+        self.into_line_error(input, 1)
+    }
+
+    /// Like [`Error::into_aoc_error`], for an `input` that starts on line
+    /// `first_line` of the whole input, e.g. a line read from a stream.
+    pub fn into_line_error(self, input: &str, first_line: usize) -> AocError {
+        let (line, column) = self.location(input);
+        let word = self
+            .rest
+            .find(|ch: char| !ch.is_alphanumeric())
+            .unwrap_or(self.rest.len());
+        let word = match word {
+            0 => self.rest.chars().next().map_or(0, char::len_utf8),
+            word => word,
+        };
+        let message = format!(
+            "{} at line {}, column {}",
+            self,
+            line + first_line - 1,
+            column
+        );
+        AocError::parse_error(input, &self.rest[..word], message)
+    }
+}
+
+impl fmt::Display for Error<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "expected {}", self.expected)?;
+        for (i, expected) in self.alternatives.iter().enumerate() {
+            match i + 1 == self.alternatives.len() {
+                true => write!(f, " or {}", expected)?,
+                false => write!(f, ", {}", expected)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'a> ParseError<&'a str> for Error<'a> {
+    fn from_error_kind(input: &'a str, kind: ErrorKind) -> Self {
+        Self::new(input, Expected::Kind(kind))
+    }
+
+    fn append(_: &'a str, _: ErrorKind, other: Self) -> Self {
+        other
+    }
+
+    fn from_char(input: &'a str, ch: char) -> Self {
+        Self::new(input, Expected::Char(ch))
+    }
+
+    /// Keeps the branch that got furthest, or what both expected if they
+    /// failed at the same spot.
+    fn or(mut self, other: Self) -> Self {
+        match self.rest.len().cmp(&other.rest.len()) {
+            std::cmp::Ordering::Less => self,
+            std::cmp::Ordering::Greater => other,
+            std::cmp::Ordering::Equal => {
+                for expected in other.expected() {
+                    if !self.expected().any(|known| known == expected) {
+                        self.alternatives.push(expected);
+                    }
+                }
+                self
+            }
+        }
+    }
+}
+
+impl<'a> ContextError<&'a str> for Error<'a> {
+    /// Names what was expected after the fact, unless the parser got past
+    /// its first token and already knows better.
+    fn add_context(input: &'a str, context: &'static str, other: Self) -> Self {
+        if input.len() == other.rest.len() {
+            Self::new(input, Expected::Text(context))
+        } else {
+            other
+        }
+    }
+}
+
+/// The diagnostic of a parser that failed on `input` or on a slice of it.
+pub fn into_aoc_error(err: nom::Err<Error<'_>>, input: &str) -> AocError {
+    match err {
+        nom::Err::Error(err) | nom::Err::Failure(err) => err.into_aoc_error(input),
+        nom::Err::Incomplete(_) => {
+            Error::new(&input[input.len()..], Expected::Text("more input")).into_aoc_error(input)
+        }
+    }
+}
+
+/// Runs `parser` on the whole of `input`, but for trailing whitespace.
+pub fn finish<'a, T>(
+    input: &'a str,
+    parser: impl FnMut(&'a str) -> IResult<'a, T>,
+) -> miette::Result<T, AocError> {
+    all_consuming(parser)(input.trim_end())
+        .finish()
+        .map(|(_, parsed)| parsed)
+        .map_err(|err| err.into_aoc_error(input))
+}
+
+/// Runs `parser` on the whole of `line`, the line `number` of an input
+/// that is read a line at a time.
+pub fn finish_line<'a, T>(
+    line: &'a str,
+    number: usize,
+    parser: impl FnMut(&'a str) -> IResult<'a, T>,
+) -> miette::Result<T, AocError> {
+    terminated(parser, end_of_line)(line)
+        .finish()
+        .map(|(_, parsed)| parsed)
+        .map_err(|err| err.into_line_error(line, number))
+}
+
+/// Matches `keyword` literally.
+pub fn tag<'a>(keyword: &'static str) -> impl FnMut(&'a str) -> IResult<'a, &'a str> {
+    move |input: &'a str| match input.strip_prefix(keyword) {
+        Some(rest) => Ok((rest, &input[..keyword.len()])),
+        None => Err(nom::Err::Error(Error::new(input, Expected::Tag(keyword)))),
+    }
+}
+
+/// One of the characters of `chars`.
+pub fn one_of<'a>(chars: &'static str) -> impl FnMut(&'a str) -> IResult<'a, char> {
+    move |input: &'a str| match input.chars().next() {
+        Some(ch) if chars.contains(ch) => Ok((&input[ch.len_utf8()..], ch)),
+        _ => Err(nom::Err::Error(Error::new(input, Expected::OneOf(chars)))),
+    }
+}
+
+/// A decimal number, negative if `T` allows it. Digits that do not fit in
+/// `T` are a failure rather than a different branch.
+pub fn number<'a, T: FromStr>(input: &'a str) -> IResult<'a, T> {
+    let (rest, digits) =
+        recognize(pair(opt(char('-')), digit1))(input).map_err(|_: nom::Err<Error>| {
+            nom::Err::Error(Error::new(input, Expected::Kind(ErrorKind::Digit)))
+        })?;
+    match digits.parse() {
+        Ok(number) => Ok((rest, number)),
+        Err(_) => Err(nom::Err::Failure(Error::new(
+            input,
+            Expected::Fits(type_name::<T>()),
+        ))),
+    }
+}
+
+/// Numbers separated by spaces.
+pub fn numbers<'a, T: FromStr>(input: &'a str) -> IResult<'a, Vec<T>> {
+    separated_list1(space1, number)(input)
+}
+
+/// Succeeds at a line break or the end of the input, without consuming it.
+pub fn end_of_line(input: &str) -> IResult<'_, ()> {
+    if input.is_empty() || input.starts_with('\n') || input.starts_with("\r\n") {
+        Ok((input, ()))
+    } else {
+        Err(nom::Err::Error(Error::new(
+            input,
+            Expected::Text("the end of the line"),
+        )))
+    }
+}
+
+/// A blank line between two sections.
+pub fn blank_line(input: &str) -> IResult<'_, ()> {
+    let (rest, _) = pair(line_ending, line_ending)(input)?;
+    Ok((rest, ()))
+}
+
+/// `line` on consecutive lines, each of which it must consume whole. A blank
+/// line or the end of the input ends them.
+pub fn lines<'a, T>(
+    line: impl FnMut(&'a str) -> IResult<'a, T>,
+) -> impl FnMut(&'a str) -> IResult<'a, Vec<T>> {
+    separated_list1(line_break, cut(terminated(line, end_of_line)))
+}
+
+/// Like [`lines`], but folds every line into `init` with `fold` instead of
+/// collecting them.
+pub fn fold_lines<'a, T, R>(
+    line: impl FnMut(&'a str) -> IResult<'a, T>,
+    mut init: impl FnMut() -> R,
+    mut fold: impl FnMut(R, T) -> R,
+) -> impl FnMut(&'a str) -> IResult<'a, R> {
+    let mut line = cut(terminated(line, end_of_line));
+    move |input: &'a str| {
+        let (mut rest, first) = line(input)?;
+        let mut folded = fold(init(), first);
+        while let Ok((next, ())) = line_break(rest) {
+            let parsed;
+            (rest, parsed) = line(next)?;
+            folded = fold(folded, parsed);
+        }
+        Ok((rest, folded))
+    }
+}
+
+/// A line break followed by another line.
+fn line_break(input: &str) -> IResult<'_, ()> {
+    let rest = input
+        .strip_prefix('\n')
+        .or_else(|| input.strip_prefix("\r\n"))
+        .filter(|rest| !rest.is_empty() && !rest.starts_with(['\r', '\n']));
+    match rest {
+        Some(rest) => Ok((rest, ())),
+        None => Err(nom::Err::Error(Error::new(
+            input,
+            Expected::Kind(ErrorKind::CrLf),
+        ))),
+    }
+}
+
+/// Rows of a grid whose cells are among `cells`.
+pub fn grid<'a>(cells: &'static str) -> impl FnMut(&'a str) -> IResult<'a, Vec<&'a str>> {
+    lines(row(cells))
+}
+
+/// The rest of the line, which must be made of `cells` only.
+pub fn row<'a>(cells: &'static str) -> impl FnMut(&'a str) -> IResult<'a, &'a str> {
+    move |input: &'a str| {
+        let end = input.find(['\r', '\n']).unwrap_or(input.len());
+        match input[..end].find(|cell| !cells.contains(cell)) {
+            None if end > 0 => Ok((&input[end..], &input[..end])),
+            bad => Err(nom::Err::Error(Error::new(
+                &input[bad.unwrap_or(0)..],
+                Expected::OneOf(cells),
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use nom::{
+        branch::alt,
+        sequence::{preceded, separated_pair},
+    };
+
+    fn card(input: &str) -> IResult<'_, (u32, Vec<u32>)> {
+        separated_pair(preceded(tag("Card "), number), tag(": "), numbers)(input)
+    }
+
+    #[test]
+    fn test_lines() -> miette::Result<()> {
+        let input = "Card 1: 41 48\nCard 2: 13\n";
+        assert_eq!(
+            vec![(1, vec![41, 48]), (2, vec![13])],
+            finish(input, lines(card))?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_fold_lines() -> miette::Result<()> {
+        let input = "Card 1: 41 48\nCard 2: 13\n";
+        let ids = fold_lines(card, Vec::new, |mut ids, (id, _)| {
+            ids.push(id);
+            ids
+        });
+        assert_eq!(vec![1, 2], finish(input, ids)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_error_location() {
+        let input = "Card 1: 41 48\nCard 2: 13 x2\n";
+        let err = lines(card)(input.trim_end()).unwrap_err();
+        let nom::Err::Failure(err) = err else {
+            panic!("expected a failure, got {:?}", err);
+        };
+        assert_eq!((2, 11), err.location(input));
+        assert_eq!("expected the end of the line", err.to_string());
+        match err.into_aoc_error(input) {
+            AocError::ParseError { message, span, .. } => {
+                assert_eq!("expected the end of the line at line 2, column 11", message);
+                assert_eq!((24, 1), (span.offset(), span.len()));
+            }
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_finish_line() {
+        assert_eq!(
+            Ok((7, vec![1, 2])),
+            finish_line("Card 7: 1 2", 3, card).map_err(|_| ())
+        );
+        match finish_line("Card 7: 1 x", 3, card) {
+            Err(AocError::ParseError { message, span, .. }) => {
+                assert_eq!("expected the end of the line at line 3, column 10", message);
+                assert_eq!(9, span.offset());
+            }
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_alternatives() {
+        let input = "purple";
+        match finish(input, alt((tag("red"), tag("green"), tag("blue")))) {
+            Err(AocError::ParseError { message, span, .. }) => {
+                assert_eq!(
+                    "expected `red`, `green` or `blue` at line 1, column 1",
+                    message
+                );
+                assert_eq!((0, 6), (span.offset(), span.len()));
+            }
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_number_overflow() {
+        let input = "seeds: 79 99999999999";
+        match finish(input, preceded(tag("seeds: "), numbers::<u32>)) {
+            Err(AocError::ParseError { message, span, .. }) => {
+                assert_eq!(
+                    "expected a number that fits in u32 at line 1, column 11",
+                    message
+                );
+                assert_eq!((10, 11), (span.offset(), span.len()));
+            }
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_grid_sections() -> miette::Result<()> {
+        let input = "#.\n.#\n\n##\n";
+        let patterns = separated_list1(blank_line, grid(".#"));
+        assert_eq!(vec![vec!["#.", ".#"], vec!["##"]], finish(input, patterns)?);
+        match finish("#.\n.O", grid(".#")) {
+            Err(AocError::ParseError { message, .. }) => {
+                assert_eq!("expected one of `.#` at line 2, column 2", message)
+            }
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,133 @@
+use std::fmt;
+
+use crate::errors::AocError;
+
+/// A puzzle of one day: how to parse its input and how to solve both parts.
+pub trait Solution {
+    /// Day of December the puzzle was released on.
+    const DAY: u8;
+
+    /// Parsed puzzle input of both parts. May borrow from the raw input.
+    type Input<'a>;
+
+    /// Knobs of the puzzle that are not part of the input, e.g. the
+    /// expansion factor of day 11.
+    type Params: Default;
+
+    fn parse(input: &str) -> miette::Result<Self::Input<'_>, AocError>;
+
+    fn part1(input: &Self::Input<'_>, params: &Self::Params) -> miette::Result<Answer, AocError>;
+
+    fn part2(input: &Self::Input<'_>, params: &Self::Params) -> miette::Result<Answer, AocError>;
+
+    /// Every way `input` breaks the structure the puzzle assumes, without
+    /// solving it. Only whether it parses, unless the day knows better.
+    fn check(input: &str) -> Vec<AocError> {
+        Self::parse(input).err().into_iter().collect()
+    }
+
+    /// Parses `input` and solves `part` with the default parameters.
+    fn solve(input: &str, part: u8) -> miette::Result<Answer, AocError> {
+        Self::solve_with(input, part, &Self::Params::default())
+    }
+
+    /// Parses `input` and solves `part` with `params`.
+    fn solve_with(
+        input: &str,
+        part: u8,
+        params: &Self::Params,
+    ) -> miette::Result<Answer, AocError> {
+        let parsed = Self::parse(input)?;
+        match part {
+            1 => Self::part1(&parsed, params),
+            2 => Self::part2(&parsed, params),
+            _ => Err(AocError::UnknownPart(part)),
+        }
+    }
+}
+
+/// The answer to a part, whatever integer type the solver computed it in.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Number(i128),
+    Text(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+macro_rules! from_number {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Answer {
+            fn from(n: $t) -> Self {
+                Answer::Number(n as i128)
+            }
+        })*
+    };
+}
+
+from_number!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Text(s.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Sum;
+
+    impl Solution for Sum {
+        const DAY: u8 = 0;
+        type Input<'a> = Vec<i32>;
+        type Params = ();
+
+        fn parse(input: &str) -> miette::Result<Self::Input<'_>, AocError> {
+            crate::parse::finish(input, crate::parse::numbers)
+        }
+
+        fn part1(input: &Self::Input<'_>, _: &()) -> miette::Result<Answer, AocError> {
+            Ok(input.iter().sum::<i32>().into())
+        }
+
+        fn part2(input: &Self::Input<'_>, _: &()) -> miette::Result<Answer, AocError> {
+            Ok(input
+                .iter()
+                .map(|n| n.to_string())
+                .collect::<String>()
+                .into())
+        }
+    }
+
+    #[test]
+    fn test_solve() -> miette::Result<()> {
+        assert_eq!(Answer::Number(-2), Sum::solve("1 -3", 1)?);
+        assert_eq!("1-3", Sum::solve("1 -3", 2)?.to_string());
+        assert!(matches!(
+            Sum::solve("1 -3", 3),
+            Err(AocError::UnknownPart(3))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_answer_display() {
+        assert_eq!("18446744073709551615", Answer::from(u64::MAX).to_string());
+        assert_eq!("-7", Answer::from(-7i32).to_string());
+    }
+}
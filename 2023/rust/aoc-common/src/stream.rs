@@ -0,0 +1,186 @@
+use std::{fmt::Debug, fs, io::BufRead, path::Path};
+
+use crate::{answers::Answers, errors::AocError};
+
+/// Calls `line` with every line of `reader`, without its line break, and its
+/// 1-based number. A single buffer is reused, so memory stays bounded by the
+/// longest line however long the input is.
+///
+/// Trailing blank lines are skipped, like [`crate::parse::finish`] does, but
+/// a blank line in the middle of the input or an empty input is an error.
+pub fn for_each_line<R: BufRead>(
+    mut reader: R,
+    mut line: impl FnMut(usize, &str) -> miette::Result<(), AocError>,
+) -> miette::Result<(), AocError> {
+    let mut buf = String::new();
+    let mut number = 0;
+    let mut blank = None;
+    let mut empty = true;
+    loop {
+        buf.clear();
+        if reader.read_line(&mut buf)? == 0 {
+            break;
+        }
+        number += 1;
+        let text = buf.trim_end_matches(['\n', '\r']);
+        if text.trim().is_empty() {
+            blank.get_or_insert(number);
+            continue;
+        }
+        if let Some(blank) = blank {
+            return Err(AocError::parse_error(
+                "",
+                "",
+                format!("unexpected blank line at line {}", blank),
+            ));
+        }
+        line(number, text)?;
+        empty = false;
+    }
+    if empty {
+        return Err(AocError::parse_error("", "", "the input is empty"));
+    }
+    Ok(())
+}
+
+/// Registers a test per solver module, checking that its `process_reader`
+/// gives the same answer as its `process` with [`check_streaming`].
+///
+/// ```ignore
+/// aoc_common::streaming_tests!(1 => day_01::part1, 2 => day_01::part2);
+/// ```
+#[macro_export]
+macro_rules! streaming_tests {
+    ($($(#[$meta:meta])* $part:literal => $krate:ident::$variant:ident),+ $(,)?) => {
+        $(
+            $(#[$meta])*
+            #[test]
+            fn $variant() -> miette::Result<()> {
+                $crate::stream::check_streaming(
+                    env!("CARGO_MANIFEST_DIR").as_ref(),
+                    $part,
+                    |input| $krate::$variant::process(input),
+                    |reader| $krate::$variant::process_reader(reader),
+                )
+            }
+        )+
+    };
+}
+
+/// Runs `process` and `process_reader` on every input that has an answer for
+/// `part` in the `answers.txt` of the day crate at `day_dir`, where both must
+/// succeed with the same answer, and on that input three times over, where
+/// they must agree on the answer or both fail, e.g. on an overflow.
+pub fn check_streaming<T: PartialEq + Debug>(
+    day_dir: &Path,
+    part: u8,
+    process: impl Fn(&str) -> Result<T, AocError>,
+    process_reader: impl Fn(&[u8]) -> Result<T, AocError>,
+) -> miette::Result<()> {
+    let answers = Answers::read(day_dir)?;
+    let mut inputs = answers.inputs(part).peekable();
+    if inputs.peek().is_none() {
+        miette::bail!("{} has no answers for part {}", day_dir.display(), part);
+    }
+    for name in inputs {
+        let path = day_dir.join(name);
+        let mut input = fs::read_to_string(&path)
+            .map_err(|err| miette::miette!("failed to read {}: {}", path.display(), err))?;
+        let expected = process(&input)?;
+        let streamed = process_reader(input.as_bytes())?;
+        if expected != streamed {
+            miette::bail!(
+                "part {} of {} streamed is {:?}, expected {:?}",
+                part,
+                name,
+                streamed,
+                expected
+            );
+        }
+
+        if !input.ends_with('\n') {
+            input.push('\n');
+        }
+        let input = input.repeat(3);
+        match (process(&input), process_reader(input.as_bytes())) {
+            (Ok(expected), Ok(streamed)) if expected == streamed => {}
+            (Err(_), Err(_)) => {}
+            (expected, streamed) => miette::bail!(
+                "part {} of {} three times over streamed is {:?}, expected {:?}",
+                part,
+                name,
+                streamed,
+                expected
+            ),
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(input: &str) -> miette::Result<Vec<(usize, String)>, AocError> {
+        let mut lines = Vec::new();
+        for_each_line(input.as_bytes(), |number, line| {
+            lines.push((number, line.to_string()));
+            Ok(())
+        })?;
+        Ok(lines)
+    }
+
+    #[test]
+    fn test_for_each_line() -> miette::Result<()> {
+        assert_eq!(
+            vec![(1, "a b".to_string()), (2, "c".to_string())],
+            lines("a b\r\nc\n\n")?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_check_streaming() -> miette::Result<()> {
+        let day_dir = std::env::temp_dir().join(format!("aoc-stream-{}", std::process::id()));
+        fs::create_dir_all(&day_dir).map_err(|err| miette::miette!("{}", err))?;
+        fs::write(day_dir.join("answers.txt"), "a.txt 1 2\n")
+            .and_then(|_| fs::write(day_dir.join("a.txt"), "a\nb\n"))
+            .map_err(|err| miette::miette!("{}", err))?;
+        let count = |input: &str| Ok(input.lines().count());
+        let count_reader = |reader: &[u8]| {
+            let mut count = 0;
+            for_each_line(reader, |_, _| {
+                count += 1;
+                Ok(())
+            })?;
+            Ok(count)
+        };
+        let failing = |_: &[u8]| -> Result<usize, AocError> { Err(AocError::overflow("")) };
+
+        assert!(check_streaming(&day_dir, 1, count, count_reader).is_ok());
+        // a streaming path that fails everywhere is not the same as one that
+        // agrees
+        assert!(check_streaming(&day_dir, 1, count, failing).is_err());
+        assert!(check_streaming(&day_dir, 1, count, |_| Ok(2)).is_err());
+        assert!(check_streaming(&day_dir, 2, count, count_reader).is_err());
+        fs::remove_dir_all(&day_dir).map_err(|err| miette::miette!("{}", err))?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_blank_lines() {
+        for (input, expected) in [
+            (
+                "a\n\nb\n",
+                "failed to parse input: unexpected blank line at line 2",
+            ),
+            ("", "failed to parse input: the input is empty"),
+            ("\n\n", "failed to parse input: the input is empty"),
+        ] {
+            match lines(input) {
+                Err(err) => assert_eq!(expected, err.to_string()),
+                other => panic!("expected an error, got {:?}", other),
+            }
+        }
+    }
+}
@@ -0,0 +1,275 @@
+use std::{
+    env, fmt,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use tracing::{span, Subscriber};
+use tracing_chrome::{ChromeLayerBuilder, FlushGuard};
+use tracing_subscriber::{
+    filter::LevelFilter,
+    layer::{Context, SubscriberExt},
+    registry::{LookupSpan, SpanRef},
+    util::SubscriberInitExt,
+    Layer,
+};
+
+/// Environment variable that turns on the per-phase timing tree.
+pub const TIMINGS_ENV: &str = "AOC_TIMINGS";
+
+/// Environment variable with the path to write a Chrome trace of the run to.
+pub const CHROME_TRACE_ENV: &str = "AOC_CHROME_TRACE";
+
+/// What to record of a run besides the log.
+#[derive(Debug, Default, Clone)]
+pub struct Options {
+    /// Print the per-phase timing tree at the end of the run.
+    pub timings: bool,
+    /// Write every span, down to the `debug` ones inside hot loops, as a
+    /// Chrome Trace Event file for Perfetto or `chrome://tracing`.
+    pub chrome_trace: Option<PathBuf>,
+}
+
+impl Options {
+    /// Reads `$AOC_TIMINGS` and `$AOC_CHROME_TRACE`.
+    pub fn from_env() -> Self {
+        Self {
+            timings: env::var_os(TIMINGS_ENV).is_some(),
+            chrome_trace: env::var_os(CHROME_TRACE_ENV).map(PathBuf::from),
+        }
+    }
+}
+
+/// Prints the timing tree and finishes the Chrome trace when dropped.
+#[derive(Default)]
+pub struct Guard {
+    _timings: Option<Timings>,
+    _chrome_trace: Option<FlushGuard>,
+}
+
+/// Installs the subscriber of a bin: log events to stderr, or send spans to
+/// the Tracy profiler with the `tracy` feature, plus whatever `$AOC_TIMINGS`
+/// and `$AOC_CHROME_TRACE` ask for.
+pub fn init() -> Guard {
+    init_with(Options::from_env())
+}
+
+/// Like [`init`], recording what `options` ask for until the returned guard
+/// is dropped.
+pub fn init_with(options: Options) -> Guard {
+    let timing_layer = options.timings.then(TimingLayer::default);
+    let timings = timing_layer
+        .as_ref()
+        .map(|layer| Timings(Arc::clone(&layer.phases)));
+    let (chrome_layer, chrome_trace) = match options.chrome_trace {
+        Some(path) => {
+            let (layer, guard) = ChromeLayerBuilder::new()
+                .file(path)
+                .include_args(true)
+                .build();
+            (Some(layer), Some(guard))
+        }
+        None => (None, None),
+    };
+
+    let registry = tracing_subscriber::registry()
+        .with(timing_layer.with_filter(LevelFilter::INFO))
+        .with(chrome_layer.with_filter(LevelFilter::DEBUG));
+    #[cfg(not(feature = "tracy"))]
+    registry
+        .with(tracing_subscriber::fmt::layer().with_filter(LevelFilter::INFO))
+        .init();
+    #[cfg(feature = "tracy")]
+    registry
+        .with(tracing_tracy::TracyLayer::default().with_filter(LevelFilter::INFO))
+        .init();
+
+    Guard {
+        _timings: timings,
+        _chrome_trace: chrome_trace,
+    }
+}
+
+/// Runs `solve` inside a frame of the Tracy profiler, if one is running, so
+/// that every solve shows up as its own frame in the timeline.
+pub fn frame<T>(solve: impl FnOnce() -> T) -> T {
+    #[cfg(feature = "tracy")]
+    let _frame = tracy_client::Client::running()
+        .map(|client| client.non_continuous_frame(tracy_client::frame_name!("solve")));
+    solve()
+}
+
+/// Calls and busy time of every span with the same path from the root.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Phase {
+    /// Names of the span and its parents, the root first.
+    pub path: Vec<String>,
+    /// Closed spans, `0` if it is still open.
+    pub calls: usize,
+    /// Time spent inside the spans, children included.
+    pub busy: Duration,
+}
+
+/// Sums up the time spent in each span by its path, in creation order.
+#[derive(Debug, Default)]
+pub struct TimingLayer {
+    phases: Arc<Mutex<Vec<Phase>>>,
+}
+
+/// Busy time of a single open span.
+struct Busy {
+    phase: usize,
+    busy: Duration,
+    entered: Option<Instant>,
+}
+
+impl TimingLayer {
+    pub fn phases(&self) -> Vec<Phase> {
+        self.phases.lock().expect("phases are not poisoned").clone()
+    }
+}
+
+/// `target::name` for the spans of a new crate or module, `name` below them.
+fn label<S>(span: &SpanRef<'_, S>) -> String
+where
+    S: for<'a> LookupSpan<'a>,
+{
+    match span.parent() {
+        Some(parent) if parent.metadata().target() == span.metadata().target() => {
+            span.name().to_string()
+        }
+        _ => format!("{}::{}", span.metadata().target(), span.name()),
+    }
+}
+
+impl<S> Layer<S> for TimingLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, _: &span::Attributes<'_>, id: &span::Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let path = span
+            .scope()
+            .from_root()
+            .map(|span| label(&span))
+            .collect::<Vec<_>>();
+        let mut phases = self.phases.lock().expect("phases are not poisoned");
+        let phase = match phases.iter().position(|phase| phase.path == path) {
+            Some(phase) => phase,
+            None => {
+                phases.push(Phase {
+                    path,
+                    calls: 0,
+                    busy: Duration::ZERO,
+                });
+                phases.len() - 1
+            }
+        };
+        span.extensions_mut().insert(Busy {
+            phase,
+            busy: Duration::ZERO,
+            entered: None,
+        });
+    }
+
+    fn on_enter(&self, id: &span::Id, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            if let Some(busy) = span.extensions_mut().get_mut::<Busy>() {
+                busy.entered = Some(Instant::now());
+            }
+        }
+    }
+
+    fn on_exit(&self, id: &span::Id, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            if let Some(busy) = span.extensions_mut().get_mut::<Busy>() {
+                if let Some(entered) = busy.entered.take() {
+                    busy.busy += entered.elapsed();
+                }
+            }
+        }
+    }
+
+    fn on_close(&self, id: span::Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(&id) else {
+            return;
+        };
+        let extensions = span.extensions();
+        if let Some(busy) = extensions.get::<Busy>() {
+            let mut phases = self.phases.lock().expect("phases are not poisoned");
+            phases[busy.phase].calls += 1;
+            phases[busy.phase].busy += busy.busy;
+        }
+    }
+}
+
+/// Prints the timing tree to stderr when dropped.
+#[derive(Debug)]
+pub struct Timings(Arc<Mutex<Vec<Phase>>>);
+
+impl fmt::Display for Timings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let phases = self.0.lock().expect("phases are not poisoned");
+        writeln!(f, "{:<56} {:>6} {:>12}", "phase", "calls", "busy")?;
+        for phase in phases.iter() {
+            let name = format!(
+                "{:indent$}{}",
+                "",
+                phase.path.last().map_or("", String::as_str),
+                indent = 2 * (phase.path.len() - 1)
+            );
+            if phase.calls == 0 {
+                writeln!(f, "{:<56} {:>6} {:>12}", name, "open", "")?;
+            } else {
+                writeln!(f, "{:<56} {:>6} {:>12.2?}", name, phase.calls, phase.busy)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Drop for Timings {
+    fn drop(&mut self) {
+        eprint!("{}", self);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timing_layer() {
+        let layer = TimingLayer::default();
+        let phases = Arc::clone(&layer.phases);
+        let subscriber = tracing_subscriber::registry().with(layer);
+        tracing::subscriber::with_default(subscriber, || {
+            let _process = tracing::info_span!("process").entered();
+            for _ in 0..3 {
+                tracing::info_span!("parse").in_scope(|| {});
+            }
+            tracing::info_span!("solve").in_scope(|| {
+                tracing::info_span!("cycle_search").in_scope(|| {});
+            });
+        });
+
+        let target = module_path!();
+        let phases = phases.lock().unwrap();
+        let summary = phases
+            .iter()
+            .map(|phase| (phase.path.join(" > "), phase.calls))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                (format!("{target}::process"), 1),
+                (format!("{target}::process > parse"), 3),
+                (format!("{target}::process > solve"), 1),
+                (format!("{target}::process > solve > cycle_search"), 1),
+            ],
+            summary
+        );
+    }
+}
@@ -0,0 +1,573 @@
+use std::{
+    collections::{HashMap, HashSet},
+    env, fmt, fs,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use clap::{Args, Subcommand};
+
+use crate::{
+    divan,
+    history::{self, Record},
+    registry::{days, workspace_dir, VARIANTS},
+    report,
+};
+
+#[derive(Args, Debug)]
+pub struct BenchArgs {
+    #[command(subcommand)]
+    command: BenchCommand,
+
+    /// History to record into, `bench-history.tsv` of the workspace by default
+    #[arg(long, global = true)]
+    history: Option<PathBuf>,
+
+    /// Baseline to check against, `bench-baseline.tsv` of the workspace by default
+    #[arg(long, global = true)]
+    baseline: Option<PathBuf>,
+}
+
+#[derive(Subcommand, Debug)]
+enum BenchCommand {
+    /// Run the divan benches and record their timings
+    Record(RecordArgs),
+    /// Record the runs logged in each `day-XX.bench.txt` by `just bench`
+    Import,
+    /// Run the divan benches and store their timings as the new baseline of the days
+    Baseline(RecordArgs),
+    /// Run the divan benches and fail if a variant got slower than its baseline
+    Check(CheckArgs),
+    /// Run the divan benches of every day and write a performance report
+    Report(ReportArgs),
+}
+
+#[derive(Args, Debug)]
+struct RecordArgs {
+    /// Day to bench, every registered day if omitted
+    day: Option<u8>,
+
+    /// What changed since the last run, e.g. `after refactoring part1`
+    #[arg(long, default_value = "")]
+    note: String,
+
+    /// Also bench the variants registered as slow, which can take hours
+    #[arg(long)]
+    slow: bool,
+}
+
+#[derive(Args, Debug)]
+struct ReportArgs {
+    /// README whose `<!-- bench-report -->` section gets the markdown table
+    #[arg(long)]
+    readme: Option<PathBuf>,
+
+    /// Where to write the HTML page with the charts
+    #[arg(long)]
+    html: Option<PathBuf>,
+
+    /// Also bench the variants registered as slow, which can take hours
+    #[arg(long)]
+    slow: bool,
+}
+
+#[derive(Args, Debug)]
+struct CheckArgs {
+    /// Day to bench, every day of the baseline if omitted
+    day: Option<u8>,
+
+    /// How much slower, in percent, the median of a variant may get
+    #[arg(long, default_value_t = 10.0)]
+    threshold: f64,
+}
+
+pub fn bench(args: &BenchArgs) -> miette::Result<()> {
+    let history = args
+        .history
+        .clone()
+        .unwrap_or_else(|| workspace_dir().join("bench-history.tsv"));
+    let baseline = args
+        .baseline
+        .clone()
+        .unwrap_or_else(|| workspace_dir().join("bench-baseline.tsv"));
+    let records = match &args.command {
+        BenchCommand::Record(record_args) => record(record_args)?,
+        BenchCommand::Import => import(&history)?,
+        BenchCommand::Baseline(record_args) => {
+            let records = record(record_args)?;
+            let mut kept = history::load(&baseline)?;
+            kept.retain(|old| records.iter().all(|new| new.day != old.day));
+            kept.extend(records);
+            kept.sort_by_key(|record| record.day);
+            history::save(&baseline, &kept)?;
+            println!("stored the baseline in {}", baseline.display());
+            return Ok(());
+        }
+        BenchCommand::Check(check_args) => return check(check_args, &baseline),
+        BenchCommand::Report(report_args) => return write_report(report_args),
+    };
+    history::append(&history, &records)?;
+    println!(
+        "recorded {} benchmarks in {}",
+        records.len(),
+        history.display()
+    );
+    Ok(())
+}
+
+fn record(args: &RecordArgs) -> miette::Result<Vec<Record>> {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |since| since.as_secs());
+    let commit = commit()?;
+    let machine = machine();
+
+    let mut records = Vec::new();
+    for day in args.day.map_or_else(days, |day| vec![day]) {
+        let output = run_benches(day, args.slow)?;
+        records.extend(divan::parse(&output).into_iter().map(|row| Record {
+            timestamp,
+            commit: commit.clone(),
+            machine: machine.clone(),
+            run: timestamp.to_string(),
+            note: args.note.clone(),
+            day,
+            benchmark: row.benchmark,
+            stats: row.stats,
+        }));
+    }
+    Ok(records)
+}
+
+fn write_report(args: &ReportArgs) -> miette::Result<()> {
+    let readme = args
+        .readme
+        .clone()
+        .unwrap_or_else(|| workspace_dir().join("README.md"));
+    let html = args
+        .html
+        .clone()
+        .unwrap_or_else(|| workspace_dir().join("bench-report.html"));
+
+    let days = days()
+        .into_iter()
+        .map(|day| Ok((day, divan::parse(&run_benches(day, args.slow)?))))
+        .collect::<miette::Result<Vec<_>>>()?;
+    let entries = report::entries(&days, VARIANTS);
+    let curves = report::curves(&days);
+
+    let markdown = report::markdown(&entries, &curves);
+    print!("{}", markdown);
+    report::update_readme(&readme, &markdown)?;
+    fs::write(&html, report::html(&entries, &curves))
+        .map_err(|err| miette::miette!("failed to write {}: {}", html.display(), err))?;
+    println!("wrote {} and {}", readme.display(), html.display());
+    Ok(())
+}
+
+fn check(args: &CheckArgs, baseline: &Path) -> miette::Result<()> {
+    let baseline = history::load(baseline)?;
+    let mut days = baseline.iter().map(|record| record.day).collect::<Vec<_>>();
+    days.dedup();
+    if let Some(day) = args.day {
+        days.retain(|&d| d == day);
+    }
+    if days.is_empty() {
+        miette::bail!("the baseline has no benchmarks of the selected days");
+    }
+
+    let (mut regressions, mut removed) = (0, 0);
+    for day in days {
+        let day_baseline = baseline
+            .iter()
+            .filter(|record| record.day == day)
+            .cloned()
+            .collect::<Vec<_>>();
+        // slow variants are benched again if the baseline was recorded with them
+        let current = record(&RecordArgs {
+            day: Some(day),
+            note: String::new(),
+            slow: day_baseline
+                .iter()
+                .any(|record| slow_stage(day, &record.benchmark)),
+        })?;
+        for change in compare(&day_baseline, &current) {
+            let regressed = change.gates()
+                && change
+                    .percent()
+                    .is_some_and(|percent| percent > args.threshold);
+            let gone = change.gates() && change.after.is_none();
+            regressions += usize::from(regressed);
+            removed += usize::from(gone);
+            println!(
+                "{:<40} {}{}",
+                format!("day-{:02} {}", change.day, change.benchmark),
+                change,
+                match (regressed, gone) {
+                    (true, _) => "  REGRESSED",
+                    (_, true) => "  REMOVED",
+                    _ => "",
+                }
+            );
+        }
+    }
+
+    if regressions > 0 || removed > 0 {
+        miette::bail!(
+            "{} variants are more than {}% slower than the baseline and {} are no longer benched",
+            regressions,
+            args.threshold,
+            removed
+        );
+    }
+    Ok(())
+}
+
+/// The median of a benchmark in the baseline and now, `None` where it was
+/// added since the baseline or is no longer benched.
+#[derive(Debug, PartialEq)]
+pub struct Change {
+    pub day: u8,
+    pub benchmark: String,
+    pub before: Option<f64>,
+    pub after: Option<f64>,
+}
+
+impl Change {
+    pub fn percent(&self) -> Option<f64> {
+        let (before, after) = (self.before?, self.after?);
+        Some((after - before) / before * 100.0)
+    }
+
+    /// Only the `total` of a variant fails the check, its stages are noisier
+    /// and shown to tell which one regressed.
+    fn gates(&self) -> bool {
+        !self.benchmark.contains('/') || self.benchmark.ends_with("/total")
+    }
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.before, self.after, self.percent()) {
+            (Some(before), Some(after), Some(percent)) => write!(
+                f,
+                "{} -> {} ({:+.0}%)",
+                divan::format_duration(before),
+                divan::format_duration(after),
+                percent
+            ),
+            (None, Some(after), _) => write!(f, "added, {}", divan::format_duration(after)),
+            (Some(before), None, _) => {
+                write!(f, "removed, was {}", divan::format_duration(before))
+            }
+            _ => write!(f, "not benched"),
+        }
+    }
+}
+
+/// Median changes of every benchmark in `current`, followed by those of the
+/// `baseline` that are not in `current` any more.
+pub fn compare(baseline: &[Record], current: &[Record]) -> Vec<Change> {
+    let latest = |records: &[Record], day: u8, benchmark: &str| {
+        records
+            .iter()
+            .rev()
+            .find(|record| record.day == day && record.benchmark == benchmark)
+            .map(|record| record.stats.median)
+    };
+    let mut changes = current
+        .iter()
+        .map(|record| Change {
+            day: record.day,
+            benchmark: record.benchmark.clone(),
+            before: latest(baseline, record.day, &record.benchmark),
+            after: Some(record.stats.median),
+        })
+        .collect::<Vec<_>>();
+    let mut seen = HashSet::new();
+    for record in baseline {
+        if latest(current, record.day, &record.benchmark).is_none()
+            && seen.insert((record.day, record.benchmark.as_str()))
+        {
+            changes.push(Change {
+                day: record.day,
+                benchmark: record.benchmark.clone(),
+                before: latest(baseline, record.day, &record.benchmark),
+                after: None,
+            });
+        }
+    }
+    changes
+}
+
+/// Whether `benchmark` is a stage of a variant of `day` registered as slow,
+/// which is only benched with `--slow`.
+fn slow_stage(day: u8, benchmark: &str) -> bool {
+    benchmark.split_once('/').is_some_and(|(module, _)| {
+        VARIANTS
+            .iter()
+            .any(|variant| variant.day == day && variant.slow && variant.module == module)
+    })
+}
+
+/// Output of `cargo bench -p day-XX`, its progress goes to stderr. The
+/// benches of `slow` variants are only registered with the `slow` feature.
+pub fn run_benches(day: u8, slow: bool) -> miette::Result<String> {
+    let package = format!("day-{:02}", day);
+    let mut cargo = Command::new(env::var("CARGO").unwrap_or_else(|_| "cargo".to_string()));
+    cargo.args(["bench", "-q", "-p", &package]);
+    let variants = VARIANTS
+        .iter()
+        .filter(|variant| variant.day == day)
+        .collect::<Vec<_>>();
+    let mut features = Vec::new();
+    // the parallel variants are only registered with the `parallel` feature
+    if variants
+        .iter()
+        .any(|variant| variant.module.ends_with("_parallel"))
+    {
+        features.push("parallel");
+    }
+    if slow && variants.iter().any(|variant| variant.slow) {
+        features.push("slow");
+    }
+    if !features.is_empty() {
+        cargo.args(["--features", &features.join(",")]);
+    }
+    let output = cargo
+        .current_dir(workspace_dir())
+        .stderr(Stdio::inherit())
+        .output()
+        .map_err(|err| miette::miette!("failed to run cargo bench: {}", err))?;
+    if !output.status.success() {
+        miette::bail!("cargo bench -p {} failed with {}", package, output.status);
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Short hash of `HEAD`, with `-dirty` if the tree has uncommitted changes.
+pub fn commit() -> miette::Result<String> {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .current_dir(workspace_dir())
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+    let head = git(&["rev-parse", "--short", "HEAD"])
+        .ok_or_else(|| miette::miette!("failed to find the git commit"))?;
+    match git(&["status", "--porcelain", "--untracked-files=no"]) {
+        Some(status) if status.is_empty() => Ok(head),
+        _ => Ok(format!("{}-dirty", head)),
+    }
+}
+
+fn machine() -> String {
+    env::var("HOSTNAME")
+        .ok()
+        .or_else(|| fs::read_to_string("/etc/hostname").ok())
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// Records of every table in the `.bench.txt` logs that is not in the
+/// `history` yet. The logs only hold the tables, so each run is dated by the
+/// commit that added it.
+fn import(history: &Path) -> miette::Result<Vec<Record>> {
+    let imported = history::load(history)?
+        .into_iter()
+        .map(|record| record.run)
+        .collect::<HashSet<_>>();
+
+    let mut records = Vec::new();
+    for day in days() {
+        let name = format!("day-{:02}.bench.txt", day);
+        let path = workspace_dir().join(&name);
+        let Ok(log) = fs::read_to_string(&path) else {
+            continue;
+        };
+        let blame = blame(&name);
+        records.extend(
+            parse_log(&name, day, &log, &blame)
+                .into_iter()
+                .filter(|record| !imported.contains(&record.run)),
+        );
+    }
+    Ok(records)
+}
+
+/// Records of the tables in a `.bench.txt` log. `blame` maps line numbers,
+/// counting from 1, to the commit that added them and its time.
+fn parse_log(name: &str, day: u8, log: &str, blame: &HashMap<usize, (String, u64)>) -> Vec<Record> {
+    let lines = log.lines().collect::<Vec<_>>();
+    divan::parse(log)
+        .into_iter()
+        .map(|row| {
+            let header = (0..=row.line)
+                .rev()
+                .find(|&n| lines[n].contains("fastest"))
+                .expect("rows follow a header");
+            // free text between tables like `--- after refactoring part1`
+            let note = lines[..header]
+                .iter()
+                .rev()
+                .map(|line| line.trim().trim_matches('-').trim())
+                .find(|line| !line.is_empty() && !line.contains('│'))
+                .unwrap_or_default();
+            let (commit, timestamp) = blame
+                .get(&(header + 1))
+                .cloned()
+                .unwrap_or_else(|| ("unknown".to_string(), 0));
+            Record {
+                timestamp,
+                commit,
+                machine: "unknown".to_string(),
+                run: format!("{}:{}", name, header + 1),
+                note: note.to_string(),
+                day,
+                benchmark: row.benchmark,
+                stats: row.stats,
+            }
+        })
+        .collect()
+}
+
+/// Commit and author time of every line of `file`, from `git blame`.
+fn blame(file: &str) -> HashMap<usize, (String, u64)> {
+    let Some(output) = Command::new("git")
+        .args(["blame", "--line-porcelain", "--", file])
+        .current_dir(workspace_dir())
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+    else {
+        return HashMap::new();
+    };
+
+    let mut lines = HashMap::new();
+    let mut current = None;
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        if let Some(time) = line.strip_prefix("author-time ") {
+            if let Some((_, _, timestamp)) = &mut current {
+                *timestamp = time.parse().unwrap_or_default();
+            }
+        } else if line.starts_with('\t') {
+            if let Some((commit, n, timestamp)) = current.take() {
+                lines.insert(n, (commit, timestamp));
+            }
+        } else if current.is_none() {
+            // `<sha> <original line> <final line> [<group size>]`
+            let mut fields = line.split(' ');
+            if let (Some(sha), _, Some(n)) = (fields.next(), fields.next(), fields.next()) {
+                let commit = match sha.trim_start_matches('0') {
+                    "" => "uncommitted".to_string(),
+                    _ => sha.chars().take(7).collect(),
+                };
+                current = n.parse().ok().map(|n| (commit, n, 0));
+            }
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_log() {
+        let log = "\
+day_10    fastest       │ slowest       │ median        │ mean          │ samples │ iters
+├─ part1  274.7 µs      │ 414.2 µs      │ 285 µs        │ 295.9 µs      │ 100     │ 100
+╰─ part2  215.4 µs      │ 283.8 µs      │ 216.8 µs      │ 219.3 µs      │ 100     │ 100
+
+--- after refactoring part1
+
+day_10    fastest       │ slowest       │ median        │ mean          │ samples │ iters
+╰─ part1  251 µs        │ 414.2 µs      │ 285 µs        │ 295.9 µs      │ 100     │ 100
+";
+        let blame = HashMap::from([(7, ("702c2cb".to_string(), 1701820800))]);
+        let records = parse_log("day-10.bench.txt", 10, log, &blame);
+        let runs = records
+            .iter()
+            .map(|r| {
+                (
+                    r.run.as_str(),
+                    r.note.as_str(),
+                    r.benchmark.as_str(),
+                    r.commit.as_str(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                ("day-10.bench.txt:1", "", "part1", "unknown"),
+                ("day-10.bench.txt:1", "", "part2", "unknown"),
+                (
+                    "day-10.bench.txt:7",
+                    "after refactoring part1",
+                    "part1",
+                    "702c2cb"
+                ),
+            ],
+            runs
+        );
+        assert_eq!(251e3, records[2].stats.fastest);
+        assert_eq!(1701820800, records[2].timestamp);
+    }
+
+    #[test]
+    fn test_compare() {
+        let record = |benchmark: &str, median: f64| Record {
+            timestamp: 0,
+            commit: String::new(),
+            machine: String::new(),
+            run: String::new(),
+            note: String::new(),
+            day: 5,
+            benchmark: benchmark.to_string(),
+            stats: divan::Stats {
+                fastest: median,
+                slowest: median,
+                median,
+                mean: median,
+                samples: 100,
+                iters: 100,
+            },
+        };
+        let baseline = [
+            record("part2_binary_search", 490.3e3),
+            record("part1_btree", 120e3),
+        ];
+        let current = [
+            record("part2_binary_search", 620e3),
+            record("part2_btree", 1e9),
+        ];
+        let changes = compare(&baseline, &current)
+            .iter()
+            .map(|change| format!("{} {}", change.benchmark, change))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                "part2_binary_search 490µs -> 620µs (+26%)",
+                "part2_btree added, 1.00s",
+                "part1_btree removed, was 120µs",
+            ],
+            changes
+        );
+    }
+
+    #[test]
+    fn test_slow_stage() {
+        assert!(slow_stage(5, "part2_btree/total"));
+        assert!(slow_stage(11, "part1_expand_bfs/parse"));
+        assert!(!slow_stage(5, "part2_binary_search/total"));
+        // scaling benches of slow variants stay on small inputs
+        assert!(!slow_stage(5, "seeds_per_range/part2_btree/1"));
+        assert!(!slow_stage(11, "part2_btree/total"));
+    }
+}
@@ -0,0 +1,120 @@
+use std::{collections::BTreeSet, fs};
+
+use aoc_common::answers::Answers;
+use clap::Args;
+
+use crate::registry::{day_dir, Check, CHECKS};
+
+#[derive(Args, Debug)]
+pub struct CheckArgs {
+    /// Day to check, every registered day if omitted
+    day: Option<u8>,
+
+    /// Input file to check instead of the day's `input.txt`, `-` for stdin.
+    /// Falls back to `$AOC_INPUT`.
+    #[arg(long, requires = "day")]
+    input: Option<String>,
+
+    /// Also check the examples listed in each day's `answers.txt`
+    #[arg(long, conflicts_with = "input")]
+    examples: bool,
+}
+
+pub fn check(args: &CheckArgs) -> miette::Result<()> {
+    let checks = CHECKS
+        .iter()
+        .filter(|(day, _)| args.day.is_none_or(|selected| selected == *day))
+        .collect::<Vec<_>>();
+    if checks.is_empty() {
+        miette::bail!("no registered day matches the selection");
+    }
+
+    let mut checked = 0;
+    let mut invalid = 0;
+    for &&(day, check) in &checks {
+        let inputs = match &args.input {
+            Some(path) => vec![(path.clone(), read(day, Some(path.clone())))],
+            None => inputs(day, args.examples)?
+                .into_iter()
+                .map(|input| {
+                    let contents = fs::read_to_string(day_dir(day).join(&input))
+                        .map_err(|err| miette::miette!("failed to read {}: {}", input, err));
+                    (input, contents)
+                })
+                .collect(),
+        };
+        for (name, contents) in inputs {
+            checked += 1;
+            let label = format!("day-{:02} {}", day, name);
+            match contents.map(|contents| check_input(check, &contents)) {
+                Ok(violations) if violations.is_empty() => println!("{:<28} ok", label),
+                Ok(violations) => {
+                    invalid += 1;
+                    let plural = if violations.len() == 1 { "" } else { "s" };
+                    println!("{:<28} {} violation{}", label, violations.len(), plural);
+                    for violation in violations {
+                        eprintln!("{:?}", violation);
+                    }
+                }
+                Err(err) => {
+                    invalid += 1;
+                    println!("{:<28} ERROR {}", label, err);
+                }
+            }
+        }
+    }
+
+    println!("{} of {} inputs are valid", checked - invalid, checked);
+    if invalid > 0 {
+        miette::bail!("{invalid} of {checked} inputs are invalid");
+    }
+    Ok(())
+}
+
+/// Every violation of `input`, as reports that render their source.
+fn check_input(check: Check, input: &str) -> Vec<miette::Report> {
+    check(input).into_iter().map(miette::Report::new).collect()
+}
+
+/// Reads `path` (`-` for stdin), `$AOC_INPUT` or the day's bundled `input.txt`.
+fn read(day: u8, path: Option<String>) -> miette::Result<String> {
+    aoc_common::input::read_input(path, &day_dir(day).join("input.txt"))
+        .map_err(|err| miette::miette!("failed to read input of day {}: {}", day, err))
+}
+
+/// The bundled `input.txt`, then with `examples` every other input of
+/// `answers.txt`, each once.
+fn inputs(day: u8, examples: bool) -> miette::Result<Vec<String>> {
+    let mut inputs = vec!["input.txt".to_string()];
+    if examples {
+        let answers = Answers::read(&day_dir(day))?;
+        let examples = [1, 2]
+            .into_iter()
+            .flat_map(|part| answers.inputs(part))
+            .filter(|input| *input != "input.txt")
+            .collect::<BTreeSet<_>>();
+        inputs.extend(examples.into_iter().map(str::to_string));
+    }
+    Ok(inputs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inputs_are_valid() -> miette::Result<()> {
+        let mut failures = Vec::new();
+        for &(day, check) in CHECKS {
+            for input in inputs(day, true)? {
+                let contents = fs::read_to_string(day_dir(day).join(&input))
+                    .map_err(|err| miette::miette!("failed to read {}: {}", input, err))?;
+                for violation in check(&contents) {
+                    failures.push(format!("day-{:02} {}: {}", day, input, violation));
+                }
+            }
+        }
+        assert!(failures.is_empty(), "{:#?}", failures);
+        Ok(())
+    }
+}
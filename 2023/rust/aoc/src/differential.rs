@@ -0,0 +1,357 @@
+use std::{
+    collections::HashSet,
+    fs,
+    panic::{self, AssertUnwindSafe},
+};
+
+use aoc_common::generate::{generate, Shape};
+use clap::Args;
+use rand::{rngs::StdRng, SeedableRng};
+
+use crate::registry::{day_dir, Selection, Variant};
+
+#[derive(Args, Debug)]
+pub struct DiffArgs {
+    /// Day to cross-check, every day with several variants of a part if omitted
+    day: Option<u8>,
+
+    /// Part to cross-check (`1` or `2`), every part if omitted
+    part: Option<u8>,
+
+    /// Number of random inputs fed to each part
+    #[arg(long, default_value_t = 200)]
+    cases: usize,
+
+    /// Seed of the random inputs, a fresh one if omitted
+    #[arg(long)]
+    seed: Option<u64>,
+
+    /// Also run variants registered as slow on the real input
+    #[arg(long)]
+    slow: bool,
+}
+
+/// What a variant made of an input: its answer, or why it has none.
+pub type Outcome = Result<String, String>;
+
+/// An input on which the variants of a part do not all give the same answer.
+#[derive(Debug)]
+pub struct Disagreement {
+    pub input: String,
+    pub outcomes: Vec<(&'static Variant, Outcome)>,
+}
+
+pub fn diff(args: &DiffArgs) -> miette::Result<()> {
+    let seed = args.seed.unwrap_or_else(rand::random);
+    let selection = Selection {
+        day: args.day,
+        part: args.part,
+        slow: true,
+        ..Default::default()
+    };
+    let groups = groups(&selection);
+    if groups.is_empty() {
+        miette::bail!("no part with several variants matches the selection");
+    }
+
+    // variants are expected to fail on some inputs while shrinking, their
+    // panics are reported as outcomes instead
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let mut disagreements = 0;
+    for group in &groups {
+        let real = group
+            .iter()
+            .copied()
+            .filter(|variant| args.slow || !variant.slow)
+            .collect::<Vec<_>>();
+        let found = samples(group)
+            .into_iter()
+            .find_map(|input| check(group, &input))
+            .or_else(|| read(group[0], "input.txt").and_then(|input| check(&real, &input)))
+            .or_else(|| random(group, args.cases, seed));
+        match found {
+            Some(disagreement) => {
+                disagreements += 1;
+                report(&shrink(&disagreement));
+            }
+            None => println!("{:<28} ok", group_label(group)),
+        }
+    }
+    panic::set_hook(hook);
+
+    println!("random inputs from seed {}", seed);
+    if disagreements > 0 {
+        miette::bail!("the variants of {disagreements} parts disagree");
+    }
+    Ok(())
+}
+
+/// Variants of the same day and part, for every part that has several.
+pub fn groups(selection: &Selection) -> Vec<Vec<&'static Variant>> {
+    let mut groups: Vec<Vec<&'static Variant>> = Vec::new();
+    for variant in selection.variants() {
+        match groups.last_mut() {
+            Some(group) if group[0].day == variant.day && group[0].part == variant.part => {
+                group.push(variant)
+            }
+            _ => groups.push(vec![variant]),
+        }
+    }
+    groups.retain(|group| group.len() > 1);
+    groups
+}
+
+fn group_label(group: &[&Variant]) -> String {
+    format!("day-{:02} part{}", group[0].day, group[0].part)
+}
+
+/// Every example of the day, whichever part its answer is for.
+pub fn samples(group: &[&Variant]) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(day_dir(group[0].day).join("examples")) else {
+        return Vec::new();
+    };
+    let mut paths = entries
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .collect::<Vec<_>>();
+    paths.sort();
+    paths
+        .iter()
+        .filter_map(|path| fs::read_to_string(path).ok())
+        .collect()
+}
+
+fn read(variant: &Variant, input: &str) -> Option<String> {
+    fs::read_to_string(day_dir(variant.day).join(input)).ok()
+}
+
+/// Largest size of a random input, in lines.
+const MAX_SIZE: usize = 32;
+
+/// The first of `cases` random inputs the variants disagree on. Inputs grow
+/// with the case number up to `MAX_SIZE` and start over, so small ones are
+/// tried first and brute force variants stay fast.
+pub fn random(group: &[&'static Variant], cases: usize, seed: u64) -> Option<Disagreement> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    (0..cases).find_map(|case| {
+        let size = 1 + (case / 4) % MAX_SIZE;
+        let input = generate(group[0].day, Shape::new(size), &mut rng)?.input;
+        check(group, &input)
+    })
+}
+
+pub fn run(variant: &Variant, input: &str) -> Outcome {
+    match panic::catch_unwind(AssertUnwindSafe(|| (variant.process)(input))) {
+        Ok(Ok(answer)) => Ok(answer),
+        Ok(Err(err)) => Err(err.to_string()),
+        Err(panic) => Err(match panic.downcast_ref::<&str>() {
+            Some(message) => format!("panicked: {}", message),
+            None => match panic.downcast_ref::<String>() {
+                Some(message) => format!("panicked: {}", message),
+                None => "panicked".to_string(),
+            },
+        }),
+    }
+}
+
+/// Runs every variant of `group` on `input`. They agree if they all give the
+/// same answer, or all fail.
+pub fn check(group: &[&'static Variant], input: &str) -> Option<Disagreement> {
+    let outcomes = group
+        .iter()
+        .map(|&variant| (variant, run(variant, input)))
+        .collect::<Vec<_>>();
+    let answers = outcomes
+        .iter()
+        .map(|(_, outcome)| outcome.as_ref().ok())
+        .collect::<HashSet<_>>();
+    (answers.len() > 1).then(|| Disagreement {
+        input: input.to_string(),
+        outcomes,
+    })
+}
+
+/// Shrinks the input of `disagreement` while the variants keep disagreeing,
+/// by dropping lines, words and grid columns and by halving numbers.
+pub fn shrink(disagreement: &Disagreement) -> Disagreement {
+    let group = disagreement
+        .outcomes
+        .iter()
+        .map(|(variant, _)| *variant)
+        .collect::<Vec<_>>();
+    let mut smallest = check(&group, &disagreement.input).unwrap_or_else(|| Disagreement {
+        input: disagreement.input.clone(),
+        outcomes: disagreement.outcomes.clone(),
+    });
+    loop {
+        let Some(smaller) = candidates(&smallest.input).find_map(|input| check(&group, &input))
+        else {
+            return smallest;
+        };
+        smallest = smaller;
+    }
+}
+
+/// Inputs one step smaller than `input`.
+fn candidates(input: &str) -> impl Iterator<Item = String> + '_ {
+    let lines = input.lines().collect::<Vec<_>>();
+    let join = |lines: Vec<String>| lines.join("\n");
+
+    let without_lines = (0..lines.len()).map({
+        let lines = lines.clone();
+        move |i| {
+            let mut lines = lines.iter().map(|l| l.to_string()).collect::<Vec<_>>();
+            lines.remove(i);
+            join(lines)
+        }
+    });
+
+    let words = lines
+        .iter()
+        .enumerate()
+        .flat_map(|(i, line)| (0..line.split(' ').count()).map(move |j| (i, j)))
+        .collect::<Vec<_>>();
+    let without_words = words.clone().into_iter().map({
+        let lines = lines.clone();
+        move |(i, j)| edit_word(&lines, i, j, |_| None)
+    });
+    let halved_numbers = words.into_iter().flat_map({
+        let lines = lines.clone();
+        move |(i, j)| {
+            let halve = |word: &str| match word.parse::<u64>() {
+                Ok(n) if n > 0 => Some((n / 2).to_string()),
+                _ => Some(word.to_string()),
+            };
+            let decrement = |word: &str| match word.parse::<u64>() {
+                Ok(n) if n > 0 => Some((n - 1).to_string()),
+                _ => Some(word.to_string()),
+            };
+            [
+                edit_word(&lines, i, j, halve),
+                edit_word(&lines, i, j, decrement),
+            ]
+        }
+    });
+
+    let width = lines.first().map_or(0, |line| line.len());
+    let is_grid = width > 1
+        && lines
+            .iter()
+            .all(|line| line.len() == width && line.is_ascii());
+    let without_columns = (0..if is_grid { width } else { 0 }).map({
+        let lines = lines.clone();
+        move |j| {
+            join(
+                lines
+                    .iter()
+                    .map(|line| format!("{}{}", &line[..j], &line[j + 1..]))
+                    .collect(),
+            )
+        }
+    });
+
+    without_lines
+        .chain(without_columns)
+        .chain(without_words)
+        .chain(halved_numbers)
+        .filter(move |candidate| candidate.as_str() != input)
+}
+
+/// `lines` with word `j` of line `i` replaced by `edit`, or dropped if it
+/// returns `None`.
+fn edit_word(lines: &[&str], i: usize, j: usize, edit: impl Fn(&str) -> Option<String>) -> String {
+    lines
+        .iter()
+        .enumerate()
+        .map(|(k, line)| {
+            if k != i {
+                return line.to_string();
+            }
+            line.split(' ')
+                .enumerate()
+                .filter_map(|(l, word)| {
+                    if l == j {
+                        edit(word)
+                    } else {
+                        Some(word.to_string())
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn report(disagreement: &Disagreement) {
+    let group = disagreement
+        .outcomes
+        .iter()
+        .map(|(variant, _)| *variant)
+        .collect::<Vec<_>>();
+    println!("{:<28} DISAGREE on", group_label(&group));
+    for line in disagreement.input.lines() {
+        println!("    {}", line);
+    }
+    for (variant, outcome) in &disagreement.outcomes {
+        match outcome {
+            Ok(answer) => println!("  {:<26} {}", variant.module, answer),
+            Err(err) => println!("  {:<26} ERROR {}", variant.module, err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_variants_agree() {
+        let groups = groups(&Selection {
+            slow: true,
+            ..Default::default()
+        });
+        // every part with a parallel variant has a group of its own
+        let expected = if cfg!(feature = "parallel") { 12 } else { 4 };
+        assert_eq!(expected, groups.len());
+        for group in &groups {
+            let found = samples(group)
+                .into_iter()
+                .find_map(|input| check(group, &input))
+                .or_else(|| random(group, 100, 2023));
+            if let Some(disagreement) = found {
+                panic!("{:#?}", shrink(&disagreement));
+            }
+        }
+    }
+
+    static LINES: Variant = Variant {
+        day: 0,
+        part: 1,
+        module: "part1",
+        slow: false,
+        process: |input| Ok(input.lines().count().to_string()),
+    };
+
+    static LINES_BUT_X: Variant = Variant {
+        day: 0,
+        part: 1,
+        module: "part1_but_x",
+        slow: false,
+        process: |input| {
+            Ok(input
+                .lines()
+                .filter(|line| !line.contains('x'))
+                .count()
+                .to_string())
+        },
+    };
+
+    #[test]
+    fn test_shrink() {
+        let group = [&LINES, &LINES_BUT_X];
+        assert!(check(&group, "ab\ncd").is_none());
+        let disagreement = check(&group, "ab\nc 12 x d\nef").expect("x is skipped");
+        assert_eq!("x", shrink(&disagreement).input);
+    }
+}
@@ -0,0 +1,235 @@
+/// Timings of one benchmark in a divan table, durations in nanoseconds.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stats {
+    pub fastest: f64,
+    pub slowest: f64,
+    pub median: f64,
+    pub mean: f64,
+    pub samples: u64,
+    pub iters: u64,
+}
+
+/// A row of a divan table that has timings.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Row {
+    /// Root of the tree, the bench target, e.g. `day_05`.
+    pub target: String,
+    /// Path below the root, e.g. `part2_binary_search/solve`.
+    pub benchmark: String,
+    pub stats: Stats,
+    /// Median allocations, if the bench runs with divan's `AllocProfiler`.
+    pub allocs: Option<Allocs>,
+    /// Line of the row in the parsed output, counting from 0.
+    pub line: usize,
+}
+
+/// Median number and size of the allocations of one iteration.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Allocs {
+    pub count: f64,
+    pub bytes: f64,
+}
+
+/// Every timed row of the divan tables in `output`, other lines are skipped.
+pub fn parse(output: &str) -> Vec<Row> {
+    let mut rows: Vec<Row> = Vec::new();
+    let mut target = None;
+    let mut path: Vec<&str> = Vec::new();
+    // kind of the allocation counter being read and how many of its rows were
+    let mut counter: Option<(&str, usize)> = None;
+    for (n, line) in output.lines().enumerate() {
+        if !line.contains('│') {
+            target = None;
+            continue;
+        }
+        if line.contains("fastest") {
+            target = line.split_whitespace().next();
+            path.clear();
+            continue;
+        }
+        let Some(target) = target else {
+            continue;
+        };
+
+        // every level of the tree is indented by 3 characters of `│  ├─ `
+        let tree = line
+            .char_indices()
+            .find(|(_, ch)| !matches!(ch, '│' | '├' | '╰' | '─' | ' '))
+            .map_or(line.len(), |(i, _)| i);
+        let depth = line[..tree].chars().count() / 3;
+        let mut columns = line[tree..].split('│').map(str::trim);
+
+        // `AllocProfiler` counters below a row: `alloc:`, then a row with
+        // the count and one with the size
+        if !line[..tree].contains(['├', '╰']) {
+            let first = columns.next().unwrap_or_default();
+            let median = columns.nth(1).unwrap_or_default();
+            if let Some(kind) = first.strip_suffix(':') {
+                counter = Some((kind, 0));
+            } else if let (Some(("alloc", i)), Some(row)) = (&mut counter, rows.last_mut()) {
+                let allocs = row.allocs.get_or_insert_with(Default::default);
+                match i {
+                    0 => allocs.count = median.parse().unwrap_or_default(),
+                    _ => allocs.bytes = bytes(median).unwrap_or_default(),
+                }
+                *i += 1;
+            }
+            continue;
+        }
+        counter = None;
+
+        let Some((benchmark, fastest)) = columns.next().map(|first| {
+            first
+                .split_once(' ')
+                .map_or((first, ""), |(name, fastest)| (name, fastest.trim()))
+        }) else {
+            continue;
+        };
+        path.truncate(depth.saturating_sub(1));
+        path.push(benchmark);
+
+        let mut columns = std::iter::once(fastest).chain(columns);
+        let mut next = || columns.next().unwrap_or_default();
+        let stats = (|| {
+            Some(Stats {
+                fastest: duration(next())?,
+                slowest: duration(next())?,
+                median: duration(next())?,
+                mean: duration(next())?,
+                samples: next().parse().ok()?,
+                iters: next().parse().ok()?,
+            })
+        })();
+        if let Some(stats) = stats {
+            rows.push(Row {
+                target: target.to_string(),
+                benchmark: path.join("/"),
+                stats,
+                allocs: None,
+                line: n,
+            });
+        }
+    }
+    rows
+}
+
+/// A divan duration like `490.3 µs` in nanoseconds.
+pub fn duration(text: &str) -> Option<f64> {
+    let (value, unit) = text.trim().split_once(' ')?;
+    let scale = match unit.trim() {
+        "ps" => 1e-3,
+        "ns" => 1.0,
+        "µs" | "us" => 1e3,
+        "ms" => 1e6,
+        "s" => 1e9,
+        _ => return None,
+    };
+    // round to picoseconds to drop the float noise of scaling
+    Some((value.parse::<f64>().ok()? * scale * 1e3).round() / 1e3)
+}
+
+/// A divan size like `1.5 KB` in bytes.
+pub fn bytes(text: &str) -> Option<f64> {
+    let (value, unit) = text.trim().split_once(' ')?;
+    let scale = match unit.trim() {
+        "B" => 1.0,
+        "KB" => 1e3,
+        "MB" => 1e6,
+        "GB" => 1e9,
+        "KiB" => 1024.0,
+        "MiB" => 1024.0 * 1024.0,
+        "GiB" => 1024.0 * 1024.0 * 1024.0,
+        _ => return None,
+    };
+    Some(value.parse::<f64>().ok()? * scale)
+}
+
+/// `nanos` in the most readable unit, e.g. `490µs`.
+pub fn format_duration(nanos: f64) -> String {
+    let (value, unit) = match nanos {
+        n if n >= 1e9 => (n / 1e9, "s"),
+        n if n >= 1e6 => (n / 1e6, "ms"),
+        n if n >= 1e3 => (n / 1e3, "µs"),
+        n => (n, "ns"),
+    };
+    let precision = match value {
+        v if v >= 100.0 => 0,
+        v if v >= 10.0 => 1,
+        _ => 2,
+    };
+    format!("{:.*}{}", precision, value, unit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OUTPUT: &str = "\
+Timer precision: 20 ns
+day_06       fastest       │ slowest       │ median        │ mean          │ samples │ iters
+├─ part1                   │               │               │               │         │
+│  ├─ parse  274.7 ns      │ 2.626 µs      │ 277.7 ns      │ 305.9 ns      │ 100     │ 100
+│  │         131.1 MB/s    │ 13.71 MB/s    │ 129.6 MB/s    │ 117.7 MB/s    │         │
+│  │         max alloc:    │               │               │               │         │
+│  │           1.187       │ 1.187         │ 1.187         │ 1.187         │         │
+│  │           74 B        │ 74 B          │ 74 B          │ 74 B          │         │
+│  │         alloc:        │               │               │               │         │
+│  │           4           │ 4             │ 5             │ 4             │         │
+│  │           224 B       │ 224 B         │ 1.5 KB        │ 224 B         │         │
+│  │         dealloc:      │               │               │               │         │
+│  │           3           │ 3             │ 3             │ 3             │         │
+│  │           160 B       │ 160 B         │ 160 B         │ 160 B         │         │
+│  ╰─ total  253.3 ns      │ 404.7 ns      │ 277.6 ns      │ 274.4 ns      │ 100     │ 800
+╰─ part2                   │               │               │               │         │
+   ╰─ solve  17.52 ns      │ 18.08 ns      │ 17.73 ns      │ 17.75 ns      │ 100     │ 12800
+
+day_11                  fastest  │ slowest  │ median   │ mean     │ samples │ iters
+╰─ part1_expand_bfs     543.3 ms │ 569.3 ms │ 559.9 ms │ 559.9 ms │ 100     │ 100
+";
+
+    #[test]
+    fn test_parse() {
+        let rows = parse(OUTPUT);
+        let names = rows
+            .iter()
+            .map(|row| format!("{} {}", row.target, row.benchmark))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                "day_06 part1/parse",
+                "day_06 part1/total",
+                "day_06 part2/solve",
+                "day_11 part1_expand_bfs"
+            ],
+            names
+        );
+        assert_eq!(
+            Stats {
+                fastest: 274.7,
+                slowest: 2626.0,
+                median: 277.7,
+                mean: 305.9,
+                samples: 100,
+                iters: 100,
+            },
+            rows[0].stats
+        );
+        assert_eq!(
+            Some(Allocs {
+                count: 5.0,
+                bytes: 1500.0
+            }),
+            rows[0].allocs
+        );
+        assert_eq!(None, rows[1].allocs);
+        assert_eq!(543.3e6, rows[3].stats.fastest);
+        assert_eq!(19, rows[3].line);
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!("490µs", format_duration(490_300.0));
+        assert_eq!("17.7ns", format_duration(17.73));
+        assert_eq!("1.63ms", format_duration(1_631_000.0));
+    }
+}
@@ -0,0 +1,476 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use clap::Args;
+
+use crate::registry::{day_dir, workspace_dir};
+
+/// Year of the puzzles of this workspace.
+const YEAR: u16 = 2023;
+const BASE_URL: &str = "https://adventofcode.com";
+const SESSION_ENV: &str = "AOC_SESSION";
+const BASE_URL_ENV: &str = "AOC_BASE_URL";
+const CACHE_ENV: &str = "AOC_CACHE";
+const CONTACT_ENV: &str = "AOC_CONTACT";
+/// Least time between two requests to the site, across runs.
+const INTERVAL: Duration = Duration::from_secs(3);
+
+/// User-Agent of the requests, with the `contact` that the site asks of
+/// automated clients.
+fn user_agent(contact: &str) -> String {
+    format!(
+        "advent-of-code-2023 input fetcher {} (+{}; local cache, one request every 3 s)",
+        env!("CARGO_PKG_VERSION"),
+        contact
+    )
+}
+
+#[derive(Args, Debug)]
+pub struct FetchArgs {
+    /// Day to fetch, e.g. `5`
+    day: u8,
+
+    /// Value of the `session` cookie of a logged in adventofcode.com.
+    /// Falls back to `$AOC_SESSION`
+    #[arg(long)]
+    session: Option<String>,
+
+    /// How the site can reach whoever runs this, e.g. an email or the URL of
+    /// the repository, sent in the User-Agent. Falls back to `$AOC_CONTACT`
+    #[arg(long)]
+    contact: Option<String>,
+
+    /// Site to fetch from, e.g. a local stand-in. Falls back to
+    /// `$AOC_BASE_URL`, then adventofcode.com
+    #[arg(long)]
+    base_url: Option<String>,
+
+    /// Directory of the cache. Falls back to `$AOC_CACHE`, then `.aoc-cache`
+    /// in the workspace
+    #[arg(long)]
+    cache: Option<PathBuf>,
+
+    /// Fetch the puzzle text again, e.g. once part 2 is unlocked
+    #[arg(long)]
+    refresh: bool,
+
+    /// Print the puzzle text
+    #[arg(long)]
+    puzzle: bool,
+
+    /// Replace an `input.txt` that differs from the fetched input
+    #[arg(long)]
+    force: bool,
+}
+
+/// Fetches the input and puzzle text of a day through the cache and writes
+/// the input to the day crate's `input.txt`.
+pub fn fetch(args: &FetchArgs) -> miette::Result<()> {
+    if !(1..=25).contains(&args.day) {
+        miette::bail!("there is no day {}, days go from 1 to 25", args.day);
+    }
+    let client = Client::new(
+        args.base_url
+            .clone()
+            .or_else(|| env::var(BASE_URL_ENV).ok())
+            .unwrap_or_else(|| BASE_URL.to_string()),
+        args.session.clone().or_else(|| env::var(SESSION_ENV).ok()),
+        args.contact.clone().or_else(|| env::var(CONTACT_ENV).ok()),
+        args.cache
+            .clone()
+            .or_else(|| env::var_os(CACHE_ENV).map(PathBuf::from))
+            .unwrap_or_else(|| workspace_dir().join(".aoc-cache")),
+    );
+
+    let dir = day_dir(args.day);
+    if !dir.is_dir() {
+        miette::bail!(
+            "there is no day-{:02} crate to put the input in, create it with `just create day-{:02}`",
+            args.day,
+            args.day
+        );
+    }
+    let input = client.input(args.day)?;
+    let path = dir.join("input.txt");
+    if install_input(&path, &input, args.force)? {
+        println!("wrote {}", path.display());
+    } else {
+        println!("{} is up to date", path.display());
+    }
+
+    let puzzle = client.puzzle(args.day, args.refresh)?;
+    if args.puzzle {
+        println!("\n{}", puzzle_text(&puzzle));
+    }
+    Ok(())
+}
+
+/// Client of the site, which keeps every response in a cache on disk and
+/// waits its turn before each request.
+#[derive(Debug)]
+pub struct Client {
+    base_url: String,
+    session: Option<String>,
+    contact: Option<String>,
+    cache: PathBuf,
+    interval: Duration,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    pub fn new(
+        base_url: String,
+        session: Option<String>,
+        contact: Option<String>,
+        cache: PathBuf,
+    ) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session,
+            contact: contact.filter(|contact| !contact.trim().is_empty()),
+            cache,
+            interval: INTERVAL,
+            agent: ureq::AgentBuilder::new()
+                .timeout(Duration::from_secs(30))
+                .build(),
+        }
+    }
+
+    /// The puzzle input of `day`, which never changes once fetched.
+    pub fn input(&self, day: u8) -> miette::Result<String> {
+        self.cached(
+            &format!("{}/day/{}/input", YEAR, day),
+            day,
+            "input.txt",
+            false,
+        )
+    }
+
+    /// The page of `day`, whose puzzle text gains part 2 once part 1 is
+    /// solved, so `refresh` fetches it again.
+    pub fn puzzle(&self, day: u8, refresh: bool) -> miette::Result<String> {
+        self.cached(
+            &format!("{}/day/{}", YEAR, day),
+            day,
+            "puzzle.html",
+            refresh,
+        )
+    }
+
+    /// The response to `path`, from the file `name` of the day in the cache
+    /// unless it is not there yet or `refresh` is set.
+    fn cached(&self, path: &str, day: u8, name: &str, refresh: bool) -> miette::Result<String> {
+        let file = self
+            .cache
+            .join(YEAR.to_string())
+            .join(format!("day-{:02}", day))
+            .join(name);
+        if !refresh {
+            if let Ok(contents) = fs::read_to_string(&file) {
+                return Ok(contents);
+            }
+        }
+        let contents = self.get(path)?;
+        if let Some(dir) = file.parent() {
+            fs::create_dir_all(dir)
+                .map_err(|err| miette::miette!("failed to create {}: {}", dir.display(), err))?;
+        }
+        fs::write(&file, &contents)
+            .map_err(|err| miette::miette!("failed to write {}: {}", file.display(), err))?;
+        Ok(contents)
+    }
+
+    fn get(&self, path: &str) -> miette::Result<String> {
+        let url = format!("{}/{}", self.base_url, path);
+        let Some(contact) = &self.contact else {
+            miette::bail!(
+                "adventofcode.com asks automated clients for contact details, pass an email or the URL of this repository with --contact or ${}",
+                CONTACT_ENV
+            );
+        };
+        let mut request = self.agent.get(&url).set("User-Agent", &user_agent(contact));
+        if let Some(session) = &self.session {
+            request = request.set("Cookie", &format!("session={}", session));
+        } else if path.ends_with("/input") {
+            miette::bail!(
+                "inputs differ by user, pass the `session` cookie of adventofcode.com with --session or ${}",
+                SESSION_ENV
+            );
+        }
+        self.wait_turn()?;
+        match request.call() {
+            Ok(response) => response
+                .into_string()
+                .map_err(|err| miette::miette!("failed to read {}: {}", url, err)),
+            Err(ureq::Error::Status(404, _)) => {
+                miette::bail!("{} is not there, the puzzle may not be out yet", url)
+            }
+            Err(ureq::Error::Status(400 | 500, _)) if self.session.is_some() => miette::bail!(
+                "{} refused the session cookie, log in again and copy the new one",
+                url
+            ),
+            Err(ureq::Error::Status(status, _)) => {
+                miette::bail!("{} answered with status {}", url, status)
+            }
+            Err(err) => miette::bail!("failed to fetch {}: {}", url, err),
+        }
+    }
+
+    /// Sleeps until `interval` has passed since the last request, which is
+    /// kept in the cache so that runs in a row are polite too.
+    fn wait_turn(&self) -> miette::Result<()> {
+        let file = self.cache.join("last-request");
+        let now = || {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+        };
+        let last = fs::read_to_string(&file)
+            .ok()
+            .and_then(|millis| millis.trim().parse().ok())
+            .map(Duration::from_millis);
+        if let Some(wait) = last.and_then(|last| (last + self.interval).checked_sub(now())) {
+            thread::sleep(wait);
+        }
+        fs::create_dir_all(&self.cache)
+            .map_err(|err| miette::miette!("failed to create {}: {}", self.cache.display(), err))?;
+        fs::write(&file, now().as_millis().to_string())
+            .map_err(|err| miette::miette!("failed to write {}: {}", file.display(), err))
+    }
+}
+
+/// Writes `input` to `path` unless it is there already. An `input.txt` with
+/// other contents is only replaced with `force`, an empty one always is.
+/// Whether it wrote the file.
+fn install_input(path: &Path, input: &str, force: bool) -> miette::Result<bool> {
+    match fs::read_to_string(path) {
+        Ok(existing) if existing == input => return Ok(false),
+        Ok(existing) if !existing.is_empty() && !force => miette::bail!(
+            "{} differs from the fetched input, pass --force to replace it",
+            path.display()
+        ),
+        _ => {}
+    }
+    fs::write(path, input)
+        .map_err(|err| miette::miette!("failed to write {}: {}", path.display(), err))?;
+    Ok(true)
+}
+
+/// The text of the `<article>`s of a puzzle page, without the markup.
+fn puzzle_text(page: &str) -> String {
+    let mut articles = Vec::new();
+    let mut rest = page;
+    while let Some(start) = rest.find("<article") {
+        let Some(end) = rest[start..].find("</article>") else {
+            break;
+        };
+        articles.push(strip_tags(&rest[start..start + end]));
+        rest = &rest[start + end..];
+    }
+    articles.join("\n\n")
+}
+
+fn strip_tags(html: &str) -> String {
+    let mut text = String::new();
+    let mut rest = html;
+    while let Some(start) = rest.find('<') {
+        text.push_str(&rest[..start]);
+        let end = rest[start..]
+            .find('>')
+            .map_or(rest.len(), |end| start + end + 1);
+        let tag = &rest[start..end];
+        let block = ["</h2", "</p", "</pre", "</li"]
+            .iter()
+            .any(|closing| tag.starts_with(closing));
+        if block && !text.ends_with('\n') {
+            text.push('\n');
+        }
+        rest = &rest[end..];
+    }
+    text.push_str(rest);
+    text.trim()
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{Arc, Mutex},
+        time::Instant,
+    };
+
+    use tiny_http::{Response, Server};
+
+    use super::*;
+
+    const PAGE: &str = "<html><main><article class=\"day-desc\"><h2>--- Day 5: Seeds ---</h2><p>Which seed has the &lt;lowest&gt; location?</p>\n</article><p>Answer:</p></main></html>";
+
+    /// User-Agent of each request to a [`stand_in`].
+    type Requests = Arc<Mutex<Vec<Option<String>>>>;
+
+    /// A stand-in for the site on a free local port, which only gives the
+    /// input of day 5 to the session `token` and records the User-Agent of
+    /// its requests.
+    fn stand_in() -> (String, Requests) {
+        let server = Server::http("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/", server.server_addr().to_ip().unwrap());
+        let requests = Requests::default();
+        let seen = Arc::clone(&requests);
+        thread::spawn(move || {
+            for request in server.incoming_requests() {
+                let header = |name: &'static str| {
+                    request
+                        .headers()
+                        .iter()
+                        .find(|header| header.field.equiv(name))
+                        .map(|header| header.value.to_string())
+                };
+                seen.lock().unwrap().push(header("User-Agent"));
+                let session = header("Cookie");
+                let (status, body) = match (request.url(), session.as_deref()) {
+                    ("/2023/day/5/input", Some("session=token")) => (200, "seeds: 79 14\n"),
+                    ("/2023/day/5/input", _) => (400, "Please log in to get your puzzle input."),
+                    ("/2023/day/5", _) => (200, PAGE),
+                    _ => (404, "404 Not Found"),
+                };
+                let response = Response::from_string(body).with_status_code(status);
+                request.respond(response).unwrap();
+            }
+        });
+        (url, requests)
+    }
+
+    fn count(requests: &Requests) -> usize {
+        requests.lock().unwrap().len()
+    }
+
+    fn client(url: &str, session: Option<&str>, cache: &Path) -> Client {
+        Client {
+            interval: Duration::ZERO,
+            ..Client::new(
+                url.to_string(),
+                session.map(str::to_string),
+                Some("me@example.com".to_string()),
+                cache.to_path_buf(),
+            )
+        }
+    }
+
+    #[test]
+    fn test_cache() -> miette::Result<()> {
+        let (url, requests) = stand_in();
+        let cache = tempfile::tempdir().unwrap();
+        let client = client(&url, Some("token"), cache.path());
+        assert_eq!("seeds: 79 14\n", client.input(5)?);
+        assert_eq!("seeds: 79 14\n", client.input(5)?);
+        assert_eq!(PAGE, client.puzzle(5, false)?);
+        assert_eq!(2, count(&requests));
+        assert!(cache.path().join("2023/day-05/input.txt").is_file());
+
+        assert_eq!(PAGE, client.puzzle(5, true)?);
+        assert_eq!(3, count(&requests));
+        let agent = user_agent("me@example.com");
+        assert!(agent.contains("(+me@example.com; "));
+        assert_eq!(vec![Some(agent); 3], *requests.lock().unwrap());
+        Ok(())
+    }
+
+    #[test]
+    fn test_contact() -> miette::Result<()> {
+        let (url, requests) = stand_in();
+        let cache = tempfile::tempdir().unwrap();
+        let anonymous = |contact: Option<&str>| Client {
+            interval: Duration::ZERO,
+            ..Client::new(
+                url.clone(),
+                Some("token".to_string()),
+                contact.map(str::to_string),
+                cache.path().to_path_buf(),
+            )
+        };
+        for contact in [None, Some(" ")] {
+            let err = anonymous(contact).input(5).unwrap_err();
+            assert!(err.to_string().contains("--contact"));
+        }
+        assert_eq!(0, count(&requests));
+
+        // cached responses need no contact
+        client(&url, Some("token"), cache.path()).input(5)?;
+        assert_eq!("seeds: 79 14\n", anonymous(None).input(5)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_session() {
+        let (url, requests) = stand_in();
+        let cache = tempfile::tempdir().unwrap();
+        let message = |session| {
+            client(&url, session, cache.path())
+                .input(5)
+                .unwrap_err()
+                .to_string()
+        };
+        assert!(message(None).contains("pass the `session` cookie"));
+        assert_eq!(0, count(&requests));
+        assert!(message(Some("expired")).contains("refused the session cookie"));
+        assert!(!cache.path().join("2023/day-05/input.txt").exists());
+    }
+
+    #[test]
+    fn test_not_out_yet() {
+        let (url, _) = stand_in();
+        let cache = tempfile::tempdir().unwrap();
+        let err = client(&url, Some("token"), cache.path())
+            .input(6)
+            .unwrap_err();
+        assert!(err.to_string().contains("may not be out yet"));
+    }
+
+    #[test]
+    fn test_rate_limit() -> miette::Result<()> {
+        let (url, requests) = stand_in();
+        let cache = tempfile::tempdir().unwrap();
+        let polite = || Client {
+            interval: Duration::from_millis(300),
+            ..client(&url, None, cache.path())
+        };
+        let start = Instant::now();
+        let first = polite();
+        first.puzzle(5, true)?;
+        first.puzzle(5, true)?;
+        // the next run waits for the last request of the one before
+        polite().puzzle(5, true)?;
+        assert!(start.elapsed() >= Duration::from_millis(600));
+        assert_eq!(3, count(&requests));
+        Ok(())
+    }
+
+    #[test]
+    fn test_install_input() -> miette::Result<()> {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("input.txt");
+        fs::write(&path, "").unwrap();
+        assert!(install_input(&path, "1 2 3\n", false)?);
+        assert!(!install_input(&path, "1 2 3\n", false)?);
+        assert!(install_input(&path, "4 5 6\n", false).is_err());
+        assert!(install_input(&path, "4 5 6\n", true)?);
+        assert_eq!("4 5 6\n", fs::read_to_string(&path).unwrap());
+        Ok(())
+    }
+
+    #[test]
+    fn test_puzzle_text() {
+        assert_eq!(
+            "--- Day 5: Seeds ---\nWhich seed has the <lowest> location?",
+            puzzle_text(PAGE)
+        );
+    }
+}
@@ -0,0 +1,116 @@
+use aoc_common::generate::{generate_seeded, Shape};
+use clap::Args;
+
+#[derive(Args, Debug)]
+pub struct GenerateArgs {
+    /// Day to generate an input for
+    day: u8,
+
+    /// About how many lines, or rows of a grid, the input has
+    #[arg(long, default_value_t = 32)]
+    size: usize,
+
+    /// Share of the cells of a grid that are galaxies, rocks, symbols or the
+    /// inside of the loop, random if omitted
+    #[arg(long)]
+    density: Option<f64>,
+
+    /// Columns of a grid, or length of the seed ranges of day 5, random if
+    /// omitted
+    #[arg(long)]
+    width: Option<usize>,
+
+    /// Seed of the input, a fresh one if omitted
+    #[arg(long)]
+    seed: Option<u64>,
+}
+
+/// Prints a random input to stdout, and its seed and known answers to stderr.
+pub fn print(args: &GenerateArgs) -> miette::Result<()> {
+    if let Some(density) = args
+        .density
+        .filter(|density| !(0.0..=1.0).contains(density))
+    {
+        miette::bail!("the density must be between 0 and 1, got {}", density);
+    }
+    let seed = args.seed.unwrap_or_else(rand::random);
+    let shape = Shape {
+        size: args.size,
+        density: args.density,
+        width: args.width,
+    };
+    let generated = generate_seeded(args.day, shape, seed)
+        .ok_or_else(|| miette::miette!("day {} has no generator", args.day))?;
+
+    println!("{}", generated.input);
+    eprintln!("seed {}", seed);
+    for (part, answer) in (1..).zip(&generated.answers) {
+        match answer {
+            Some(answer) => eprintln!("part{} {}", part, answer),
+            None => eprintln!("part{} unknown", part),
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::generate::{generate_seeded, Generated, Shape};
+
+    use crate::{
+        differential,
+        registry::{days, Selection, CHECKS},
+    };
+
+    fn inputs(day: u8) -> impl Iterator<Item = Generated> {
+        [1, 2, 5, 16, 32].into_iter().flat_map(move |size| {
+            (0..4).map(move |seed| {
+                generate_seeded(day, Shape::new(size), seed).expect("every day has a generator")
+            })
+        })
+    }
+
+    #[test]
+    fn test_deterministic() {
+        for day in days() {
+            let first = generate_seeded(day, Shape::new(8), 7);
+            let second = generate_seeded(day, Shape::new(8), 7);
+            assert!(first.is_some(), "day {} has no generator", day);
+            assert_eq!(first, second);
+        }
+    }
+
+    #[test]
+    fn test_inputs_are_valid() {
+        for &(day, check) in CHECKS {
+            for generated in inputs(day) {
+                let errors = check(&generated.input);
+                assert!(
+                    errors.is_empty(),
+                    "day {} generated an invalid input: {:?}\n{}",
+                    day,
+                    errors,
+                    generated.input
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_known_answers() {
+        for variant in Selection::default().variants() {
+            for generated in inputs(variant.day) {
+                let Some(answer) = &generated.answers[variant.part as usize - 1] else {
+                    continue;
+                };
+                assert_eq!(
+                    Ok(answer.to_string()),
+                    differential::run(variant, &generated.input),
+                    "{} on\n{}",
+                    variant.label(),
+                    generated.input
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,175 @@
+use std::{
+    fs,
+    io::{self, Write},
+    path::Path,
+};
+
+use crate::divan::Stats;
+
+const HEADER: &str =
+    "timestamp\tcommit\tmachine\trun\tnote\tday\tbenchmark\tfastest_ns\tslowest_ns\tmedian_ns\tmean_ns\tsamples\titers";
+
+/// One benchmark of one run, as stored in the bench history.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    /// Seconds since the Unix epoch.
+    pub timestamp: u64,
+    pub commit: String,
+    pub machine: String,
+    /// Identifies the `cargo bench` run the record came from, e.g. its
+    /// timestamp or the line of an imported `.bench.txt` table.
+    pub run: String,
+    /// What changed before the run, e.g. `after refactoring part1`.
+    pub note: String,
+    pub day: u8,
+    /// Path of the benchmark in the divan tree, e.g. `part2_binary_search/solve`.
+    pub benchmark: String,
+    pub stats: Stats,
+}
+
+impl Record {
+    fn to_line(&self) -> String {
+        let clean = |field: &str| field.replace(['\t', '\n'], " ");
+        let Stats {
+            fastest,
+            slowest,
+            median,
+            mean,
+            samples,
+            iters,
+        } = &self.stats;
+        format!(
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            self.timestamp,
+            clean(&self.commit),
+            clean(&self.machine),
+            clean(&self.run),
+            clean(&self.note),
+            self.day,
+            clean(&self.benchmark),
+            fastest,
+            slowest,
+            median,
+            mean,
+            samples,
+            iters
+        )
+    }
+
+    fn from_line(line: &str) -> Option<Self> {
+        let fields = line.split('\t').collect::<Vec<_>>();
+        let [timestamp, commit, machine, run, note, day, benchmark, fastest, slowest, median, mean, samples, iters] =
+            fields[..]
+        else {
+            return None;
+        };
+        Some(Record {
+            timestamp: timestamp.parse().ok()?,
+            commit: commit.to_string(),
+            machine: machine.to_string(),
+            run: run.to_string(),
+            note: note.to_string(),
+            day: day.parse().ok()?,
+            benchmark: benchmark.to_string(),
+            stats: Stats {
+                fastest: fastest.parse().ok()?,
+                slowest: slowest.parse().ok()?,
+                median: median.parse().ok()?,
+                mean: mean.parse().ok()?,
+                samples: samples.parse().ok()?,
+                iters: iters.parse().ok()?,
+            },
+        })
+    }
+}
+
+/// Every record of the history at `path`, oldest first. A missing file is
+/// an empty history.
+pub fn load(path: &Path) -> miette::Result<Vec<Record>> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => miette::bail!("failed to read {}: {}", path.display(), err),
+    };
+    contents
+        .lines()
+        .enumerate()
+        .skip(1)
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(n, line)| {
+            Record::from_line(line).ok_or_else(|| {
+                miette::miette!("{}:{}: malformed record `{}`", path.display(), n + 1, line)
+            })
+        })
+        .collect()
+}
+
+/// Appends `records` to the history at `path`, creating it if needed.
+pub fn append(path: &Path, records: &[Record]) -> miette::Result<()> {
+    write(path, records, true)
+}
+
+/// Replaces the history at `path` with `records`.
+pub fn save(path: &Path, records: &[Record]) -> miette::Result<()> {
+    write(path, records, false)
+}
+
+fn write(path: &Path, records: &[Record], append: bool) -> miette::Result<()> {
+    let write = || -> io::Result<()> {
+        let header = !append || !path.exists();
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .write(true)
+            .append(append)
+            .truncate(!append)
+            .open(path)?;
+        if header {
+            writeln!(file, "{}", HEADER)?;
+        }
+        for record in records {
+            writeln!(file, "{}", record.to_line())?;
+        }
+        Ok(())
+    };
+    write().map_err(|err| miette::miette!("failed to write {}: {}", path.display(), err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() -> miette::Result<()> {
+        let record = Record {
+            timestamp: 1701820800,
+            commit: "7a233f9".to_string(),
+            machine: "laptop".to_string(),
+            run: "day-05.bench.txt:31".to_string(),
+            note: "after\trefactoring".to_string(),
+            day: 5,
+            benchmark: "part2_binary_search".to_string(),
+            stats: Stats {
+                fastest: 486.9e3,
+                slowest: 586.9e3,
+                median: 490.3e3,
+                mean: 497.3e3,
+                samples: 100,
+                iters: 100,
+            },
+        };
+        let path = std::env::temp_dir().join(format!("bench-history-{}.tsv", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let records = [record.clone(), record.clone()];
+        save(&path, &records[..1])?;
+        append(&path, &records[1..])?;
+        let records = load(&path)?;
+        fs::remove_file(&path).ok();
+
+        let expected = Record {
+            note: "after refactoring".to_string(),
+            ..record
+        };
+        assert_eq!(vec![expected.clone(), expected], records);
+        Ok(())
+    }
+}
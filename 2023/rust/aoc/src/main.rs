@@ -0,0 +1,73 @@
+use std::path::PathBuf;
+
+use aoc_common::trace;
+use clap::{Parser, Subcommand};
+
+mod bench;
+mod check;
+mod differential;
+mod divan;
+mod fetch;
+mod generate;
+mod history;
+mod register;
+mod registry;
+mod report;
+mod run;
+mod verify;
+
+#[derive(Parser, Debug)]
+#[command(about = "Advent of Code 2023 solutions")]
+struct Cli {
+    /// Print a per-phase timing tree to stderr at the end, like `$AOC_TIMINGS`
+    #[arg(long, global = true)]
+    timings: bool,
+
+    /// Write a Chrome trace of the run to this file, like `$AOC_CHROME_TRACE`
+    #[arg(long, global = true, value_name = "FILE")]
+    chrome_trace: Option<PathBuf>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Run solutions and print the answer and wall time of each
+    Run(run::RunArgs),
+    /// Check answers against each day's `answers.txt`
+    Verify(verify::VerifyArgs),
+    /// Validate inputs against the structure each day assumes, listing every violation
+    Check(check::CheckArgs),
+    /// Cross-check the variants of each part on samples, the real input and random inputs
+    Diff(differential::DiffArgs),
+    /// Print a random input of a day, and the answers to it where they are known
+    Generate(generate::GenerateArgs),
+    /// Download the input and puzzle text of a day into its crate and a local cache
+    Fetch(fetch::FetchArgs),
+    /// Record benchmark timings in the bench history
+    Bench(bench::BenchArgs),
+    /// Register a day crate made from `daily_template` with the runner and the fuzz targets
+    Register(register::RegisterArgs),
+}
+
+fn main() -> miette::Result<()> {
+    let cli = Cli::parse();
+    let mut options = trace::Options::from_env();
+    options.timings |= cli.timings;
+    if let Some(path) = cli.chrome_trace {
+        options.chrome_trace = Some(path);
+    }
+    let _trace = trace::init_with(options);
+
+    match cli.command {
+        Command::Run(args) => run::run(&args),
+        Command::Verify(args) => verify::verify(&args),
+        Command::Check(args) => check::check(&args),
+        Command::Diff(args) => differential::diff(&args),
+        Command::Generate(args) => generate::print(&args),
+        Command::Fetch(args) => fetch::fetch(&args),
+        Command::Bench(args) => bench::bench(&args),
+        Command::Register(args) => register::register(&args),
+    }
+}
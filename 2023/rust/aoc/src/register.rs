@@ -0,0 +1,393 @@
+use std::{fs, path::Path};
+
+use clap::Args;
+
+use crate::registry::{day_dir, workspace_dir};
+
+#[derive(Args, Debug)]
+pub struct RegisterArgs {
+    /// Day whose crate to register, e.g. `15` for `day-15`
+    day: u8,
+}
+
+/// Adds the `part1` and `part2` of a crate made from `daily_template` to the
+/// runner's registry and dependencies, and to the fuzz targets. Days that are
+/// already registered are left alone.
+pub fn register(args: &RegisterArgs) -> miette::Result<()> {
+    let day = args.day;
+    let name = format!("day-{:02}", day);
+    if !day_dir(day).join("Cargo.toml").is_file() {
+        miette::bail!(
+            "there is no {} crate, create it with `just create {}`",
+            name,
+            name
+        );
+    }
+    let workspace = workspace_dir();
+
+    edit(&workspace.join("aoc/Cargo.toml"), |manifest| {
+        dependency(manifest, day)
+    })?;
+    edit(&workspace.join("aoc/src/registry.rs"), |registry| {
+        registry_entries(registry, day)
+    })?;
+    edit(&workspace.join("fuzz/Cargo.toml"), |manifest| {
+        let with_dependency = dependency(manifest, day);
+        let updated = fuzz_targets(with_dependency.as_deref().unwrap_or(manifest), day);
+        (updated != manifest).then_some(updated)
+    })?;
+
+    for part in 1..=2 {
+        let target = workspace.join(format!("fuzz/fuzz_targets/{}-part{}.rs", name, part));
+        if !target.exists() {
+            write(&target, &fuzz_target(day, part))?;
+        }
+    }
+    // the examples are the first seeds of the fuzz targets, as for every day
+    let seeds = workspace.join("fuzz/seeds").join(&name);
+    fs::create_dir_all(&seeds)
+        .map_err(|err| miette::miette!("failed to create {}: {}", seeds.display(), err))?;
+    if let Ok(entries) = fs::read_dir(day_dir(day).join("examples")) {
+        for path in entries.flatten().map(|entry| entry.path()) {
+            let Some(file_name) = path.file_name().and_then(|name| name.to_str()) else {
+                continue;
+            };
+            let seed = seeds.join(format!("example-{}", file_name));
+            if !seed.exists() {
+                fs::copy(&path, &seed).map_err(|err| {
+                    miette::miette!(
+                        "failed to copy {} to {}: {}",
+                        path.display(),
+                        seed.display(),
+                        err
+                    )
+                })?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Rewrites the file at `path` with `update`, which returns `None` if there
+/// is nothing to change.
+fn edit(path: &Path, update: impl FnOnce(&str) -> Option<String>) -> miette::Result<()> {
+    let contents = fs::read_to_string(path)
+        .map_err(|err| miette::miette!("failed to read {}: {}", path.display(), err))?;
+    match update(&contents) {
+        Some(updated) => {
+            write(path, &updated)?;
+            println!("registered in {}", path.display());
+        }
+        None => println!("already registered in {}", path.display()),
+    }
+    Ok(())
+}
+
+fn write(path: &Path, contents: &str) -> miette::Result<()> {
+    fs::write(path, contents)
+        .map_err(|err| miette::miette!("failed to write {}: {}", path.display(), err))
+}
+
+/// Inserts `entry` into the list of per-day lines of `text` that `day_of`
+/// tells the day of, before the first line of a later day, or else before
+/// the first line after the list that `is_end`. `None` if `day` is already
+/// in the list.
+fn insert(
+    text: &str,
+    day: u8,
+    entry: &str,
+    day_of: impl Fn(&str) -> Option<u8>,
+    is_end: impl Fn(&str) -> bool,
+) -> Option<String> {
+    let mut lines = text.lines().collect::<Vec<_>>();
+    let days = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, line)| day_of(line).map(|d| (i, d)))
+        .collect::<Vec<_>>();
+    if days.iter().any(|&(_, d)| d == day) {
+        return None;
+    }
+    let at = match days.iter().find(|&&(_, d)| d > day) {
+        // keep the attributes of the later day's first line on it
+        Some(&(mut i, _)) => {
+            while i > 0 && lines[i - 1].trim_start().starts_with("#[") {
+                i -= 1;
+            }
+            i
+        }
+        None => {
+            let last = days.last().map_or(0, |&(i, _)| i);
+            (last + 1..lines.len())
+                .find(|&i| is_end(lines[i]))
+                .unwrap_or(lines.len())
+        }
+    };
+    lines.splice(at..at, entry.lines());
+    Some(lines.join("\n") + "\n")
+}
+
+/// The number `n` in `prefix<n>` at the start of `line`, after any indent.
+fn number_after(line: &str, prefix: &str) -> Option<u8> {
+    let rest = line.trim_start().strip_prefix(prefix)?;
+    let end = rest
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(rest.len());
+    rest[..end].parse().ok()
+}
+
+/// Adds the day crate to the `[dependencies]` of a manifest.
+fn dependency(manifest: &str, day: u8) -> Option<String> {
+    insert(
+        manifest,
+        day,
+        &format!("day-{:02} = {{ path = \"../day-{:02}\" }}", day, day),
+        |line| number_after(line, "day-").filter(|_| line.contains("= { path = \"../day-")),
+        |line| line.trim().is_empty(),
+    )
+}
+
+/// Adds the day to the `VARIANTS` and the `CHECKS` of the registry, each
+/// unless it is already in there, e.g. after a manual edit.
+fn registry_entries(registry: &str, day: u8) -> Option<String> {
+    let with_variants = variants(registry, day);
+    let updated = with_variants.as_deref().unwrap_or(registry);
+    check(updated, day).or(with_variants)
+}
+
+/// Adds both parts of the day to the `VARIANTS` of the registry.
+fn variants(registry: &str, day: u8) -> Option<String> {
+    let entry = (1..=2)
+        .map(|part| {
+            format!(
+                "    variant!({}, {}, day_{:02}::part{}),",
+                day, part, day, part
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    insert(
+        registry,
+        day,
+        &entry,
+        |line| number_after(line, "variant!("),
+        |line| line == "];",
+    )
+}
+
+/// Adds the input check of the day to the `CHECKS` of the registry.
+fn check(registry: &str, day: u8) -> Option<String> {
+    insert(
+        registry,
+        day,
+        &format!(
+            "    ({}, <day_{:02}::Day{:02} as Solution>::check),",
+            day, day, day
+        ),
+        |line| number_after(line, "(").filter(|_| line.contains("as Solution>::check")),
+        |line| line == "];",
+    )
+}
+
+/// Adds a `[[bin]]` fuzz target for both parts of the day, in day order.
+fn fuzz_targets(manifest: &str, day: u8) -> String {
+    let mut sections = manifest.trim_end().split("\n\n").collect::<Vec<_>>();
+    let day_of = |section: &str| {
+        section
+            .strip_prefix("[[bin]]\nname = \"day-")
+            .and_then(|rest| number_after(rest, ""))
+    };
+    let targets = (1..=2)
+        .map(|part| {
+            format!(
+                "[[bin]]\nname = \"day-{:02}-part{}\"\npath = \"fuzz_targets/day-{:02}-part{}.rs\"\ntest = false\ndoc = false\nbench = false",
+                day, part, day, part
+            )
+        })
+        .collect::<Vec<_>>();
+    if sections.iter().any(|section| day_of(section) == Some(day)) {
+        return manifest.to_string();
+    }
+    let at = sections
+        .iter()
+        .position(|section| day_of(section).is_some_and(|d| d > day))
+        .unwrap_or(sections.len());
+    sections.splice(at..at, targets.iter().map(String::as_str));
+    sections.join("\n\n") + "\n"
+}
+
+fn fuzz_target(day: u8, part: u8) -> String {
+    format!(
+        "#![no_main]\n\nuse libfuzzer_sys::fuzz_target;\n\nfuzz_target!(|input: &str| {{\n    let _ = day_{:02}::part{}::process(input);\n}});\n",
+        day, part
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(lines: &[&str]) -> String {
+        lines.join("\n") + "\n"
+    }
+
+    #[test]
+    fn test_dependency() {
+        let manifest = lines(&[
+            "[dependencies]",
+            "clap = { workspace = true }",
+            "day-01 = { path = \"../day-01\" }",
+            "day-14 = { path = \"../day-14\" }",
+            "",
+            "[features]",
+        ]);
+        assert_eq!(
+            Some(lines(&[
+                "[dependencies]",
+                "clap = { workspace = true }",
+                "day-01 = { path = \"../day-01\" }",
+                "day-12 = { path = \"../day-12\" }",
+                "day-14 = { path = \"../day-14\" }",
+                "",
+                "[features]",
+            ])),
+            dependency(&manifest, 12)
+        );
+        assert_eq!(
+            Some(lines(&[
+                "[dependencies]",
+                "clap = { workspace = true }",
+                "day-01 = { path = \"../day-01\" }",
+                "day-14 = { path = \"../day-14\" }",
+                "day-15 = { path = \"../day-15\" }",
+                "",
+                "[features]",
+            ])),
+            dependency(&manifest, 15)
+        );
+        assert_eq!(None, dependency(&manifest, 14));
+    }
+
+    #[test]
+    fn test_registry() {
+        let registry = lines(&[
+            "pub static VARIANTS: &[Variant] = &[",
+            "    variant!(11, 1, day_11::part1),",
+            "    variant!(11, 2, day_11::part2, |input| {",
+            "        day_11::part2::process(input, 2)",
+            "    }),",
+            "    #[cfg(feature = \"parallel\")]",
+            "    variant!(13, 1, day_13::part1_parallel),",
+            "];",
+            "",
+            "pub static CHECKS: &[(u8, Check)] = &[",
+            "    (11, <day_11::Day11 as Solution>::check),",
+            "    (13, <day_13::Day13 as Solution>::check),",
+            "];",
+        ]);
+        assert_eq!(
+            Some(lines(&[
+                "pub static VARIANTS: &[Variant] = &[",
+                "    variant!(11, 1, day_11::part1),",
+                "    variant!(11, 2, day_11::part2, |input| {",
+                "        day_11::part2::process(input, 2)",
+                "    }),",
+                "    variant!(12, 1, day_12::part1),",
+                "    variant!(12, 2, day_12::part2),",
+                "    #[cfg(feature = \"parallel\")]",
+                "    variant!(13, 1, day_13::part1_parallel),",
+                "];",
+                "",
+                "pub static CHECKS: &[(u8, Check)] = &[",
+                "    (11, <day_11::Day11 as Solution>::check),",
+                "    (12, <day_12::Day12 as Solution>::check),",
+                "    (13, <day_13::Day13 as Solution>::check),",
+                "];",
+            ])),
+            registry_entries(&registry, 12)
+        );
+        assert_eq!(
+            Some(lines(&[
+                "pub static VARIANTS: &[Variant] = &[",
+                "    variant!(11, 1, day_11::part1),",
+                "    variant!(11, 2, day_11::part2, |input| {",
+                "        day_11::part2::process(input, 2)",
+                "    }),",
+                "    #[cfg(feature = \"parallel\")]",
+                "    variant!(13, 1, day_13::part1_parallel),",
+                "    variant!(14, 1, day_14::part1),",
+                "    variant!(14, 2, day_14::part2),",
+                "];",
+                "",
+                "pub static CHECKS: &[(u8, Check)] = &[",
+                "    (11, <day_11::Day11 as Solution>::check),",
+                "    (13, <day_13::Day13 as Solution>::check),",
+                "    (14, <day_14::Day14 as Solution>::check),",
+                "];",
+            ])),
+            registry_entries(&registry, 14)
+        );
+        assert_eq!(None, variants(&registry, 13));
+    }
+
+    #[test]
+    fn test_missing_check() {
+        // day 13 has its variants but not its check
+        let registry = lines(&[
+            "pub static VARIANTS: &[Variant] = &[",
+            "    variant!(13, 1, day_13::part1),",
+            "];",
+            "",
+            "pub static CHECKS: &[(u8, Check)] = &[",
+            "    (11, <day_11::Day11 as Solution>::check),",
+            "];",
+        ]);
+        assert_eq!(
+            Some(lines(&[
+                "pub static VARIANTS: &[Variant] = &[",
+                "    variant!(13, 1, day_13::part1),",
+                "];",
+                "",
+                "pub static CHECKS: &[(u8, Check)] = &[",
+                "    (11, <day_11::Day11 as Solution>::check),",
+                "    (13, <day_13::Day13 as Solution>::check),",
+                "];",
+            ])),
+            registry_entries(&registry, 13)
+        );
+        assert_eq!(
+            None,
+            registry_entries(&registry_entries(&registry, 13).unwrap(), 13)
+        );
+    }
+
+    #[test]
+    fn test_fuzz_targets() {
+        let target = |day| {
+            format!(
+                "[[bin]]\nname = \"day-{:02}-part1\"\npath = \"fuzz_targets/day-{:02}-part1.rs\"",
+                day, day
+            )
+        };
+        let manifest = format!(
+            "[workspace]\nmembers = [\".\"]\n\n{}\n\n{}\n",
+            target(1),
+            target(14)
+        );
+        let registered = fuzz_targets(&manifest, 12);
+        let names = registered
+            .lines()
+            .filter(|line| line.starts_with("name = "))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                "name = \"day-01-part1\"",
+                "name = \"day-12-part1\"",
+                "name = \"day-12-part2\"",
+                "name = \"day-14-part1\"",
+            ],
+            names
+        );
+        assert_eq!(registered, fuzz_targets(&registered, 12));
+    }
+}
@@ -0,0 +1,235 @@
+use std::path::{Path, PathBuf};
+
+use aoc_common::{errors::AocError, solution::Solution};
+
+/// Solves a whole puzzle input and renders the answer.
+pub type Process = fn(&str) -> miette::Result<String>;
+
+/// A single `process` function of a day crate, e.g. `day_05::part2_binary_search`.
+#[derive(Debug)]
+pub struct Variant {
+    pub day: u8,
+    pub part: u8,
+    /// Name of the module the `process` function lives in.
+    pub module: &'static str,
+    /// Slow variants only run when they are selected with `--variant` or `--slow`.
+    pub slow: bool,
+    pub process: Process,
+}
+
+impl Variant {
+    const fn slow(self) -> Self {
+        Self { slow: true, ..self }
+    }
+
+    /// Module suffix after `partN_`, `None` for the plain `partN` module.
+    pub fn name(&self) -> Option<&'static str> {
+        self.module
+            .strip_prefix(&format!("part{}", self.part))
+            .and_then(|suffix| suffix.strip_prefix('_'))
+    }
+
+    pub fn label(&self) -> String {
+        format!("day-{:02} {}", self.day, self.module)
+    }
+
+    /// The `input.txt` bundled with the day crate.
+    pub fn input_path(&self) -> PathBuf {
+        day_dir(self.day).join("input.txt")
+    }
+}
+
+/// Root of the cargo workspace.
+pub fn workspace_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("the aoc crate is in the workspace")
+        .to_path_buf()
+}
+
+/// Directory of the crate of `day`.
+pub fn day_dir(day: u8) -> PathBuf {
+    workspace_dir().join(format!("day-{:02}", day))
+}
+
+/// Every day with a registered variant, in order.
+pub fn days() -> Vec<u8> {
+    let mut days = VARIANTS
+        .iter()
+        .map(|variant| variant.day)
+        .collect::<Vec<_>>();
+    days.dedup();
+    days
+}
+
+/// Registers `$krate::$module::process` as part `$part` of day `$day`.
+/// An explicit closure can be given for solvers that take extra arguments.
+macro_rules! variant {
+    ($day:literal, $part:literal, $krate:ident::$module:ident) => {
+        variant!($day, $part, $krate::$module, $krate::$module::process)
+    };
+    ($day:literal, $part:literal, $krate:ident::$module:ident, $process:expr) => {
+        Variant {
+            day: $day,
+            part: $part,
+            module: stringify!($module),
+            slow: false,
+            process: |input| Ok(($process)(input)?.to_string()),
+        }
+    };
+}
+
+pub static VARIANTS: &[Variant] = &[
+    variant!(1, 1, day_01::part1),
+    #[cfg(feature = "parallel")]
+    variant!(1, 1, day_01::part1_parallel),
+    variant!(1, 2, day_01::part2),
+    variant!(1, 2, day_01::part2_aho),
+    #[cfg(feature = "parallel")]
+    variant!(1, 2, day_01::part2_parallel),
+    variant!(2, 1, day_02::part1),
+    #[cfg(feature = "parallel")]
+    variant!(2, 1, day_02::part1_parallel),
+    variant!(2, 2, day_02::part2),
+    #[cfg(feature = "parallel")]
+    variant!(2, 2, day_02::part2_parallel),
+    variant!(3, 1, day_03::part1),
+    variant!(3, 2, day_03::part2),
+    variant!(4, 1, day_04::part1),
+    variant!(4, 2, day_04::part2),
+    variant!(5, 1, day_05::part1_binary_search),
+    variant!(5, 1, day_05::part1_btree),
+    #[cfg(feature = "parallel")]
+    variant!(5, 1, day_05::part1_parallel),
+    variant!(5, 2, day_05::part2_binary_search),
+    variant!(5, 2, day_05::part2_btree).slow(),
+    #[cfg(feature = "parallel")]
+    variant!(5, 2, day_05::part2_parallel),
+    variant!(6, 1, day_06::part1),
+    variant!(6, 2, day_06::part2),
+    variant!(7, 1, day_07::part1),
+    variant!(7, 2, day_07::part2),
+    variant!(8, 1, day_08::part1),
+    variant!(8, 2, day_08::part2),
+    variant!(9, 1, day_09::part1),
+    #[cfg(feature = "parallel")]
+    variant!(9, 1, day_09::part1_parallel),
+    variant!(9, 2, day_09::part2),
+    #[cfg(feature = "parallel")]
+    variant!(9, 2, day_09::part2_parallel),
+    variant!(10, 1, day_10::part1),
+    variant!(10, 2, day_10::part2),
+    variant!(11, 1, day_11::part1_expand),
+    variant!(11, 1, day_11::part1_expand_bfs).slow(),
+    variant!(11, 1, day_11::part1_no_expand),
+    variant!(11, 1, day_11::part1_no_expand_opt),
+    #[cfg(feature = "parallel")]
+    variant!(11, 1, day_11::part1_parallel),
+    variant!(11, 2, day_11::part2, |input| day_11::part2::process(
+        input,
+        day_11::Params::default().expansion_factor
+    )),
+    #[cfg(feature = "parallel")]
+    variant!(11, 2, day_11::part2_parallel, |input| {
+        day_11::part2_parallel::process(input, day_11::Params::default().expansion_factor)
+    }),
+    variant!(13, 1, day_13::part1),
+    #[cfg(feature = "parallel")]
+    variant!(13, 1, day_13::part1_parallel),
+    variant!(13, 2, day_13::part2),
+    #[cfg(feature = "parallel")]
+    variant!(13, 2, day_13::part2_parallel),
+    variant!(14, 1, day_14::part1),
+    variant!(14, 2, day_14::part2),
+];
+
+/// Every way a puzzle input breaks the structure its day assumes.
+pub type Check = fn(&str) -> Vec<AocError>;
+
+/// The input check of every registered day, in order.
+pub static CHECKS: &[(u8, Check)] = &[
+    (1, <day_01::Day01 as Solution>::check),
+    (2, <day_02::Day02 as Solution>::check),
+    (3, <day_03::Day03 as Solution>::check),
+    (4, <day_04::Day04 as Solution>::check),
+    (5, <day_05::Day05 as Solution>::check),
+    (6, <day_06::Day06 as Solution>::check),
+    (7, <day_07::Day07 as Solution>::check),
+    (8, <day_08::Day08 as Solution>::check),
+    (9, <day_09::Day09 as Solution>::check),
+    (10, <day_10::Day10 as Solution>::check),
+    (11, <day_11::Day11 as Solution>::check),
+    (13, <day_13::Day13 as Solution>::check),
+    (14, <day_14::Day14 as Solution>::check),
+];
+
+/// Which registered variants to run.
+#[derive(Debug, Default)]
+pub struct Selection<'a> {
+    pub day: Option<u8>,
+    pub part: Option<u8>,
+    pub variant: Option<&'a str>,
+    pub slow: bool,
+}
+
+impl Selection<'_> {
+    fn matches(&self, variant: &Variant) -> bool {
+        self.day.is_none_or(|day| day == variant.day)
+            && self.part.is_none_or(|part| part == variant.part)
+            && self.variant.map_or(!variant.slow || self.slow, |name| {
+                variant.name() == Some(name) || variant.module == name
+            })
+    }
+
+    pub fn variants(&self) -> impl Iterator<Item = &'static Variant> + '_ {
+        VARIANTS.iter().filter(|variant| self.matches(variant))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_variant_name() {
+        let names = VARIANTS
+            .iter()
+            .filter(|v| v.day == 5 && !v.module.ends_with("_parallel"))
+            .map(Variant::name)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                Some("binary_search"),
+                Some("btree"),
+                Some("binary_search"),
+                Some("btree")
+            ],
+            names
+        );
+    }
+
+    #[test]
+    fn test_every_day_has_a_check() {
+        let checked = CHECKS.iter().map(|&(day, _)| day).collect::<Vec<_>>();
+        assert_eq!(days(), checked);
+    }
+
+    #[test]
+    fn test_selection() {
+        let selection = Selection {
+            day: Some(5),
+            part: Some(2),
+            variant: Some("binary_search"),
+            ..Default::default()
+        };
+        let labels = selection.variants().map(Variant::label).collect::<Vec<_>>();
+        assert_eq!(vec!["day-05 part2_binary_search"], labels);
+
+        let selection = Selection {
+            day: Some(11),
+            ..Default::default()
+        };
+        let parallel = if cfg!(feature = "parallel") { 2 } else { 0 };
+        assert_eq!(4 + parallel, selection.variants().count());
+    }
+}
@@ -0,0 +1,565 @@
+use std::{fmt::Write, fs, path::Path};
+
+use crate::{
+    divan::{format_duration, Row},
+    registry::Variant,
+};
+
+/// Start and end markers of the generated section of the README.
+const README_START: &str = "<!-- bench-report -->";
+const README_END: &str = "<!-- /bench-report -->";
+
+/// Timing of one variant in the report.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub day: u8,
+    pub part: u8,
+    pub variant: String,
+    /// Median time of the whole variant, parsing included, in nanoseconds.
+    /// `None` for a registered variant that was not benched.
+    pub median: Option<f64>,
+    pub allocs: Option<f64>,
+    /// Whether it is the fastest of several variants of its part.
+    pub fastest: bool,
+    /// Whether it is registered as slow, whose benches are opt-in.
+    pub slow: bool,
+}
+
+/// One entry per variant, from the `total` rows of the benches of each day,
+/// or the rows of variants benched as a whole, followed by the `variants` of
+/// those days that have no bench.
+pub fn entries(days: &[(u8, Vec<Row>)], variants: &[Variant]) -> Vec<Entry> {
+    let mut entries = days
+        .iter()
+        .flat_map(|(day, rows)| {
+            rows.iter().filter_map(move |row| {
+                let variant = match row.benchmark.split_once('/') {
+                    Some((variant, "total")) => variant,
+                    Some(_) => return None,
+                    None => &row.benchmark,
+                };
+                let part = variant.strip_prefix("part")?.get(..1)?.parse().ok()?;
+                Some(Entry {
+                    day: *day,
+                    part,
+                    variant: variant.to_string(),
+                    median: Some(row.stats.median),
+                    allocs: row.allocs.as_ref().map(|allocs| allocs.count),
+                    fastest: false,
+                    slow: false,
+                })
+            })
+        })
+        .collect::<Vec<_>>();
+    for variant in variants {
+        if !days.iter().any(|(day, _)| *day == variant.day) {
+            continue;
+        }
+        match entries
+            .iter_mut()
+            .find(|entry| (entry.day, entry.variant.as_str()) == (variant.day, variant.module))
+        {
+            Some(entry) => entry.slow = variant.slow,
+            None => entries.push(Entry {
+                day: variant.day,
+                part: variant.part,
+                variant: variant.module.to_string(),
+                median: None,
+                allocs: None,
+                fastest: false,
+                slow: variant.slow,
+            }),
+        }
+    }
+    entries.sort_by_key(|entry| (entry.day, entry.part));
+
+    for (day, part) in parts(&entries) {
+        let variants = entries
+            .iter_mut()
+            .filter(|entry| (entry.day, entry.part) == (day, part) && entry.median.is_some())
+            .collect::<Vec<_>>();
+        if variants.len() > 1 {
+            if let Some(fastest) = variants.into_iter().min_by(|a, b| {
+                a.median
+                    .unwrap_or(f64::MAX)
+                    .total_cmp(&b.median.unwrap_or(f64::MAX))
+            }) {
+                fastest.fastest = true;
+            }
+        }
+    }
+    entries
+}
+
+/// Medians of one variant over the sizes of a scaling bench.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Curve {
+    pub day: u8,
+    /// What the sizes count, e.g. `galaxies`.
+    pub axis: String,
+    pub variant: String,
+    /// Sizes and their median times in nanoseconds, by increasing size.
+    pub points: Vec<(f64, f64)>,
+}
+
+impl Curve {
+    /// Exponent `k` of the power law `time ~ n^k` that fits the points
+    /// best, by least squares on a log-log scale.
+    pub fn growth(&self) -> Option<f64> {
+        if self.points.len() < 2 {
+            return None;
+        }
+        let logs = self
+            .points
+            .iter()
+            .map(|&(n, time)| (n.ln(), time.ln()))
+            .collect::<Vec<_>>();
+        let count = logs.len() as f64;
+        let mean_n = logs.iter().map(|(n, _)| n).sum::<f64>() / count;
+        let mean_time = logs.iter().map(|(_, time)| time).sum::<f64>() / count;
+        let (covariance, variance) = logs.iter().fold((0.0, 0.0), |(cov, var), (n, time)| {
+            (
+                cov + (n - mean_n) * (time - mean_time),
+                var + (n - mean_n).powi(2),
+            )
+        });
+        (variance > 0.0).then(|| covariance / variance)
+    }
+}
+
+/// One curve per variant of each scaling bench, from rows like
+/// `galaxies/part1_expand/512` that `aoc_common::bench_scaling!` registers.
+pub fn curves(days: &[(u8, Vec<Row>)]) -> Vec<Curve> {
+    let mut curves: Vec<Curve> = Vec::new();
+    for (day, rows) in days {
+        for row in rows {
+            let mut path = row.benchmark.split('/');
+            let (Some(axis), Some(variant), Some(n), None) =
+                (path.next(), path.next(), path.next(), path.next())
+            else {
+                continue;
+            };
+            let (Ok(n), true) = (n.parse::<f64>(), variant.starts_with("part")) else {
+                continue;
+            };
+            let point = (n, row.stats.median);
+            match curves.iter_mut().find(|curve| {
+                (curve.day, curve.axis.as_str(), curve.variant.as_str()) == (*day, axis, variant)
+            }) {
+                Some(curve) => curve.points.push(point),
+                None => curves.push(Curve {
+                    day: *day,
+                    axis: axis.to_string(),
+                    variant: variant.to_string(),
+                    points: vec![point],
+                }),
+            }
+        }
+    }
+    for curve in &mut curves {
+        curve.points.sort_by(|a, b| a.0.total_cmp(&b.0));
+    }
+    curves
+}
+
+fn charts(curves: &[Curve]) -> Vec<(u8, &str)> {
+    let mut charts = curves
+        .iter()
+        .map(|curve| (curve.day, curve.axis.as_str()))
+        .collect::<Vec<_>>();
+    charts.dedup();
+    charts
+}
+
+fn format_growth(curve: &Curve) -> String {
+    curve
+        .growth()
+        .map_or_else(|| "-".to_string(), |growth| format!("n^{:.2}", growth))
+}
+
+fn parts(entries: &[Entry]) -> Vec<(u8, u8)> {
+    let mut parts = entries
+        .iter()
+        .map(|entry| (entry.day, entry.part))
+        .collect::<Vec<_>>();
+    parts.dedup();
+    parts
+}
+
+/// Time to solve every part once with its fastest variant.
+pub fn total(entries: &[Entry]) -> f64 {
+    parts(entries)
+        .into_iter()
+        .filter_map(|(day, part)| {
+            entries
+                .iter()
+                .filter(|entry| (entry.day, entry.part) == (day, part))
+                .filter_map(|entry| entry.median)
+                .min_by(f64::total_cmp)
+        })
+        .sum()
+}
+
+/// The median of an entry, or why it has none.
+fn format_median(entry: &Entry) -> String {
+    match (entry.median, entry.slow) {
+        (Some(median), _) => format_duration(median),
+        (None, true) => "slow, not benched".to_string(),
+        (None, false) => "not benched".to_string(),
+    }
+}
+
+fn format_allocs(allocs: Option<f64>) -> String {
+    allocs.map_or_else(|| "-".to_string(), |count| format!("{}", count))
+}
+
+pub fn markdown(entries: &[Entry], curves: &[Curve]) -> String {
+    let mut markdown = String::from(
+        "| Day | Part | Variant | Median | Allocations |\n|---:|---:|---|---:|---:|\n",
+    );
+    for entry in entries {
+        let bold = |text: String| {
+            if entry.fastest {
+                format!("**{}**", text)
+            } else {
+                text
+            }
+        };
+        writeln!(
+            markdown,
+            "| {} | {} | {} | {} | {} |",
+            entry.day,
+            entry.part,
+            bold(format!("`{}`", entry.variant)),
+            bold(format_median(entry)),
+            format_allocs(entry.allocs)
+        )
+        .expect("writing to a string cannot fail");
+    }
+    writeln!(
+        markdown,
+        "\n**Total time for all of 2023: {}** (fastest variant of every part, in bold where there are several)",
+        format_duration(total(entries))
+    )
+    .expect("writing to a string cannot fail");
+
+    if curves.is_empty() {
+        return markdown;
+    }
+    markdown.push_str(
+        "\n### Scaling\n\nMedian time of each variant on generated inputs of growing size `n`, and the exponent `k` of the power law `time ~ n^k` that fits it best.\n\n| Day | Variant | n | Smallest | Largest | Growth |\n|---:|---|---|---:|---:|---:|\n",
+    );
+    for curve in curves {
+        let (Some(first), Some(last)) = (curve.points.first(), curve.points.last()) else {
+            continue;
+        };
+        writeln!(
+            markdown,
+            "| {} | `{}` | {} → {} {} | {} | {} | {} |",
+            curve.day,
+            curve.variant,
+            first.0,
+            last.0,
+            curve.axis.replace('_', " "),
+            format_duration(first.1),
+            format_duration(last.1),
+            format_growth(curve)
+        )
+        .expect("writing to a string cannot fail");
+    }
+    markdown
+}
+
+/// Colors of the lines of a scaling chart, one per variant.
+const PALETTE: [&str; 6] = ["#2a7", "#d62", "#37c", "#a3a", "#c92", "#555"];
+
+/// A log-log chart of the curves of one scaling bench, a line per variant.
+fn scaling_chart(curves: &[&Curve]) -> String {
+    const WIDTH: f64 = 520.0;
+    const HEIGHT: f64 = 240.0;
+    const LEFT: f64 = 70.0;
+    const BOTTOM: f64 = 20.0;
+    let points = curves.iter().flat_map(|curve| &curve.points);
+    let (mut n_min, mut n_max, mut time_min, mut time_max) =
+        (f64::MAX, f64::MIN_POSITIVE, f64::MAX, f64::MIN_POSITIVE);
+    for &(n, time) in points {
+        (n_min, n_max) = (n_min.min(n), n_max.max(n));
+        (time_min, time_max) = (time_min.min(time), time_max.max(time));
+    }
+    let scale = |value: f64, min: f64, max: f64, length: f64| match max > min {
+        true => (value.ln() - min.ln()) / (max.ln() - min.ln()) * length,
+        false => 0.0,
+    };
+    let x = |n: f64| LEFT + scale(n, n_min, n_max, WIDTH);
+    let y = |time: f64| HEIGHT - scale(time, time_min, time_max, HEIGHT) + 10.0;
+
+    let mut chart = format!(
+        r#"<line class="axis" x1="{left}" y1="10" x2="{left}" y2="{bottom}"/><line class="axis" x1="{left}" y1="{bottom}" x2="{right}" y2="{bottom}"/><text x="0" y="{top}">{slowest}</text><text x="0" y="{bottom}">{fastest}</text>"#,
+        left = LEFT,
+        right = LEFT + WIDTH,
+        top = y(time_max) + 5.0,
+        bottom = HEIGHT + 10.0,
+        slowest = format_duration(time_max),
+        fastest = format_duration(time_min),
+    );
+    if let Some(curve) = curves.first() {
+        for &(n, _) in &curve.points {
+            write!(
+                chart,
+                r#"<text x="{:.1}" y="{}" text-anchor="middle">{}</text>"#,
+                x(n),
+                HEIGHT + 10.0 + BOTTOM,
+                n
+            )
+            .expect("writing to a string cannot fail");
+        }
+    }
+    for (i, curve) in curves.iter().enumerate() {
+        let color = PALETTE[i % PALETTE.len()];
+        let line = curve
+            .points
+            .iter()
+            .map(|&(n, time)| format!("{:.1},{:.1}", x(n), y(time)))
+            .collect::<Vec<_>>()
+            .join(" ");
+        write!(
+            chart,
+            r#"<polyline points="{}" fill="none" stroke="{}" stroke-width="2"/><text x="{}" y="{}" fill="{}">{} · {}</text>"#,
+            line,
+            color,
+            LEFT + WIDTH + 20.0,
+            20 + i * 18,
+            color,
+            curve.variant,
+            format_growth(curve)
+        )
+        .expect("writing to a string cannot fail");
+    }
+    format!(
+        r#"<svg width="860" height="{}">{}</svg>"#,
+        HEIGHT + 10.0 + BOTTOM + 10.0,
+        chart
+    )
+}
+
+/// A standalone page with a bar chart of the variants per day, and a log-log
+/// chart of each scaling bench.
+pub fn html(entries: &[Entry], curves: &[Curve]) -> String {
+    let mut days = entries.iter().map(|entry| entry.day).collect::<Vec<_>>();
+    days.dedup();
+
+    let mut sections = String::new();
+    for day in days {
+        let variants = entries
+            .iter()
+            .filter(|entry| entry.day == day)
+            .collect::<Vec<_>>();
+        let slowest = variants
+            .iter()
+            .filter_map(|entry| entry.median)
+            .fold(f64::MIN_POSITIVE, f64::max);
+        let mut bars = String::new();
+        for (i, entry) in variants.iter().enumerate() {
+            let y = i * 28;
+            let width = entry
+                .median
+                .map_or(0.0, |median| (median / slowest * 400.0).max(1.0));
+            writeln!(
+                bars,
+                r#"<text x="0" y="{}">{}</text><rect class="{}" x="200" y="{}" width="{:.1}" height="20"/><text x="{:.1}" y="{}">{} · {} allocs</text>"#,
+                y + 15,
+                entry.variant,
+                if entry.fastest { "bar fastest" } else { "bar" },
+                y,
+                width,
+                width + 208.0,
+                y + 15,
+                format_median(entry),
+                format_allocs(entry.allocs)
+            )
+            .expect("writing to a string cannot fail");
+        }
+        writeln!(
+            sections,
+            r#"<section><h2>Day {:02}</h2><svg width="800" height="{}">{}</svg></section>"#,
+            day,
+            variants.len() * 28,
+            bars
+        )
+        .expect("writing to a string cannot fail");
+    }
+    if !curves.is_empty() {
+        sections.push_str("<h2>Scaling</h2>\n<p>Median time on generated inputs of growing size <code>n</code>, both axes logarithmic, with the exponent <code>k</code> of the best fit <code>time ~ n^k</code>.</p>\n");
+    }
+    for (day, axis) in charts(curves) {
+        let curves = curves
+            .iter()
+            .filter(|curve| (curve.day, curve.axis.as_str()) == (day, axis))
+            .collect::<Vec<_>>();
+        writeln!(
+            sections,
+            "<section><h3>Day {:02} by {}</h3>{}</section>",
+            day,
+            axis.replace('_', " "),
+            scaling_chart(&curves)
+        )
+        .expect("writing to a string cannot fail");
+    }
+
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>Advent of Code 2023 performance</title>
+<style>
+body {{ font-family: sans-serif; max-width: 900px; margin: 2em auto; }}
+svg text {{ font-size: 13px; font-family: monospace; }}
+.bar {{ fill: #9ab; }}
+.fastest {{ fill: #2a7; }}
+.axis {{ stroke: #888; }}
+</style>
+</head>
+<body>
+<h1>Advent of Code 2023 performance</h1>
+<p><strong>Total time for all of 2023: {}</strong>, fastest variant of every part in green.</p>
+{}</body>
+</html>
+"#,
+        format_duration(total(entries)),
+        sections
+    )
+}
+
+/// Replaces the generated section of the README at `path` with `markdown`,
+/// appending one if there is none yet.
+pub fn update_readme(path: &Path, markdown: &str) -> miette::Result<()> {
+    let readme = fs::read_to_string(path)
+        .map_err(|err| miette::miette!("failed to read {}: {}", path.display(), err))?;
+    let section = format!("{}\n{}{}", README_START, markdown, README_END);
+    let readme = match (readme.find(README_START), readme.find(README_END)) {
+        (Some(start), Some(end)) if start < end => format!(
+            "{}{}{}",
+            &readme[..start],
+            section,
+            &readme[end + README_END.len()..]
+        ),
+        _ => format!("{}\n## Performance\n\n{}\n", readme.trim_end(), section),
+    };
+    fs::write(path, readme)
+        .map_err(|err| miette::miette!("failed to write {}: {}", path.display(), err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::divan::{Allocs, Stats};
+
+    fn row(benchmark: &str, median: f64) -> Row {
+        Row {
+            target: "day_05".to_string(),
+            benchmark: benchmark.to_string(),
+            stats: Stats {
+                fastest: median,
+                slowest: median,
+                median,
+                mean: median,
+                samples: 100,
+                iters: 100,
+            },
+            allocs: Some(Allocs {
+                count: 12.0,
+                bytes: 1024.0,
+            }),
+            line: 0,
+        }
+    }
+
+    #[test]
+    fn test_report() {
+        let days = [
+            (
+                1,
+                vec![row("part1/parse", 10e3), row("part1/total", 36.4e3)],
+            ),
+            (
+                5,
+                vec![
+                    row("part2_binary_search/total", 490.3e3),
+                    row("part2_btree/total", 2e9),
+                ],
+            ),
+        ];
+        let variant = |day, part, module| Variant {
+            day,
+            part,
+            module,
+            slow: false,
+            process: |_| Ok(String::new()),
+        };
+        let variants = [
+            variant(1, 1, "part1"),
+            variant(5, 2, "part2_binary_search"),
+            Variant {
+                slow: true,
+                ..variant(5, 2, "part2_btree")
+            },
+            variant(5, 2, "part2_parallel"),
+            variant(6, 1, "part1"),
+        ];
+        let entries = entries(&days, &variants);
+        assert_eq!(
+            vec![false, true, false, false],
+            entries.iter().map(|e| e.fastest).collect::<Vec<_>>()
+        );
+        assert_eq!(36.4e3 + 490.3e3, total(&entries));
+
+        let markdown = markdown(&entries, &[]);
+        assert!(markdown.contains("| 1 | 1 | `part1` | 36.4µs | 12 |"));
+        assert!(markdown.contains("| 5 | 2 | **`part2_binary_search`** | **490µs** | 12 |"));
+        assert!(markdown.contains("| 5 | 2 | `part2_btree` | 2.00s | 12 |"));
+        assert!(markdown.contains("| 5 | 2 | `part2_parallel` | not benched | - |"));
+        assert!(!markdown.contains("| 6 |"));
+        assert!(markdown.contains("**Total time for all of 2023: 527µs**"));
+        assert!(!markdown.contains("Scaling"));
+        assert!(html(&entries, &[]).contains("<h2>Day 05</h2>"));
+    }
+
+    #[test]
+    fn test_curves() {
+        let days = [(
+            11,
+            vec![
+                row("part1_expand/total", 500e3),
+                row("galaxies/part1_no_expand/128", 4e3),
+                row("galaxies/part1_no_expand/64", 1e3),
+                row("galaxies/part1_no_expand/256", 16e3),
+                row("throughput/part1/str", 1e3),
+                row("galaxies/part2/64", 1e3),
+            ],
+        )];
+        let curves = curves(&days);
+        assert_eq!(2, curves.len());
+        assert_eq!(
+            vec![(64.0, 1e3), (128.0, 4e3), (256.0, 16e3)],
+            curves[0].points
+        );
+        assert!((curves[0].growth().unwrap() - 2.0).abs() < 1e-9);
+        assert_eq!(None, curves[1].growth());
+
+        let expand_bfs = Variant {
+            day: 11,
+            part: 1,
+            module: "part1_expand_bfs",
+            slow: true,
+            process: |_| Ok(String::new()),
+        };
+        let entries = entries(&days, &[expand_bfs]);
+        let markdown = markdown(&entries, &curves);
+        assert!(markdown.contains("| 11 | 1 | `part1_expand_bfs` | slow, not benched | - |"));
+        assert!(markdown
+            .contains("| 11 | `part1_no_expand` | 64 → 256 galaxies | 1.00µs | 16.0µs | n^2.00 |"));
+        assert!(markdown.contains("| 11 | `part2` | 64 → 64 galaxies | 1.00µs | 1.00µs | - |"));
+        assert!(html(&entries, &curves).contains("<h3>Day 11 by galaxies</h3>"));
+    }
+}
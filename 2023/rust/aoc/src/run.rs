@@ -0,0 +1,100 @@
+use std::{env, time::Instant};
+
+use aoc_common::input::INPUT_ENV;
+use clap::Args;
+
+use crate::registry::{Selection, Variant};
+
+#[derive(Args, Debug)]
+pub struct RunArgs {
+    /// Day to run, e.g. `5`
+    #[arg(required_unless_present = "all", conflicts_with = "all")]
+    day: Option<u8>,
+
+    /// Part to run (`1` or `2`), every part if omitted
+    part: Option<u8>,
+
+    /// Only run this variant, e.g. `binary_search` for `part2_binary_search`
+    #[arg(long)]
+    variant: Option<String>,
+
+    /// Run every registered day
+    #[arg(long)]
+    all: bool,
+
+    /// Also run variants registered as slow
+    #[arg(long)]
+    slow: bool,
+
+    /// Input file to use instead of each day's `input.txt`, `-` for stdin.
+    /// Falls back to `$AOC_INPUT`.
+    #[arg(long)]
+    input: Option<String>,
+}
+
+pub fn run(args: &RunArgs) -> miette::Result<()> {
+    let selection = Selection {
+        day: args.day,
+        part: args.part,
+        variant: args.variant.as_deref(),
+        slow: args.slow,
+    };
+
+    // an input given explicitly is the same for every day, and stdin can
+    // only be read once
+    let explicit = args
+        .input
+        .clone()
+        .or_else(|| env::var(INPUT_ENV).ok())
+        .map(read_explicit_input);
+    let mut bundled = None;
+    let mut ran = 0;
+    let mut failed = 0;
+    for variant in selection.variants() {
+        ran += 1;
+        // variants are registered in day order, so each input is read once
+        if explicit.is_none() && !matches!(bundled, Some((day, _)) if day == variant.day) {
+            bundled = Some((variant.day, read_input(variant)));
+        }
+        let input = explicit
+            .as_ref()
+            .or(bundled.as_ref().map(|(_, input)| input));
+        let result = match input {
+            Some(Ok(input)) => run_variant(variant, input),
+            Some(Err(err)) => Err(miette::miette!("{}", err)),
+            None => unreachable!("input is read above"),
+        };
+        if let Err(err) = result {
+            failed += 1;
+            eprintln!("{:<28} {:?}", variant.label(), err);
+        }
+    }
+
+    if ran == 0 {
+        miette::bail!("no registered solution matches the selection");
+    }
+    if failed > 0 {
+        miette::bail!("{failed} of {ran} solutions failed");
+    }
+    Ok(())
+}
+
+/// Reads the input at `path`, `-` for stdin.
+fn read_explicit_input(path: String) -> miette::Result<String> {
+    aoc_common::input::read_input(Some(path.clone()), path.as_ref())
+        .map_err(|err| miette::miette!("failed to read input from {}: {}", path, err))
+}
+
+/// Reads the day's bundled `input.txt`.
+fn read_input(variant: &Variant) -> miette::Result<String> {
+    aoc_common::input::read_input(None, &variant.input_path())
+        .map_err(|err| miette::miette!("failed to read input of {}: {}", variant.label(), err))
+}
+
+fn run_variant(variant: &Variant, input: &str) -> miette::Result<()> {
+    let start = Instant::now();
+    let answer = aoc_common::trace::frame(|| (variant.process)(input))?;
+    let elapsed = start.elapsed();
+    println!("{:<28} {:>16} {:>12.2?}", variant.label(), answer, elapsed);
+    Ok(())
+}
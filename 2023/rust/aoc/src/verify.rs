@@ -0,0 +1,157 @@
+use std::fs;
+
+use aoc_common::answers::Answers;
+use clap::Args;
+
+use crate::registry::{day_dir, Selection, Variant};
+
+#[derive(Args, Debug)]
+pub struct VerifyArgs {
+    /// Day to verify, every registered day if omitted
+    day: Option<u8>,
+
+    /// Part to verify (`1` or `2`), every part if omitted
+    part: Option<u8>,
+
+    /// Only verify this variant, e.g. `binary_search` for `part2_binary_search`
+    #[arg(long)]
+    variant: Option<String>,
+
+    /// Also verify variants registered as slow
+    #[arg(long)]
+    slow: bool,
+
+    /// Only verify the examples, not the bundled `input.txt`
+    #[arg(long)]
+    examples: bool,
+}
+
+/// What a variant computed on one input, compared to `answers.txt`.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Pass,
+    Fail {
+        expected: String,
+        got: String,
+    },
+    /// `answers.txt` has no answer for the input yet.
+    Missing {
+        got: String,
+    },
+    Error(String),
+}
+
+impl Outcome {
+    fn is_failure(&self) -> bool {
+        matches!(self, Outcome::Fail { .. } | Outcome::Error(_))
+    }
+}
+
+pub fn verify(args: &VerifyArgs) -> miette::Result<()> {
+    let selection = Selection {
+        day: args.day,
+        part: args.part,
+        variant: args.variant.as_deref(),
+        slow: args.slow,
+    };
+
+    let mut answers = None;
+    let mut checked = 0;
+    let mut failed = 0;
+    for variant in selection.variants() {
+        // variants are registered in day order, so each answers.txt is read once
+        if !matches!(answers, Some((day, _)) if day == variant.day) {
+            answers = Some((variant.day, Answers::read(&day_dir(variant.day))?));
+        }
+        let Some((_, answers)) = &answers else {
+            unreachable!("answers are read above")
+        };
+        for input in inputs(answers, variant, args.examples) {
+            let outcome = verify_variant(variant, answers, &input);
+            checked += 1;
+            if outcome.is_failure() {
+                failed += 1;
+            }
+            println!("{:<28} {:<26} {}", variant.label(), input, render(&outcome));
+        }
+    }
+
+    if checked == 0 {
+        miette::bail!("no registered solution matches the selection");
+    }
+    println!("{} of {} checks passed", checked - failed, checked);
+    if failed > 0 {
+        miette::bail!("{failed} of {checked} checks failed");
+    }
+    Ok(())
+}
+
+/// The bundled `input.txt` (unless `examples` only) followed by every other
+/// input with an answer for the variant's part.
+fn inputs(answers: &Answers, variant: &Variant, examples: bool) -> Vec<String> {
+    let mut inputs = Vec::new();
+    if !examples {
+        inputs.push("input.txt".to_string());
+    }
+    inputs.extend(
+        answers
+            .inputs(variant.part)
+            .filter(|input| *input != "input.txt")
+            .map(str::to_string),
+    );
+    inputs
+}
+
+pub fn verify_variant(variant: &Variant, answers: &Answers, input: &str) -> Outcome {
+    let path = day_dir(variant.day).join(input);
+    let contents = match fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(err) => return Outcome::Error(format!("failed to read {}: {}", path.display(), err)),
+    };
+    let got = match (variant.process)(&contents) {
+        Ok(got) => got,
+        Err(err) => return Outcome::Error(err.to_string()),
+    };
+    match answers.get(input, variant.part) {
+        Some(expected) if expected == got => Outcome::Pass,
+        Some(expected) => Outcome::Fail {
+            expected: expected.to_string(),
+            got,
+        },
+        None => Outcome::Missing { got },
+    }
+}
+
+fn render(outcome: &Outcome) -> String {
+    match outcome {
+        Outcome::Pass => "ok".to_string(),
+        Outcome::Fail { expected, got } => format!("FAIL expected {}, got {}", expected, got),
+        Outcome::Missing { got } => format!("no expected answer, got {}", got),
+        Outcome::Error(err) => format!("ERROR {}", err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::VARIANTS;
+
+    #[test]
+    fn test_examples() -> miette::Result<()> {
+        let mut failures = Vec::new();
+        for day in 1..=25 {
+            let variants = VARIANTS.iter().filter(|v| v.day == day);
+            let answers = Answers::read(&day_dir(day))?;
+            for variant in variants {
+                for input in inputs(&answers, variant, true) {
+                    let outcome = verify_variant(variant, &answers, &input);
+                    if outcome != Outcome::Pass {
+                        failures.push(format!("{} {}: {:?}", variant.label(), input, outcome));
+                    }
+                }
+            }
+        }
+        assert!(failures.is_empty(), "{:#?}", failures);
+        Ok(())
+    }
+}
@@ -1,22 +1,10 @@
-use {{crate_name}}::*;
+#[global_allocator]
+static ALLOC: divan::AllocProfiler = divan::AllocProfiler::system();
 
 fn main() {
     // Run registered benchmarks.
     divan::main();
 }
 
-#[divan::bench]
-fn part1() {
-    part1::process(divan::black_box(include_str!(
-        "../input.txt",
-    )))
-    .unwrap();
-}
-
-#[divan::bench]
-fn part2() {
-    part2::process(divan::black_box(include_str!(
-        "../input.txt",
-    )))
-    .unwrap();
-}
+aoc_common::bench_stages!({{crate_name}}::part1);
+aoc_common::bench_stages!({{crate_name}}::part2);
@@ -1,4 +1,29 @@
-pub mod errors;
+use aoc_common::solution::{Answer, Solution};
+use errors::AocError;
+
+pub use aoc_common::errors;
+pub mod input;
+pub mod parser;
 
 pub mod part1;
 pub mod part2;
+
+pub struct {{crate_name | remove: "_" | capitalize}};
+
+impl Solution for {{crate_name | remove: "_" | capitalize}} {
+    const DAY: u8 = {{day}};
+    type Input<'a> = Vec<&'a str>;
+    type Params = ();
+
+    fn parse(input: &str) -> miette::Result<Self::Input<'_>, AocError> {
+        part1::parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _: &()) -> miette::Result<Answer, AocError> {
+        part1::solve(input).map(Answer::from)
+    }
+
+    fn part2(input: &Self::Input<'_>, _: &()) -> miette::Result<Answer, AocError> {
+        part2::solve(input).map(Answer::from)
+    }
+}
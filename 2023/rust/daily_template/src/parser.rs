@@ -0,0 +1,7 @@
+use aoc_common::parse::{lines, IResult};
+use nom::character::complete::not_line_ending;
+
+/// Lines of the puzzle input, until the day has a grammar of its own.
+pub fn puzzle(input: &str) -> IResult<'_, Vec<&str>> {
+    lines(not_line_ending)(input)
+}
@@ -1,19 +1,26 @@
-use crate::errors::AocError;
+use aoc_common::parse::finish;
 
-#[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String, AocError> {
-    todo!("day xx - part 2");
+use crate::{errors::AocError, parser};
+
+#[tracing::instrument(skip(input), fields(input_len = input.len()))]
+pub fn process(input: &str) -> miette::Result<{{answer}}, AocError> {
+    solve(&parse(input)?)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+#[tracing::instrument(skip_all)]
+pub fn parse(input: &str) -> miette::Result<Vec<&str>, AocError> {
+    finish(input, parser::puzzle)
+}
 
-    #[test]
-    fn test_process() -> miette::Result<()> {
-        todo!("haven't built test yet");
-        let input = "";
-        assert_eq!("", process(input)?);
-        Ok(())
-    }
+/// Counts the lines, a stand-in answer until the puzzle is solved.
+#[tracing::instrument(skip_all)]
+pub fn solve(lines: &[&str]) -> miette::Result<{{answer}}, AocError> {
+{% if answer == "String" -%}
+    Ok(lines.len().to_string())
+{% elsif answer == "usize" -%}
+    Ok(lines.len())
+{% else -%}
+    {{answer}}::try_from(lines.len())
+        .map_err(|_| AocError::overflow("there are more lines than the answer can count"))
+{% endif -%}
 }
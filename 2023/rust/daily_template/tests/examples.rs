@@ -0,0 +1,7 @@
+//! Every variant on every example in `examples/`, checked against the
+//! answers in `answers.txt`.
+
+aoc_common::example_tests!(
+    1 => {{crate_name}}::part1,
+    2 => {{crate_name}}::part2,
+);
@@ -1,21 +1,33 @@
-use day_01::*;
+use aoc_common::generate::{generate_seeded, Shape};
+
+#[global_allocator]
+static ALLOC: divan::AllocProfiler = divan::AllocProfiler::system();
 
 fn main() {
     // Run registered benchmarks.
     divan::main();
 }
 
-#[divan::bench]
-fn part1() {
-    part1::process(divan::black_box(include_str!("../input.txt",))).unwrap();
-}
+aoc_common::bench_stages!(day_01::part1);
+#[cfg(feature = "parallel")]
+aoc_common::bench_stages!(day_01::part1_parallel);
+aoc_common::bench_stages!(day_01::part2);
+aoc_common::bench_stages!(day_01::part2_aho);
+#[cfg(feature = "parallel")]
+aoc_common::bench_stages!(day_01::part2_parallel);
 
-#[divan::bench]
-fn part2() {
-    part2::process(divan::black_box(include_str!("../input.txt",))).unwrap();
-}
+aoc_common::bench_throughput!(day_01::part1, day_01::part2);
 
-#[divan::bench]
-fn part2_aho() {
-    part2_aho::process(divan::black_box(include_str!("../input.txt",))).unwrap();
+fn document(lines: usize) -> String {
+    generate_seeded(1, Shape::new(lines), 0).unwrap().input
 }
+
+// part2_aho takes seconds on the longest documents
+aoc_common::bench_scaling!(lines: [100, 1_000, 10_000, 100_000] => document;
+    day_01::part1,
+    #[cfg(feature = "parallel")]
+    day_01::part1_parallel,
+    day_01::part2,
+    #[cfg(feature = "parallel")]
+    day_01::part2_parallel,
+);
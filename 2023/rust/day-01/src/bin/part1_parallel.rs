@@ -0,0 +1,21 @@
+use day_01::input::read_input;
+use day_01::part1_parallel::process;
+use miette::Context;
+
+#[cfg(feature = "dhat-heap")]
+#[global_allocator]
+static ALLOC: dhat::Alloc = dhat::Alloc;
+
+#[tracing::instrument]
+fn main() -> miette::Result<()> {
+    #[cfg(feature = "dhat-heap")]
+    let _profiler = dhat::Profiler::new_heap();
+
+    #[cfg(not(feature = "dhat-heap"))]
+    let _trace = aoc_common::trace::init();
+
+    let file = read_input(std::env::args().nth(1)).context("read input")?;
+    let result = aoc_common::trace::frame(|| process(&file)).context("process part 1")?;
+    println!("{}", result);
+    Ok(())
+}
@@ -1,3 +1,4 @@
+use day_01::input::read_input;
 use day_01::part2_aho::process;
 use miette::Context;
 
@@ -11,10 +12,10 @@ fn main() -> miette::Result<()> {
     let _profiler = dhat::Profiler::new_heap();
 
     #[cfg(not(feature = "dhat-heap"))]
-    tracing_subscriber::fmt::init();
+    let _trace = aoc_common::trace::init();
 
-    let file = include_str!("../../input.txt");
-    let result = process(file).context("process part 2")?;
+    let file = read_input(std::env::args().nth(1)).context("read input")?;
+    let result = aoc_common::trace::frame(|| process(&file)).context("process part 2")?;
     println!("{}", result);
     Ok(())
 }
@@ -0,0 +1,43 @@
+use aoc_common::check::Violations;
+use nom::character::complete::alphanumeric1;
+
+use crate::errors::AocError;
+
+const SPELLED: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+/// Every line is letters and digits, with a digit, spelled out or not, to
+/// calibrate from.
+pub fn check(input: &str) -> Vec<AocError> {
+    let mut violations = Violations::new(input);
+    for (line, _) in violations.lines(alphanumeric1) {
+        let has_digit = line.contains(|ch: char| ch.is_ascii_digit())
+            || SPELLED.iter().any(|digit| line.contains(digit));
+        if !has_digit {
+            violations.report(line, "line has no digit");
+        }
+    }
+    violations.into_errors()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check() {
+        assert!(check("1abc2\ntwo1nine\n").is_empty());
+        let errors = check("1abc2\npqr stu\nabcdef\n")
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                "failed to parse input: expected the end of the line at line 2, column 4",
+                "invalid puzzle input: line has no digit",
+            ],
+            errors
+        );
+    }
+}
@@ -1,5 +1,90 @@
-pub mod errors;
+use aoc_common::{
+    parse::finish,
+    solution::{Answer, Solution},
+};
+use errors::AocError;
+
+pub use aoc_common::errors;
+pub mod check;
+pub mod input;
+pub mod parser;
 
 pub mod part1;
+#[cfg(feature = "parallel")]
+pub mod part1_parallel;
 pub mod part2;
 pub mod part2_aho;
+#[cfg(feature = "parallel")]
+pub mod part2_parallel;
+
+pub struct Day01;
+
+/// The calibration document, a line per value, along with its text for
+/// diagnostics.
+#[derive(Debug)]
+pub struct Document<'a> {
+    pub(crate) text: &'a str,
+    pub(crate) lines: Vec<&'a str>,
+}
+
+impl Document<'_> {
+    /// The `value` of `line`, with its errors moved onto the whole document.
+    pub(crate) fn value_of(
+        &self,
+        line: &str,
+        value: fn(&str) -> miette::Result<u32, AocError>,
+    ) -> miette::Result<u32, AocError> {
+        value(line).map_err(|err| err.within(self.text, line))
+    }
+}
+
+/// The calibration document, which both parts read.
+#[tracing::instrument(skip_all)]
+pub fn parse(input: &str) -> miette::Result<Document<'_>, AocError> {
+    Ok(Document {
+        text: input,
+        lines: finish(input, parser::document)?,
+    })
+}
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+    type Input<'a> = Document<'a>;
+    type Params = ();
+
+    fn parse(input: &str) -> miette::Result<Self::Input<'_>, AocError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _: &()) -> miette::Result<Answer, AocError> {
+        part1::solve(input).map(Answer::from)
+    }
+
+    fn part2(input: &Self::Input<'_>, _: &()) -> miette::Result<Answer, AocError> {
+        part2::solve(input).map(Answer::from)
+    }
+
+    fn check(input: &str) -> Vec<AocError> {
+        check::check(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use miette::SourceSpan;
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    fn test_no_digit(#[values(1, 2)] part: u8) {
+        let input = "1abc2\nabc\n";
+        match Day01::solve(input, part) {
+            Err(AocError::InvalidInput { src, span, .. }) => {
+                assert_eq!(input, src);
+                assert_eq!(SourceSpan::from((6, 3)), span);
+            }
+            other => panic!("expected an invalid input, got {:?}", other),
+        }
+    }
+}
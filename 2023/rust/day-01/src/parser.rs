@@ -0,0 +1,38 @@
+use aoc_common::parse::{lines, IResult};
+use nom::character::complete::alphanumeric1;
+
+/// The calibration document: a line per value.
+pub fn document(input: &str) -> IResult<'_, Vec<&str>> {
+    lines(line)(input)
+}
+
+/// Letters and digits.
+pub fn line(input: &str) -> IResult<'_, &str> {
+    alphanumeric1(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{errors::AocError, parse::finish};
+
+    use super::*;
+
+    #[test]
+    fn test_document() -> miette::Result<()> {
+        assert_eq!(
+            vec!["1abc2", "pqr3stu8vwx"],
+            finish("1abc2\npqr3stu8vwx\n", document)?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_document_error() {
+        match finish("1abc2\npqr3 stu8vwx\n", document) {
+            Err(AocError::ParseError { message, .. }) => {
+                assert_eq!("expected the end of the line at line 2, column 5", message)
+            }
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+}
@@ -1,43 +1,95 @@
-use crate::errors::AocError;
-
-#[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let result = input
-        .lines()
-        .map(|line| {
-            let first = line.chars().find_map(|ch| ch.to_digit(10)).unwrap();
-            let last = line.chars().rev().find_map(|ch| ch.to_digit(10)).unwrap();
-            first * 10 + last
-        })
-        .sum::<u32>()
-        .to_string();
-
-    Ok(result)
+use std::io::BufRead;
+
+use aoc_common::{parse::finish_line, stream::for_each_line};
+
+use crate::{errors::AocError, parser, Document};
+
+pub use crate::parse;
+
+#[tracing::instrument(skip(input), fields(input_len = input.len()))]
+pub fn process(input: &str) -> miette::Result<u32, AocError> {
+    solve(&parse(input)?)
+}
+
+/// Like [`process`], but reads the input from `reader` a line at a time with
+/// [`for_each_line`].
+#[tracing::instrument(skip_all)]
+pub fn process_reader(reader: impl BufRead) -> miette::Result<u32, AocError> {
+    let mut sum = 0;
+    for_each_line(reader, |number, line| {
+        sum = add_value(
+            sum,
+            calibration_value(finish_line(line, number, parser::line)?)?,
+        )?;
+        Ok(())
+    })?;
+    Ok(sum)
+}
+
+#[tracing::instrument(skip_all)]
+pub fn solve(document: &Document<'_>) -> miette::Result<u32, AocError> {
+    document.lines.iter().try_fold(0, |sum, line| {
+        add_value(sum, document.value_of(line, calibration_value)?)
+    })
+}
+
+pub(crate) fn add_value(sum: u32, value: u32) -> Result<u32, AocError> {
+    sum.checked_add(value).ok_or_else(|| {
+        AocError::overflow("the sum of the calibration values is more than u32::MAX")
+    })
+}
+
+/// The first and last digit of `line`, as a two-digit number.
+pub(crate) fn calibration_value(line: &str) -> miette::Result<u32, AocError> {
+    let first = line
+        .chars()
+        .find_map(|ch| ch.to_digit(10))
+        .ok_or_else(|| AocError::invalid_input(line, line, "line has no digit"))?;
+    let last = line
+        .chars()
+        .rev()
+        .find_map(|ch| ch.to_digit(10))
+        .unwrap_or(first);
+    Ok(first * 10 + last)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
     use rstest::rstest;
 
     #[rstest]
-    #[case("1abc3", "13")]
-    #[case("13", "13")]
-    #[case("arst1tnoien3orsiet", "13")]
-    #[case("5", "55")]
-    fn test_process_line(#[case] input: &str, #[case] expected: &str) -> miette::Result<()> {
+    #[case("1abc3", 13)]
+    #[case("13", 13)]
+    #[case("arst1tnoien3orsiet", 13)]
+    #[case("5", 55)]
+    fn test_process_line(#[case] input: &str, #[case] expected: u32) -> miette::Result<()> {
         assert_eq!(expected, process(input)?);
         Ok(())
     }
 
     #[test]
-    fn test_process() -> miette::Result<()> {
-        let input = "1abc2
-pqr3stu8vwx
-a1b2c3d4e5f
-treb7uchet
-";
-        assert_eq!("142", process(input)?);
-        Ok(())
+    fn test_add_value() {
+        // 99 per line overflows after about 43M lines, too many for a test
+        assert!(matches!(
+            add_value(u32::MAX - 98, 99),
+            Err(AocError::Overflow(_))
+        ));
+        assert_eq!(u32::MAX, add_value(u32::MAX - 99, 99).unwrap());
+    }
+
+    proptest! {
+        #[test]
+        fn test_calibration_value(
+            digits in prop::collection::vec((0..10u32, "[a-z]{0,3}"), 1..8),
+            prefix in "[a-z]{0,3}",
+        ) {
+            let line = digits.iter().fold(prefix, |line, (digit, letters)| {
+                format!("{}{}{}", line, digit, letters)
+            });
+            let expected = digits[0].0 * 10 + digits[digits.len() - 1].0;
+            prop_assert_eq!(expected, calibration_value(&line)?);
+        }
     }
 }
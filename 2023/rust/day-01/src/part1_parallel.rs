@@ -0,0 +1,32 @@
+use aoc_common::parallel::finish_lines;
+use rayon::prelude::*;
+
+use crate::{
+    errors::AocError,
+    parser,
+    part1::{add_value, calibration_value},
+    Document,
+};
+
+#[tracing::instrument(skip(input), fields(input_len = input.len()))]
+pub fn process(input: &str) -> miette::Result<u32, AocError> {
+    solve(&parse(input)?)
+}
+
+#[tracing::instrument(skip_all)]
+pub fn parse(input: &str) -> miette::Result<Document<'_>, AocError> {
+    Ok(Document {
+        text: input,
+        lines: finish_lines(input, parser::line)?,
+    })
+}
+
+/// [`crate::part1::solve`] with the lines split across the rayon thread pool.
+#[tracing::instrument(skip_all)]
+pub fn solve(document: &Document<'_>) -> miette::Result<u32, AocError> {
+    document
+        .lines
+        .par_iter()
+        .map(|line| document.value_of(line, calibration_value))
+        .try_reduce(|| 0, add_value)
+}
@@ -1,12 +1,36 @@
-use crate::errors::AocError;
+use std::io::BufRead;
 
-#[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let result = input.lines().map(process_line).sum::<u32>();
-    Ok(result.to_string())
+use aoc_common::{parse::finish_line, stream::for_each_line};
+
+use crate::{errors::AocError, parser, part1::add_value, Document};
+
+pub use crate::parse;
+
+#[tracing::instrument(skip(input), fields(input_len = input.len()))]
+pub fn process(input: &str) -> miette::Result<u32, AocError> {
+    solve(&parse(input)?)
+}
+
+/// Like [`process`], but reads the input from `reader` a line at a time with
+/// [`for_each_line`].
+#[tracing::instrument(skip_all)]
+pub fn process_reader(reader: impl BufRead) -> miette::Result<u32, AocError> {
+    let mut sum = 0;
+    for_each_line(reader, |number, line| {
+        sum = add_value(sum, process_line(finish_line(line, number, parser::line)?)?)?;
+        Ok(())
+    })?;
+    Ok(sum)
 }
 
-fn process_line(line: &str) -> u32 {
+#[tracing::instrument(skip_all)]
+pub fn solve(document: &Document<'_>) -> miette::Result<u32, AocError> {
+    document.lines.iter().try_fold(0, |sum, line| {
+        add_value(sum, document.value_of(line, process_line)?)
+    })
+}
+
+pub(crate) fn process_line(line: &str) -> miette::Result<u32, AocError> {
     let mut it = (0..line.len()).filter_map(|i| {
         let substr = &line[i..];
         if substr.starts_with("one") {
@@ -31,16 +55,19 @@ fn process_line(line: &str) -> u32 {
             substr.chars().next().and_then(|ch| ch.to_digit(10))
         }
     });
-    let first = it.next().expect("should be a number");
-    match it.last() {
-        Some(last) => first * 10 + last,
-        None => first * 10 + first,
+    let first = it
+        .next()
+        .ok_or_else(|| AocError::invalid_input(line, line, "line has no digit"))?;
+    match it.next_back() {
+        Some(last) => Ok(first * 10 + last),
+        None => Ok(first * 10 + first),
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
     use rstest::rstest;
 
     #[rstest]
@@ -53,23 +80,34 @@ mod tests {
     #[case("7pqrstsixteen", 76)]
     #[case("twone", 21)]
     #[case("eightwo", 82)]
-    fn test_process_line(#[case] input: &str, #[case] expected: u32) {
-        assert_eq!(expected, process_line(input))
+    fn test_process_line(#[case] input: &str, #[case] expected: u32) -> miette::Result<()> {
+        assert_eq!(expected, process_line(input)?);
+        Ok(())
     }
 
-    #[test]
-    fn test_process() -> miette::Result<()> {
-        let input = "two1nine
-eightwothree
-abcone2threexyz
-xtwone3four
-4nineeightseven2
-zoneight234
-7pqrstsixteen";
-        assert_eq!(
-            (29 + 83 + 13 + 24 + 42 + 14 + 76).to_string(),
-            process(input)?
-        );
-        Ok(())
+    const WORDS: [&str; 9] = [
+        "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+    ];
+
+    proptest! {
+        // the letters are none of those of the digit words, so spelling a
+        // digit out cannot make another one appear across it
+        #[test]
+        fn test_spelled_digits(
+            digits in prop::collection::vec((0..10usize, any::<bool>(), "[abcdjklmpqyz]{0,3}"), 1..8),
+        ) {
+            let (mut written, mut spelled) = (String::new(), String::new());
+            for (digit, spell, letters) in &digits {
+                written.push_str(&format!("{}{}", digit, letters));
+                match (spell, digit) {
+                    (true, 1..) => spelled.push_str(WORDS[digit - 1]),
+                    _ => spelled.push_str(&digit.to_string()),
+                }
+                spelled.push_str(letters);
+            }
+            let part1 = crate::part1::calibration_value(&written)?;
+            prop_assert_eq!(part1, process_line(&written)?);
+            prop_assert_eq!(part1, process_line(&spelled)?);
+        }
     }
 }
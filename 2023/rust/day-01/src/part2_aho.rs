@@ -1,27 +1,37 @@
 use aho_corasick::AhoCorasick;
 
-use crate::errors::AocError;
+use crate::{errors::AocError, part1::add_value, Document};
 
-#[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<String, AocError> {
-    let result = input.lines().map(process_line).sum::<u32>();
-    Ok(result.to_string())
+pub use crate::parse;
+
+#[tracing::instrument(skip(input), fields(input_len = input.len()))]
+pub fn process(input: &str) -> miette::Result<u32, AocError> {
+    solve(&parse(input)?)
+}
+
+#[tracing::instrument(skip_all)]
+pub fn solve(document: &Document<'_>) -> miette::Result<u32, AocError> {
+    document.lines.iter().try_fold(0, |sum, line| {
+        add_value(sum, document.value_of(line, process_line)?)
+    })
 }
 
-fn process_line(line: &str) -> u32 {
+fn process_line(line: &str) -> miette::Result<u32, AocError> {
     let patterns = &[
-        "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "1", "2", "3", "4",
-        "5", "6", "7", "8", "9",
+        "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "0", "1", "2", "3",
+        "4", "5", "6", "7", "8", "9",
     ];
-    let values = &[1, 2, 3, 4, 5, 6, 7, 8, 9];
+    let values = &[1, 2, 3, 4, 5, 6, 7, 8, 9, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9];
     let ac = AhoCorasick::new(patterns).expect("should be able to build AhoCorasick");
     let mut matches = ac
         .find_overlapping_iter(line)
-        .map(|m| values[m.pattern().as_usize() % 9]);
-    let first = matches.next().expect("should be a number");
+        .map(|m| values[m.pattern().as_usize()]);
+    let first = matches
+        .next()
+        .ok_or_else(|| AocError::invalid_input(line, line, "line has no digit"))?;
     let last = matches.last().unwrap_or(first);
 
-    first * 10 + last
+    Ok(first * 10 + last)
 }
 
 #[cfg(test)]
@@ -39,23 +49,10 @@ mod tests {
     #[case("7pqrstsixteen", 76)]
     #[case("twone", 21)]
     #[case("1eightwo", 12)]
-    fn test_process_line(#[case] input: &str, #[case] expected: u32) {
-        assert_eq!(expected, process_line(input))
-    }
-
-    #[test]
-    fn test_process() -> miette::Result<()> {
-        let input = "two1nine
-eightwothree
-abcone2threexyz
-xtwone3four
-4nineeightseven2
-zoneight234
-7pqrstsixteen";
-        assert_eq!(
-            (29 + 83 + 13 + 24 + 42 + 14 + 76).to_string(),
-            process(input)?
-        );
+    #[case("0", 0)]
+    #[case("nine0", 90)]
+    fn test_process_line(#[case] input: &str, #[case] expected: u32) -> miette::Result<()> {
+        assert_eq!(expected, process_line(input)?);
         Ok(())
     }
 }
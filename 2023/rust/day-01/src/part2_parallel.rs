@@ -0,0 +1,20 @@
+use rayon::prelude::*;
+
+use crate::{errors::AocError, part1::add_value, part2::process_line, Document};
+
+pub use crate::part1_parallel::parse;
+
+#[tracing::instrument(skip(input), fields(input_len = input.len()))]
+pub fn process(input: &str) -> miette::Result<u32, AocError> {
+    solve(&parse(input)?)
+}
+
+/// [`crate::part2::solve`] with the lines split across the rayon thread pool.
+#[tracing::instrument(skip_all)]
+pub fn solve(document: &Document<'_>) -> miette::Result<u32, AocError> {
+    document
+        .lines
+        .par_iter()
+        .map(|line| document.value_of(line, process_line))
+        .try_reduce(|| 0, add_value)
+}
@@ -0,0 +1,54 @@
+//! Allocation budgets of every variant on the real input. dhat counts every
+//! allocation of the process, so this file must hold a single test.
+
+use aoc_common::budget::{assert_within, Budget};
+
+#[global_allocator]
+static ALLOC: dhat::Alloc = dhat::Alloc;
+
+#[test]
+fn allocations() -> miette::Result<()> {
+    let input = day_01::input::read_input(None)?;
+    assert_within(
+        "part1",
+        Budget {
+            blocks: 10,
+            max_bytes: 19_000,
+        },
+        || day_01::part1::process(&input),
+    )?;
+    assert_within(
+        "part2",
+        Budget {
+            blocks: 10,
+            max_bytes: 19_000,
+        },
+        || day_01::part2::process(&input),
+    )?;
+    assert_within(
+        "part2_aho",
+        Budget {
+            blocks: 72_000,
+            max_bytes: 40_000,
+        },
+        || day_01::part2_aho::process(&input),
+    )?;
+    // streaming keeps no more than a line and the running totals alive
+    assert_within(
+        "part1_reader",
+        Budget {
+            blocks: 10,
+            max_bytes: 1_000,
+        },
+        || day_01::part1::process_reader(input.as_bytes()),
+    )?;
+    assert_within(
+        "part2_reader",
+        Budget {
+            blocks: 10,
+            max_bytes: 1_000,
+        },
+        || day_01::part2::process_reader(input.as_bytes()),
+    )?;
+    Ok(())
+}
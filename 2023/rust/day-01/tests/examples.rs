@@ -0,0 +1,12 @@
+//! Every variant on every example in `examples/`, checked against the
+//! answers in `answers.txt`.
+
+aoc_common::example_tests!(
+    1 => day_01::part1,
+    #[cfg(feature = "parallel")]
+    1 => day_01::part1_parallel,
+    2 => day_01::part2,
+    2 => day_01::part2_aho,
+    #[cfg(feature = "parallel")]
+    2 => day_01::part2_parallel,
+);
@@ -0,0 +1,31 @@
+use aoc_common::check::Violations;
+
+use crate::{errors::AocError, parser};
+
+/// Every line is a game, whichever of them are malformed.
+pub fn check(input: &str) -> Vec<AocError> {
+    let mut violations = Violations::new(input);
+    violations.lines(parser::game);
+    violations.into_errors()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check() {
+        assert!(check("Game 1: 3 blue, 4 red; 1 red\nGame 2: 1 blue\n").is_empty());
+        let errors = check("Game 1: 3 blue, 4 purple\nGame 2: 1 blue\nGame 3 1 red\n")
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                "failed to parse input: expected `red`, `green` or `blue` at line 1, column 19",
+                "failed to parse input: expected `: ` at line 3, column 7",
+            ],
+            errors
+        );
+    }
+}
@@ -1,4 +1,38 @@
-pub mod errors;
+use aoc_common::solution::{Answer, Solution};
+use errors::AocError;
+
+pub use aoc_common::errors;
+pub mod check;
+pub mod input;
+pub mod parser;
 
 pub mod part1;
+#[cfg(feature = "parallel")]
+pub mod part1_parallel;
 pub mod part2;
+#[cfg(feature = "parallel")]
+pub mod part2_parallel;
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+    type Input<'a> = (Vec<part1::Game>, Vec<(u32, u32, u32)>);
+    type Params = ();
+
+    fn parse(input: &str) -> miette::Result<Self::Input<'_>, AocError> {
+        Ok((part1::parse(input)?, part2::parse(input)?))
+    }
+
+    fn part1(input: &Self::Input<'_>, _: &()) -> miette::Result<Answer, AocError> {
+        part1::solve(&input.0).map(Answer::from)
+    }
+
+    fn part2(input: &Self::Input<'_>, _: &()) -> miette::Result<Answer, AocError> {
+        part2::solve(&input.1).map(Answer::from)
+    }
+
+    fn check(input: &str) -> Vec<AocError> {
+        check::check(input)
+    }
+}
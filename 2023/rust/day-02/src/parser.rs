@@ -0,0 +1,88 @@
+use aoc_common::parse::{lines, number, tag, IResult};
+use nom::{
+    branch::alt,
+    combinator::{cut, value},
+    multi::separated_list1,
+    sequence::{delimited, pair, separated_pair},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    Red,
+    Green,
+    Blue,
+}
+
+/// Cubes of each color shown at once.
+pub type Draw = Vec<(u32, Color)>;
+
+/// Every game of the record, with its id and draws.
+pub fn games(input: &str) -> IResult<'_, Vec<(u32, Vec<Draw>)>> {
+    lines(game)(input)
+}
+
+/// `Game <id>: ` followed by the draws.
+pub fn game(input: &str) -> IResult<'_, (u32, Vec<Draw>)> {
+    pair(delimited(tag("Game "), number, tag(": ")), draws)(input)
+}
+
+/// Draws separated by `; `, each a list of `<count> <color>` separated by `, `.
+pub fn draws(input: &str) -> IResult<'_, Vec<Draw>> {
+    separated_list1(
+        tag("; "),
+        separated_list1(tag(", "), separated_pair(number, tag(" "), cut(color))),
+    )(input)
+}
+
+fn color(input: &str) -> IResult<'_, Color> {
+    alt((
+        value(Color::Red, tag("red")),
+        value(Color::Green, tag("green")),
+        value(Color::Blue, tag("blue")),
+    ))(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{errors::AocError, parse::finish};
+    use rstest::rstest;
+
+    use super::*;
+
+    #[test]
+    fn test_game() -> miette::Result<()> {
+        let game = finish("Game 3: 8 green, 6 blue; 1 red", game)?;
+        assert_eq!(
+            (
+                3,
+                vec![
+                    vec![(8, Color::Green), (6, Color::Blue)],
+                    vec![(1, Color::Red)]
+                ]
+            ),
+            game
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("Game 1 3 blue", "expected `: ` at line 1, column 7")]
+    #[case(
+        "Game 1: 3 blue, 4 purple",
+        "expected `red`, `green` or `blue` at line 1, column 19"
+    )]
+    #[case(
+        "Game 1: 3 blue\nGame 2: 3 blue;",
+        "expected the end of the line at line 2, column 15"
+    )]
+    #[case(
+        "Game 1: 3 blue\ngame 2: 1 red",
+        "expected `Game ` at line 2, column 1"
+    )]
+    fn test_games_error(#[case] input: &str, #[case] expected: &str) {
+        match finish(input, games) {
+            Err(AocError::ParseError { message, .. }) => assert_eq!(expected, message),
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+}
@@ -1,47 +1,92 @@
-use once_cell::sync::Lazy;
-use regex::Regex;
+use std::io::BufRead;
 
-use crate::errors::AocError;
+use aoc_common::{
+    parse::{finish, finish_line},
+    stream::for_each_line,
+};
 
-#[tracing::instrument]
+use crate::{
+    errors::AocError,
+    parser::{self, Color, Draw},
+};
+
+/// Id of a game and the most red, green and blue cubes shown at once.
+pub type Game = (u32, (u32, u32, u32));
+
+#[tracing::instrument(skip(input), fields(input_len = input.len()))]
 pub fn process(input: &str) -> miette::Result<u32, AocError> {
-    let mut result = 0;
-    for line in input.lines() {
-        let game_id = get_game_id(line)?;
-        if process_line(line)? {
-            result += game_id;
+    solve(&parse(input)?)
+}
+
+/// Like [`process`], but reads the input from `reader` a line at a time with
+/// [`for_each_line`].
+#[tracing::instrument(skip_all)]
+pub fn process_reader(reader: impl BufRead) -> miette::Result<u32, AocError> {
+    let mut sum = 0;
+    for_each_line(reader, |number, line| {
+        let (id, draws) = finish_line(line, number, parser::game)?;
+        if is_possible(most_cubes(&draws)) {
+            sum = add_id(sum, id)?;
         }
-    }
-    Ok(result)
+        Ok(())
+    })?;
+    Ok(sum)
 }
 
-fn get_game_id(line: &str) -> miette::Result<u32, AocError> {
-    static RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"Game (?P<id>\d+):\s?").unwrap());
-    let cap = RE.captures(line).unwrap();
-    Ok(cap["id"].parse::<u32>()?)
+#[tracing::instrument(skip_all)]
+pub fn parse(input: &str) -> miette::Result<Vec<Game>, AocError> {
+    let games = finish(input, parser::games)?;
+    Ok(games
+        .iter()
+        .map(|(id, draws)| (*id, most_cubes(draws)))
+        .collect())
 }
 
-fn process_line(line: &str) -> miette::Result<bool, AocError> {
-    static RE: Lazy<Regex> =
-        Lazy::new(|| Regex::new(r"(?P<num>\d+) (?P<color>red|green|blue)").unwrap());
-    let (red, green, blue) = (12, 13, 14);
-    for cap in RE.captures_iter(line) {
-        let num = cap["num"].parse::<u32>()?;
-        if (&cap["color"] == "red" && num > red)
-            || (&cap["color"] == "green" && num > green)
-            || (&cap["color"] == "blue" && num > blue)
-        {
-            return Ok(false);
+#[tracing::instrument(skip_all)]
+pub fn solve(games: &[Game]) -> miette::Result<u32, AocError> {
+    games
+        .iter()
+        .filter(|(_, cubes)| is_possible(*cubes))
+        .try_fold(0, |sum, &(id, _)| add_id(sum, id))
+}
+
+pub(crate) fn add_id(sum: u32, id: u32) -> Result<u32, AocError> {
+    sum.checked_add(id)
+        .ok_or_else(|| AocError::overflow("the sum of the game ids is more than u32::MAX"))
+}
+
+/// The most red, green and blue cubes shown at once in `draws`.
+pub(crate) fn most_cubes(draws: &[Draw]) -> (u32, u32, u32) {
+    let (mut red, mut green, mut blue) = (0, 0, 0);
+    for &(num, color) in draws.iter().flatten() {
+        match color {
+            Color::Red => red = red.max(num),
+            Color::Green => green = green.max(num),
+            Color::Blue => blue = blue.max(num),
         }
     }
-    Ok(true)
+    (red, green, blue)
+}
+
+/// Whether the bag holding 12 red, 13 green and 14 blue cubes could show `cubes`.
+pub(crate) fn is_possible((red, green, blue): (u32, u32, u32)) -> bool {
+    red <= 12 && green <= 13 && blue <= 14
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
     use rstest::rstest;
 
+    fn get_game_id(line: &str) -> miette::Result<u32, AocError> {
+        Ok(finish(line, parser::game)?.0)
+    }
+
+    fn max_cubes(line: &str) -> miette::Result<(u32, u32, u32), AocError> {
+        Ok(most_cubes(&finish(line, parser::game)?.1))
+    }
+
     #[rstest]
     #[case("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green", 1)]
     #[case("Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue", 2)]
@@ -70,18 +115,42 @@ mod tests {
     )]
     #[case("Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green", true)]
     fn test_process_line(#[case] input: &str, #[case] expected: bool) -> miette::Result<()> {
-        assert_eq!(expected, process_line(input)?);
+        assert_eq!(expected, is_possible(max_cubes(input)?));
         Ok(())
     }
 
     #[test]
-    fn test_process() -> miette::Result<()> {
-        let input = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
-Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
-Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
-Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
-Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
-        assert_eq!(8, process(input)?);
-        Ok(())
+    fn test_process_reports_bad_game_id() {
+        let input = "Game 1: 3 blue, 4 red
+Game 99999999999: 1 blue";
+        match process(input) {
+            Err(AocError::ParseError { span, .. }) => {
+                assert_eq!((27, 11), (span.offset(), span.len()))
+            }
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+
+    /// Up to 6 draws of up to 3 colors, each of up to 20 cubes.
+    fn draws() -> impl Strategy<Value = Vec<Draw>> {
+        let color = prop::sample::select(vec![Color::Red, Color::Green, Color::Blue]);
+        prop::collection::vec(prop::collection::vec((1..=20u32, color), 1..=3), 1..=6)
+    }
+
+    proptest! {
+        #[test]
+        fn test_most_cubes_ignores_the_order_of_draws(
+            (draws, shuffled) in draws().prop_flat_map(|draws| (Just(draws.clone()), Just(draws).prop_shuffle()))
+        ) {
+            prop_assert_eq!(most_cubes(&draws), most_cubes(&shuffled));
+        }
+
+        #[test]
+        fn test_possible_games_have_only_possible_draws(draws in draws()) {
+            prop_assert_eq!(
+                is_possible(most_cubes(&draws)),
+                draws.iter().all(|draw| is_possible(most_cubes(std::slice::from_ref(draw))))
+            );
+        }
     }
 }
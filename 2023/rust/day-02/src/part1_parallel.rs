@@ -0,0 +1,31 @@
+use aoc_common::parallel::finish_lines;
+use rayon::prelude::*;
+
+use crate::{
+    errors::AocError,
+    parser,
+    part1::{add_id, is_possible, most_cubes, Game},
+};
+
+#[tracing::instrument(skip(input), fields(input_len = input.len()))]
+pub fn process(input: &str) -> miette::Result<u32, AocError> {
+    solve(&parse(input)?)
+}
+
+/// [`crate::part1::parse`] with the games parsed across the rayon thread pool.
+#[tracing::instrument(skip_all)]
+pub fn parse(input: &str) -> miette::Result<Vec<Game>, AocError> {
+    finish_lines(input, |line| {
+        let (rest, (id, draws)) = parser::game(line)?;
+        Ok((rest, (id, most_cubes(&draws))))
+    })
+}
+
+#[tracing::instrument(skip_all)]
+pub fn solve(games: &[Game]) -> miette::Result<u32, AocError> {
+    games
+        .par_iter()
+        .filter(|(_, cubes)| is_possible(*cubes))
+        .map(|&(id, _)| Ok(id))
+        .try_reduce(|| 0, add_id)
+}
@@ -1,32 +1,73 @@
-use once_cell::sync::Lazy;
-use regex::Regex;
+use std::io::BufRead;
 
-use crate::errors::AocError;
+use aoc_common::{
+    parse::{finish, finish_line},
+    stream::for_each_line,
+};
 
-#[tracing::instrument]
+use crate::{
+    errors::AocError,
+    parser::{self, Color, Draw},
+};
+
+#[tracing::instrument(skip(input), fields(input_len = input.len()))]
 pub fn process(input: &str) -> miette::Result<u32, AocError> {
-    let mut result = 0;
-    for line in input.lines() {
-        let (max_red, max_green, max_blue) = process_line(line)?;
-        result += max_red * max_green * max_blue;
-    }
-    Ok(result)
+    solve(&parse(input)?)
+}
+
+/// Like [`process`], but reads the input from `reader` a line at a time with
+/// [`for_each_line`].
+#[tracing::instrument(skip_all)]
+pub fn process_reader(reader: impl BufRead) -> miette::Result<u32, AocError> {
+    let mut sum = 0;
+    for_each_line(reader, |number, line| {
+        let (_, draws) = finish_line(line, number, parser::game)?;
+        sum = add_power(sum, power(most_cubes(&draws))?)?;
+        Ok(())
+    })?;
+    Ok(sum)
+}
+
+/// The most red, green and blue cubes shown at once in each game.
+#[tracing::instrument(skip_all)]
+pub fn parse(input: &str) -> miette::Result<Vec<(u32, u32, u32)>, AocError> {
+    let games = finish(input, parser::games)?;
+    Ok(games.iter().map(|(_, draws)| most_cubes(draws)).collect())
+}
+
+#[tracing::instrument(skip_all)]
+pub fn solve(games: &[(u32, u32, u32)]) -> miette::Result<u32, AocError> {
+    games
+        .iter()
+        .try_fold(0, |sum, &game| add_power(sum, power(game)?))
+}
+
+/// The product of the most red, green and blue cubes of a game.
+pub(crate) fn power((red, green, blue): (u32, u32, u32)) -> Result<u32, AocError> {
+    let power = red as u128 * green as u128 * blue as u128;
+    u32::try_from(power)
+        .map_err(|_| AocError::overflow("the power of a game is more than u32::MAX"))
+}
+
+pub(crate) fn add_power(sum: u32, power: u32) -> Result<u32, AocError> {
+    sum.checked_add(power)
+        .ok_or_else(|| AocError::overflow("the sum of the powers is more than u32::MAX"))
 }
 
 pub fn process_line(line: &str) -> miette::Result<(u32, u32, u32), AocError> {
+    Ok(most_cubes(&finish(line, parser::draws)?))
+}
+
+pub(crate) fn most_cubes(draws: &[Draw]) -> (u32, u32, u32) {
     let (mut max_red, mut max_green, mut max_blue) = (0, 0, 0);
-    static RE: Lazy<Regex> =
-        Lazy::new(|| Regex::new(r"(?P<num>\d+) (?P<color>red|green|blue)").unwrap());
-    for cap in RE.captures_iter(line) {
-        let num = cap["num"].parse::<u32>()?;
-        match &cap["color"] {
-            "red" => max_red = max_red.max(num),
-            "green" => max_green = max_green.max(num),
-            "blue" => max_blue = max_blue.max(num),
-            _ => unreachable!(),
+    for &(num, color) in draws.iter().flatten() {
+        match color {
+            Color::Red => max_red = max_red.max(num),
+            Color::Green => max_green = max_green.max(num),
+            Color::Blue => max_blue = max_blue.max(num),
         }
     }
-    Ok((max_red, max_green, max_blue))
+    (max_red, max_green, max_blue)
 }
 
 #[cfg(test)]
@@ -47,15 +88,4 @@ mod tests {
         assert_eq!(expected, process_line(input)?);
         Ok(())
     }
-
-    #[test]
-    fn test_process() -> miette::Result<()> {
-        let input = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
-Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
-Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
-Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
-Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
-        assert_eq!(2286, process(input)?);
-        Ok(())
-    }
 }
@@ -0,0 +1,30 @@
+use aoc_common::parallel::finish_lines;
+use rayon::prelude::*;
+
+use crate::{
+    errors::AocError,
+    parser,
+    part2::{add_power, most_cubes, power},
+};
+
+#[tracing::instrument(skip(input), fields(input_len = input.len()))]
+pub fn process(input: &str) -> miette::Result<u32, AocError> {
+    solve(&parse(input)?)
+}
+
+/// [`crate::part2::parse`] with the games parsed across the rayon thread pool.
+#[tracing::instrument(skip_all)]
+pub fn parse(input: &str) -> miette::Result<Vec<(u32, u32, u32)>, AocError> {
+    finish_lines(input, |line| {
+        let (rest, (_, draws)) = parser::game(line)?;
+        Ok((rest, most_cubes(&draws)))
+    })
+}
+
+#[tracing::instrument(skip_all)]
+pub fn solve(games: &[(u32, u32, u32)]) -> miette::Result<u32, AocError> {
+    games
+        .par_iter()
+        .map(|&game| power(game))
+        .try_reduce(|| 0, add_power)
+}
@@ -0,0 +1,11 @@
+//! Every variant on every example in `examples/`, checked against the
+//! answers in `answers.txt`.
+
+aoc_common::example_tests!(
+    1 => day_02::part1,
+    #[cfg(feature = "parallel")]
+    1 => day_02::part1_parallel,
+    2 => day_02::part2,
+    #[cfg(feature = "parallel")]
+    2 => day_02::part2_parallel,
+);
@@ -0,0 +1,32 @@
+//! Inputs the fuzz targets in `fuzz/` found a panic on, minimized.
+
+use day_02::errors::AocError;
+
+#[test]
+fn power_of_a_game_overflows() {
+    let input = "Game 1: 3664 green, 3666628 red, 400 blue";
+    assert!(matches!(
+        day_02::part2::process(input),
+        Err(AocError::Overflow(_))
+    ));
+    assert!(matches!(
+        day_02::part2::process_reader(input.as_bytes()),
+        Err(AocError::Overflow(_))
+    ));
+}
+
+#[test]
+fn color_never_shown() -> miette::Result<()> {
+    let input = "Game 1: 3664 green, 3666628 red";
+    assert_eq!(0, day_02::part2::process(input)?);
+    Ok(())
+}
+
+#[test]
+fn sum_of_ids_overflows() {
+    let input = "Game 4294967295: 1 red\nGame 1: 1 red";
+    assert!(matches!(
+        day_02::part1::process(input),
+        Err(AocError::Overflow(_))
+    ));
+}
@@ -0,0 +1,3 @@
+//! The streaming entry points give the same answers as the `&str` ones.
+
+aoc_common::streaming_tests!(1 => day_02::part1, 2 => day_02::part2);
@@ -0,0 +1,36 @@
+use aoc_common::check::Violations;
+
+use crate::{errors::AocError, parser};
+
+/// The schematic is a rectangle of digits, `.` and symbols.
+pub fn check(input: &str) -> Vec<AocError> {
+    let mut violations = Violations::new(input);
+    let rows = violations
+        .lines(parser::row)
+        .into_iter()
+        .map(|(row, _)| row)
+        .collect::<Vec<_>>();
+    violations.rectangle(&rows);
+    violations.into_errors()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check() {
+        assert!(check("467..114..\n...*......\n").is_empty());
+        let errors = check("467..114..\n...*..\n..35 .633.\n")
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                "failed to parse input: expected the end of the line at line 3, column 5",
+                "invalid puzzle input: row is 6 wide, the first row is 10 wide",
+            ],
+            errors
+        );
+    }
+}
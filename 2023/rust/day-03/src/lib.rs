@@ -1,4 +1,49 @@
-pub mod errors;
+use aoc_common::{
+    check::rectangle,
+    parse::finish,
+    solution::{Answer, Solution},
+};
+use errors::AocError;
+
+pub use aoc_common::errors;
+pub mod check;
+pub mod input;
+pub mod parser;
 
 pub mod part1;
 pub mod part2;
+
+pub struct Day03;
+
+/// The schematic as a grid of characters, which both parts read.
+#[tracing::instrument(skip_all)]
+pub fn parse(input: &str) -> miette::Result<Vec<Vec<char>>, AocError> {
+    let rows = finish(input, parser::schematic)?;
+    rectangle(input, &rows)?;
+    Ok(rows
+        .into_iter()
+        .map(|line| line.chars().collect::<Vec<_>>())
+        .collect::<Vec<_>>())
+}
+
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+    type Input<'a> = Vec<Vec<char>>;
+    type Params = ();
+
+    fn parse(input: &str) -> miette::Result<Self::Input<'_>, AocError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _: &()) -> miette::Result<Answer, AocError> {
+        part1::solve(input).map(Answer::from)
+    }
+
+    fn part2(input: &Self::Input<'_>, _: &()) -> miette::Result<Answer, AocError> {
+        part2::solve(input).map(Answer::from)
+    }
+
+    fn check(input: &str) -> Vec<AocError> {
+        check::check(input)
+    }
+}
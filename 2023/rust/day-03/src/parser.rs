@@ -0,0 +1,35 @@
+use aoc_common::parse::{lines, IResult};
+use nom::{
+    character::complete::satisfy, combinator::recognize, error::context, multi::many1_count,
+};
+
+/// Rows of the engine schematic.
+pub fn schematic(input: &str) -> IResult<'_, Vec<&str>> {
+    lines(row)(input)
+}
+
+/// Digits, `.` and symbols.
+pub fn row(input: &str) -> IResult<'_, &str> {
+    recognize(many1_count(context(
+        "a digit, `.` or a symbol",
+        satisfy(|ch| ch.is_ascii_graphic()),
+    )))(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{errors::AocError, parse::finish};
+
+    use super::*;
+
+    #[test]
+    fn test_schematic_error() {
+        match finish("467..114..\n...*......\n..35 .633.\n", schematic) {
+            Err(AocError::ParseError { message, span, .. }) => {
+                assert_eq!("expected the end of the line at line 3, column 5", message);
+                assert_eq!(26, span.offset());
+            }
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+}
@@ -1,30 +1,36 @@
+use std::iter;
+
 use crate::errors::AocError;
 
-#[tracing::instrument]
+pub use crate::parse;
+
+#[tracing::instrument(skip(input), fields(input_len = input.len()))]
 pub fn process(input: &str) -> miette::Result<u32, AocError> {
-    let input = input
-        .lines()
-        .map(|line| {
-            let mut line = line.chars().collect::<Vec<_>>();
-            line.push('.');
-            line
-        })
-        .collect::<Vec<_>>();
+    solve(&parse(input)?)
+}
 
-    let mut result = 0;
-    let mut current = 0;
+#[tracing::instrument(skip_all)]
+pub fn solve(grid: &[Vec<char>]) -> miette::Result<u32, AocError> {
+    let mut result = 0u32;
+    let mut current = 0u32;
     let (mut start, mut end) = (None, None);
-    for (r, row) in input.iter().enumerate() {
-        for (c, ch) in row.iter().enumerate() {
+    for (r, row) in grid.iter().enumerate() {
+        // a `.` past the end of the row ends the numbers that reach it
+        for (c, ch) in row.iter().chain(iter::once(&'.')).enumerate() {
             if let Some(num) = ch.to_digit(10) {
                 if start.is_none() {
                     start = Some(c);
                 }
                 end = Some(c);
-                current = current * 10 + num;
+                current = current
+                    .checked_mul(10)
+                    .and_then(|current| current.checked_add(num))
+                    .ok_or_else(|| AocError::overflow("a part number is more than u32::MAX"))?;
             } else if current > 0 {
-                if adj_symbol(&input, r, start.unwrap(), end.unwrap()) {
-                    result += current;
+                if adj_symbol(grid, r, start.unwrap(), end.unwrap()) {
+                    result = result.checked_add(current).ok_or_else(|| {
+                        AocError::overflow("the sum of the part numbers is more than u32::MAX")
+                    })?;
                 }
                 current = 0;
                 start = None;
@@ -36,12 +42,12 @@ pub fn process(input: &str) -> miette::Result<u32, AocError> {
     Ok(result)
 }
 
-fn adj_symbol(mat: &Vec<Vec<char>>, row: usize, start: usize, end: usize) -> bool {
+fn adj_symbol(mat: &[Vec<char>], row: usize, start: usize, end: usize) -> bool {
     for r in row.saturating_sub(1)..=row.saturating_add(1) {
         for c in start.saturating_sub(1)..=end.saturating_add(1) {
             if r < mat.len() && c < mat[0].len() {
                 let ch = mat[r][c];
-                if !ch.is_digit(10) && ch != '.' {
+                if !ch.is_ascii_digit() && ch != '.' {
                     return true;
                 }
             }
@@ -52,31 +58,53 @@ fn adj_symbol(mat: &Vec<Vec<char>>, row: usize, start: usize, end: usize) -> boo
 
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::*;
 
-    #[test]
-    fn test_process() -> miette::Result<()> {
-        let input = "467..114..
-...*......
-..35..633.
-......#...
-617*......
-.....+.58.
-..592.....
-......755.
-...$.*....
-.664.598..";
-        assert_eq!(4361, process(input)?);
-        Ok(())
+    /// A number on a schematic of up to 8 rows and 12 columns of `.`, as
+    /// `(rows, cols, row, col, number)`.
+    fn placement() -> impl Strategy<Value = (usize, usize, usize, usize, u32)> {
+        (1..1000u32, 1..8usize).prop_flat_map(|(number, rows)| {
+            let len = number.to_string().len();
+            (len..=12usize).prop_flat_map(move |cols| {
+                (
+                    Just(rows),
+                    Just(cols),
+                    0..rows,
+                    0..=cols - len,
+                    Just(number),
+                )
+            })
+        })
     }
 
-    #[test]
-    fn test_process2() -> miette::Result<()> {
-        let input = "...............307............130..................969...601...186.........................................312....628..........878..........
-......479#../..*..............................#.....*......*............../309.....484........................*......-..........+.....89....
-...........726..352...461..69..............435.....390...625....................................459.........152...-....580............*.....
-";
-        assert_eq!(7252, process(input)?);
-        Ok(())
+    proptest! {
+        #[test]
+        fn test_numbers_count_next_to_a_symbol(
+            (rows, cols, row, col, number) in placement(),
+            neighbor in any::<Option<prop::sample::Index>>(),
+            symbol in prop::sample::select(b"*#+$/@=%&-".to_vec()),
+        ) {
+            let mut grid = vec![vec!['.'; cols]; rows];
+            let digits = number.to_string();
+            for (i, digit) in digits.chars().enumerate() {
+                grid[row][col + i] = digit;
+            }
+            let neighbors = (row.saturating_sub(1)..(row + 2).min(rows))
+                .flat_map(|r| (col.saturating_sub(1)..(col + digits.len() + 1).min(cols)).map(move |c| (r, c)))
+                .filter(|&(r, c)| grid[r][c] == '.')
+                .collect::<Vec<_>>();
+            let expected = match neighbor.filter(|_| !neighbors.is_empty()) {
+                Some(neighbor) => {
+                    let (r, c) = neighbors[neighbor.index(neighbors.len())];
+                    grid[r][c] = symbol as char;
+                    number
+                }
+                None => 0,
+            };
+            let input = grid.iter().map(|row| row.iter().collect::<String>()).collect::<Vec<_>>();
+            prop_assert_eq!(expected, process(&input.join("\n"))?);
+        }
     }
 }
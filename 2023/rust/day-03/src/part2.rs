@@ -1,20 +1,29 @@
 use crate::errors::AocError;
 
-#[tracing::instrument]
+pub use crate::parse;
+
+#[tracing::instrument(skip(input), fields(input_len = input.len()))]
 pub fn process(input: &str) -> miette::Result<u32, AocError> {
-    let mut input = input
-        .lines()
-        .map(|line| line.chars().collect::<Vec<_>>())
-        .collect::<Vec<_>>();
-    let (m, n) = (input.len(), input[0].len());
+    solve(&parse(input)?)
+}
 
-    let mut result = 0;
+#[tracing::instrument(skip_all)]
+pub fn solve(grid: &[Vec<char>]) -> miette::Result<u32, AocError> {
+    // numbers are blanked out once counted, so work on a copy
+    let mut input = grid.to_vec();
+    let (m, n) = (input.len(), input.first().map_or(0, Vec::len));
+
+    let mut result = 0u32;
     for r in 0..m {
         for c in 0..n {
             if input[r][c] == '*' {
-                let adj_numbers = find_adj_numbers(&mut input, r, c);
-                if adj_numbers.len() == 2 {
-                    result += adj_numbers.iter().product::<u32>();
+                if let [a, b] = find_adj_numbers(&mut input, r, c)?[..] {
+                    result = a
+                        .checked_mul(b)
+                        .and_then(|ratio| result.checked_add(ratio))
+                        .ok_or_else(|| {
+                            AocError::overflow("the sum of the gear ratios is more than u32::MAX")
+                        })?;
                 }
             }
         }
@@ -24,31 +33,37 @@ pub fn process(input: &str) -> miette::Result<u32, AocError> {
 }
 
 /// Returns the adjacent numbers of the given position
-fn find_adj_numbers(mat: &mut Vec<Vec<char>>, row: usize, col: usize) -> Vec<u32> {
+fn find_adj_numbers(
+    mat: &mut [Vec<char>],
+    row: usize,
+    col: usize,
+) -> miette::Result<Vec<u32>, AocError> {
     let mut result = Vec::new();
     for r in row.saturating_sub(1)..=row.saturating_add(1) {
         for c in col.saturating_sub(1)..=col.saturating_add(1) {
-            if r < mat.len() && c < mat[0].len() {
-                if mat[r][c].is_digit(10) {
-                    result.push(get_number(mat, r, c));
-                }
+            if r < mat.len() && c < mat[0].len() && mat[r][c].is_ascii_digit() {
+                result.push(get_number(mat, r, c)?);
             }
         }
     }
-    result
+    Ok(result)
 }
 
 /// Returns the number
-fn get_number(mat: &mut Vec<Vec<char>>, row: usize, col: usize) -> u32 {
+fn get_number(mat: &mut [Vec<char>], row: usize, col: usize) -> miette::Result<u32, AocError> {
+    let overflow = || AocError::overflow("a part number is more than u32::MAX");
     let mut number = mat[row][col].to_digit(10).unwrap();
     let (mut start, mut end) = (col, col);
-    let mut multiplier = 10;
+    let mut multiplier = Some(10u32);
     while let Some(i) = start.checked_sub(1) {
         if let Some(n) = mat[row][i].to_digit(10) {
-            number = n * multiplier + number;
+            number = multiplier
+                .and_then(|multiplier| n.checked_mul(multiplier))
+                .and_then(|n| number.checked_add(n))
+                .ok_or_else(overflow)?;
             mat[row][i] = '.';
             start = i;
-            multiplier *= 10;
+            multiplier = multiplier.and_then(|multiplier| multiplier.checked_mul(10));
         } else {
             break;
         }
@@ -58,33 +73,49 @@ fn get_number(mat: &mut Vec<Vec<char>>, row: usize, col: usize) -> u32 {
             break;
         }
         if let Some(n) = mat[row][i].to_digit(10) {
-            number = number * 10 + n;
+            number = number
+                .checked_mul(10)
+                .and_then(|number| number.checked_add(n))
+                .ok_or_else(overflow)?;
             mat[row][i] = '.';
             end = i;
         } else {
             break;
         }
     }
-    number
+    Ok(number)
 }
 
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::*;
 
-    #[test]
-    fn test_process() -> miette::Result<()> {
-        let input = "467..114..
-...*......
-..35..633.
-......#...
-617*......
-.....+.58.
-..592.....
-......755.
-...$.*....
-.664.598..";
-        assert_eq!(467835, process(input)?);
-        Ok(())
+    proptest! {
+        #[test]
+        fn test_gear_ratio(
+            a in 1..1000u32,
+            b in 1..1000u32,
+            vertical in any::<bool>(),
+            (left, right, above, below) in (0..4usize, 0..4usize, 0..3usize, 0..3usize),
+        ) {
+            let pad = |n: usize| ".".repeat(n);
+            let rows = match vertical {
+                true => vec![
+                    format!("{}{}", pad(left + 3), a),
+                    format!("{}*{}", pad(left + 3), pad(right)),
+                    format!("{}{}", pad(left + 3), b),
+                ],
+                false => vec![format!("{}{}*{}{}", pad(left), a, b, pad(right))],
+            };
+            let width = rows.iter().map(String::len).max().unwrap_or_default();
+            let mut input = vec![pad(width); above];
+            input.extend(rows.into_iter().map(|row| format!("{:.<width$}", row, width = width)));
+            input.extend(vec![pad(width); below]);
+            let input = input.join("\n");
+            prop_assert_eq!(a * b, process(&input)?);
+            prop_assert_eq!(a + b, crate::part1::process(&input)?);
+        }
     }
 }
@@ -0,0 +1,29 @@
+//! Allocation budgets of every variant on the real input. dhat counts every
+//! allocation of the process, so this file must hold a single test.
+
+use aoc_common::budget::{assert_within, Budget};
+
+#[global_allocator]
+static ALLOC: dhat::Alloc = dhat::Alloc;
+
+#[test]
+fn allocations() -> miette::Result<()> {
+    let input = day_03::input::read_input(None)?;
+    assert_within(
+        "part1",
+        Budget {
+            blocks: 470,
+            max_bytes: 96_000,
+        },
+        || day_03::part1::process(&input),
+    )?;
+    assert_within(
+        "part2",
+        Budget {
+            blocks: 1_100,
+            max_bytes: 190_000,
+        },
+        || day_03::part2::process(&input),
+    )?;
+    Ok(())
+}
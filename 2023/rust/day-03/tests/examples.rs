@@ -0,0 +1,7 @@
+//! Every variant on every example in `examples/`, checked against the
+//! answers in `answers.txt`.
+
+aoc_common::example_tests!(
+    1 => day_03::part1,
+    2 => day_03::part2,
+);
@@ -0,0 +1,39 @@
+//! Inputs the fuzz targets in `fuzz/` found a panic on, minimized.
+
+use day_03::errors::AocError;
+
+#[test]
+fn ragged_rows() {
+    for input in [".3\n.", "..\n."] {
+        assert!(matches!(
+            day_03::part1::process(input),
+            Err(AocError::InvalidInput { .. })
+        ));
+        assert!(matches!(
+            day_03::part2::process(input),
+            Err(AocError::InvalidInput { .. })
+        ));
+    }
+}
+
+#[test]
+fn part_number_overflows() {
+    let input = "12345678901*\n...........2";
+    assert!(matches!(
+        day_03::part1::process(input),
+        Err(AocError::Overflow(_))
+    ));
+    assert!(matches!(
+        day_03::part2::process(input),
+        Err(AocError::Overflow(_))
+    ));
+}
+
+#[test]
+fn gear_ratio_overflows() {
+    let input = "99999*99999";
+    assert!(matches!(
+        day_03::part2::process(input),
+        Err(AocError::Overflow(_))
+    ));
+}
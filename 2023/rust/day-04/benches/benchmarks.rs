@@ -1,22 +1,12 @@
-use day_04::*;
+#[global_allocator]
+static ALLOC: divan::AllocProfiler = divan::AllocProfiler::system();
 
 fn main() {
     // Run registered benchmarks.
     divan::main();
 }
 
-#[divan::bench]
-fn part1() {
-    part1::process(divan::black_box(include_str!(
-        "../input.txt",
-    )))
-    .unwrap();
-}
+aoc_common::bench_stages!(day_04::part1);
+aoc_common::bench_stages!(day_04::part2);
 
-#[divan::bench]
-fn part2() {
-    part2::process(divan::black_box(include_str!(
-        "../input.txt",
-    )))
-    .unwrap();
-}
+aoc_common::bench_throughput!(day_04::part1, day_04::part2);
@@ -0,0 +1,42 @@
+use aoc_common::check::Violations;
+
+use crate::{errors::AocError, parser};
+
+/// Every line is a card, and the cards are numbered from 1 in order since
+/// part 2 wins copies of the cards that follow.
+pub fn check(input: &str) -> Vec<AocError> {
+    let mut violations = Violations::new(input);
+    for (i, line) in input.lines().enumerate() {
+        match violations.line(line, parser::card) {
+            Some((id, _, _)) if id as usize != i + 1 => {
+                violations.report(line, format!("expected card {}, found card {}", i + 1, id))
+            }
+            _ => {}
+        }
+    }
+    if input.trim().is_empty() {
+        violations.report(input, "there are no cards");
+    }
+    violations.into_errors()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check() {
+        assert!(check("Card 1: 41 48 | 83 86\nCard 2: 13 | 61\n").is_empty());
+        let errors = check("Card 1: 41 48 | 83 x6\nCard 2: 13 | 61\nCard 4: 1 | 2\n")
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                "failed to parse input: expected the end of the line at line 1, column 19",
+                "invalid puzzle input: expected card 3, found card 4",
+            ],
+            errors
+        );
+    }
+}
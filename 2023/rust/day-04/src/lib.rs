@@ -1,4 +1,34 @@
-pub mod errors;
+use aoc_common::solution::{Answer, Solution};
+use errors::AocError;
+
+pub use aoc_common::errors;
+pub mod check;
+pub mod input;
+pub mod parser;
 
 pub mod part1;
 pub mod part2;
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+    type Input<'a> = (Vec<part1::Card>, Vec<part2::Card>);
+    type Params = ();
+
+    fn parse(input: &str) -> miette::Result<Self::Input<'_>, AocError> {
+        Ok((part1::parse(input)?, part2::parse(input)?))
+    }
+
+    fn part1(input: &Self::Input<'_>, _: &()) -> miette::Result<Answer, AocError> {
+        part1::solve(&input.0).map(Answer::from)
+    }
+
+    fn part2(input: &Self::Input<'_>, _: &()) -> miette::Result<Answer, AocError> {
+        part2::solve(&input.1).map(Answer::from)
+    }
+
+    fn check(input: &str) -> Vec<AocError> {
+        check::check(input)
+    }
+}
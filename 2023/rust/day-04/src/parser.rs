@@ -0,0 +1,81 @@
+use std::collections::HashSet;
+
+use aoc_common::parse::{lines, number, tag, IResult};
+use nom::{
+    character::complete::space1,
+    combinator::cut,
+    multi::fold_many0,
+    sequence::{delimited, pair, preceded, separated_pair, tuple},
+};
+
+/// Id, winning numbers and the numbers you have of a scratchcard.
+pub type Card = (u32, HashSet<u32>, HashSet<u32>);
+
+/// Every scratchcard of the pile.
+pub fn cards(input: &str) -> IResult<'_, Vec<Card>> {
+    lines(card)(input)
+}
+
+/// `Card <id>: <winning numbers> | <numbers you have>`
+pub fn card(input: &str) -> IResult<'_, Card> {
+    let (rest, (id, (winning, have))) = tuple((
+        delimited(pair(tag("Card"), space1), number, tag(":")),
+        cut(separated_pair(
+            preceded(space1, number_set),
+            preceded(space1, tag("|")),
+            preceded(space1, number_set),
+        )),
+    ))(input)?;
+    Ok((rest, (id, winning, have)))
+}
+
+/// Numbers separated by spaces, collected as they are parsed.
+fn number_set(input: &str) -> IResult<'_, HashSet<u32>> {
+    let (rest, first) = number(input)?;
+    fold_many0(
+        preceded(space1, number),
+        move || HashSet::from([first]),
+        |mut set, number| {
+            set.insert(number);
+            set
+        },
+    )(rest)
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{errors::AocError, parse::finish};
+    use rstest::rstest;
+
+    use super::*;
+
+    #[test]
+    fn test_cards() -> miette::Result<()> {
+        assert_eq!(
+            vec![
+                (1, HashSet::from([41, 48]), HashSet::from([83, 86, 6])),
+                (12, HashSet::from([13]), HashSet::from([61]))
+            ],
+            finish("Card 1: 41 48 | 83 86  6\nCard  12: 13 | 61\n", cards)?
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("Card 1: 41 48 83 86", "expected a space at line 1, column 20")]
+    #[case(
+        "Card 1: 41 48 | 83 x6",
+        "expected the end of the line at line 1, column 19"
+    )]
+    #[case("Card 1 41 48 | 83", "expected `:` at line 1, column 7")]
+    #[case(
+        "Card 1: 41 48 | 83\nCard: 1 | 2",
+        "expected a space at line 2, column 5"
+    )]
+    fn test_cards_error(#[case] input: &str, #[case] expected: &str) {
+        match finish(input, cards) {
+            Err(AocError::ParseError { message, .. }) => assert_eq!(expected, message),
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+}
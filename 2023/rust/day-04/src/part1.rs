@@ -1,44 +1,109 @@
-use std::collections::HashSet;
+use std::{collections::HashSet, io::BufRead};
 
-use crate::errors::AocError;
+use aoc_common::{
+    parse::{finish, finish_line},
+    stream::for_each_line,
+};
 
-#[tracing::instrument]
+use crate::{errors::AocError, parser};
+
+/// The winning numbers and the numbers you have on a scratchcard.
+#[derive(Debug)]
+pub struct Card {
+    pub winning: HashSet<u32>,
+    pub have: HashSet<u32>,
+}
+
+#[tracing::instrument(skip(input), fields(input_len = input.len()))]
 pub fn process(input: &str) -> miette::Result<u32, AocError> {
+    solve(&parse(input)?)
+}
+
+/// Like [`process`], but reads the input from `reader` a line at a time with
+/// [`for_each_line`].
+#[tracing::instrument(skip_all)]
+pub fn process_reader(reader: impl BufRead) -> miette::Result<u32, AocError> {
     let mut result = 0;
-    for line in input.lines() {
-        let num_sets = line
-            .split(':')
-            .last()
-            .expect("Each line should start with `Game #:`")
-            .split('|')
-            .map(|s| {
-                s.split_whitespace()
-                    .map(|n| n.parse::<u32>().expect("should be a number"))
-                    .collect::<HashSet<_>>()
-            })
-            .collect::<Vec<_>>();
-        let num_won = num_sets[0].intersection(&num_sets[1]).count();
-        if num_won > 0 {
-            result += u32::pow(2, num_won as u32 - 1);
-        }
+    for_each_line(reader, |number, line| {
+        let (_, winning, have) = finish_line(line, number, parser::card)?;
+        result = add_points(result, winning.intersection(&have).count())?;
+        Ok(())
+    })?;
+    Ok(result)
+}
+
+#[tracing::instrument(skip_all)]
+pub fn parse(input: &str) -> miette::Result<Vec<Card>, AocError> {
+    let cards = finish(input, parser::cards)?
+        .into_iter()
+        .map(|(_, winning, have)| Card { winning, have })
+        .collect();
+    Ok(cards)
+}
+
+#[tracing::instrument(skip_all)]
+pub fn solve(cards: &[Card]) -> miette::Result<u32, AocError> {
+    let mut result = 0;
+    for card in cards {
+        result = add_points(result, card.winning.intersection(&card.have).count())?;
     }
 
     Ok(result)
 }
 
+/// Adds the points of a card with `num_won` winning numbers, 1 doubled for
+/// every one after the first, to `result`.
+fn add_points(result: u32, num_won: usize) -> Result<u32, AocError> {
+    if num_won == 0 {
+        return Ok(result);
+    }
+    u32::try_from(num_won - 1)
+        .ok()
+        .and_then(|doublings| 1u32.checked_shl(doublings))
+        .and_then(|points| result.checked_add(points))
+        .ok_or_else(|| AocError::overflow("the points are more than u32::MAX"))
+}
+
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeSet;
+
+    use proptest::prelude::*;
+
     use super::*;
 
-    #[test]
-    fn test_process() -> miette::Result<()> {
-        let input = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
-Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
-Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
-Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
-Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
-Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
-        assert_eq!(13, process(input)?);
-        Ok(())
+    /// Up to 15 cards of 5 winning numbers and 8 numbers you have, each with
+    /// how many of them win.
+    fn cards() -> impl Strategy<Value = (String, Vec<usize>)> {
+        let numbers = |len| prop::collection::btree_set(1..60u32, len);
+        prop::collection::vec((numbers(5), numbers(8)), 1..16).prop_map(|cards| {
+            let join = |numbers: &BTreeSet<u32>| {
+                numbers
+                    .iter()
+                    .map(|n| format!("{:>2}", n))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            };
+            let lines = cards
+                .iter()
+                .enumerate()
+                .map(|(i, (winning, have))| {
+                    format!("Card {}: {} | {}", i + 1, join(winning), join(have))
+                })
+                .collect::<Vec<_>>();
+            let matches = cards
+                .iter()
+                .map(|(winning, have)| winning.intersection(have).count())
+                .collect();
+            (lines.join("\n"), matches)
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn test_points_double_with_every_match((input, matches) in cards()) {
+            let expected = matches.iter().map(|&m| if m > 0 { 1 << (m - 1) } else { 0 }).sum::<u32>();
+            prop_assert_eq!(expected, process(&input)?);
+        }
     }
 }
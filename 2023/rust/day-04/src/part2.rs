@@ -1,53 +1,133 @@
 #![allow(dead_code, unused)]
 
-use std::collections::HashSet;
+use std::{
+    collections::{HashSet, VecDeque},
+    io::BufRead,
+};
 
-use crate::errors::AocError;
+use aoc_common::{
+    parse::{finish, finish_line},
+    stream::for_each_line,
+};
 
-#[tracing::instrument]
+use crate::{errors::AocError, parser};
+
+/// The winning numbers and the numbers you have on a scratchcard.
+#[derive(Debug)]
+pub struct Card {
+    pub winning: HashSet<u32>,
+    pub have: HashSet<u32>,
+}
+
+#[tracing::instrument(skip(input), fields(input_len = input.len()))]
 pub fn process(input: &str) -> miette::Result<u32, AocError> {
-    let lines = input.lines().collect::<Vec<&str>>();
-    let num_games = lines.len();
+    solve(&parse(input)?)
+}
+
+/// Like [`process`], but reads the input from `reader` a line at a time.
+/// Only the copies won of the next few cards are kept, so memory is bounded
+/// by the longest line and the most numbers a card can win.
+#[tracing::instrument(skip_all)]
+pub fn process_reader(reader: impl BufRead) -> miette::Result<u32, AocError> {
+    // extra copies of the cards that follow, starting with the current one
+    let mut won = VecDeque::new();
+    let mut result = 0;
+    for_each_line(reader, |number, line| {
+        let (_, winning, have) = finish_line(line, number, parser::card)?;
+        let copies = add_copies(1, won.pop_front().unwrap_or(0))?;
+        result = add_copies(result, copies)?;
+        let num_won = winning.intersection(&have).count();
+        if won.len() < num_won {
+            won.resize(num_won, 0);
+        }
+        for extra in won.iter_mut().take(num_won) {
+            *extra = add_copies(*extra, copies)?;
+        }
+        Ok(())
+    })?;
+    Ok(result)
+}
+
+#[tracing::instrument(skip_all)]
+pub fn parse(input: &str) -> miette::Result<Vec<Card>, AocError> {
+    let cards = finish(input, parser::cards)?
+        .into_iter()
+        .map(|(_, winning, have)| Card { winning, have })
+        .collect();
+    Ok(cards)
+}
+
+#[tracing::instrument(skip_all)]
+pub fn solve(cards: &[Card]) -> miette::Result<u32, AocError> {
+    let num_games = cards.len();
     let mut counter = vec![1; num_games];
 
-    for (i, line) in lines.iter().enumerate() {
-        let num_sets = line
-            .split(':')
-            .last()
-            .expect("line should start with `Game #:`")
-            .split('|')
-            .map(|nums| {
-                nums.split_whitespace()
-                    .map(|n| n.parse::<u32>().expect("should be a number"))
-                    .collect::<HashSet<_>>()
-            })
-            .collect::<Vec<_>>();
-        let num_won = num_sets[0].intersection(&num_sets[1]).count();
+    for (i, card) in cards.iter().enumerate() {
+        let num_won = card.winning.intersection(&card.have).count();
         for j in 1..=num_won {
             if i + j < num_games {
-                counter[i + j] += counter[i];
+                counter[i + j] = add_copies(counter[i + j], counter[i])?;
             }
         }
     }
 
-    let result = counter.iter().sum::<u32>();
-    Ok(result)
+    counter.into_iter().try_fold(0, add_copies)
+}
+
+fn add_copies(copies: u32, more: u32) -> Result<u32, AocError> {
+    copies
+        .checked_add(more)
+        .ok_or_else(|| AocError::overflow("the copies of the cards are more than u32::MAX"))
 }
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeSet;
+
     use super::*;
+    use proptest::prelude::*;
     use rstest::rstest;
 
-    #[test]
-    fn test_process() -> miette::Result<()> {
-        let input = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
-Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
-Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
-Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
-Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
-Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
-        assert_eq!(30, process(input)?);
-        Ok(())
+    /// Up to 15 cards of 5 winning numbers and 8 numbers you have, each with
+    /// how many of them win.
+    fn cards() -> impl Strategy<Value = (String, Vec<usize>)> {
+        let numbers = |len| prop::collection::btree_set(1..60u32, len);
+        prop::collection::vec((numbers(5), numbers(8)), 1..16).prop_map(|cards| {
+            let join = |numbers: &BTreeSet<u32>| {
+                numbers
+                    .iter()
+                    .map(|n| format!("{:>2}", n))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            };
+            let lines = cards
+                .iter()
+                .enumerate()
+                .map(|(i, (winning, have))| {
+                    format!("Card {}: {} | {}", i + 1, join(winning), join(have))
+                })
+                .collect::<Vec<_>>();
+            let matches = cards
+                .iter()
+                .map(|(winning, have)| winning.intersection(have).count())
+                .collect();
+            (lines.join("\n"), matches)
+        })
+    }
+
+    /// Card `i` and every copy it wins, and they win, recursively.
+    fn copies(matches: &[usize], i: usize) -> u32 {
+        1 + (i + 1..(i + 1 + matches[i]).min(matches.len()))
+            .map(|j| copies(matches, j))
+            .sum::<u32>()
+    }
+
+    proptest! {
+        #[test]
+        fn test_copies_win_copies((input, matches) in cards()) {
+            let expected = (0..matches.len()).map(|i| copies(&matches, i)).sum::<u32>();
+            prop_assert_eq!(expected, process(&input)?);
+            prop_assert_eq!(expected, process_reader(input.as_bytes())?);
+        }
     }
 }
@@ -0,0 +1,38 @@
+//! Inputs the fuzz targets in `fuzz/` found a panic on, minimized.
+
+use day_04::errors::AocError;
+
+#[test]
+fn points_overflow() {
+    let numbers = (1..=33)
+        .map(|n| n.to_string())
+        .collect::<Vec<_>>()
+        .join(" ");
+    let input = format!("Card 1: {} | {}", numbers, numbers);
+    assert!(matches!(
+        day_04::part1::process(&input),
+        Err(AocError::Overflow(_))
+    ));
+    assert!(matches!(
+        day_04::part1::process_reader(input.as_bytes()),
+        Err(AocError::Overflow(_))
+    ));
+}
+
+#[test]
+fn copies_overflow() {
+    // every card wins a copy of the next two, so the copies grow like the
+    // Fibonacci numbers
+    let input = (1..=50)
+        .map(|id| format!("Card {}: 1 2 | 1 2", id))
+        .collect::<Vec<_>>()
+        .join("\n");
+    assert!(matches!(
+        day_04::part2::process(&input),
+        Err(AocError::Overflow(_))
+    ));
+    assert!(matches!(
+        day_04::part2::process_reader(input.as_bytes()),
+        Err(AocError::Overflow(_))
+    ));
+}
@@ -1,25 +1,36 @@
-use day_05::*;
+use aoc_common::generate::{generate_seeded, Shape};
+
+#[global_allocator]
+static ALLOC: divan::AllocProfiler = divan::AllocProfiler::system();
 
 fn main() {
     // Run registered benchmarks.
     divan::main();
 }
 
-#[divan::bench]
-fn part1_binary_search() {
-    part1_binary_search::process(divan::black_box(include_str!("../input.txt",))).unwrap();
-}
+aoc_common::bench_stages!(day_05::part1_binary_search);
+aoc_common::bench_stages!(day_05::part1_btree);
+#[cfg(feature = "parallel")]
+aoc_common::bench_stages!(day_05::part1_parallel);
+aoc_common::bench_stages!(day_05::part2_binary_search);
+#[cfg(feature = "parallel")]
+aoc_common::bench_stages!(day_05::part2_parallel);
+// brute forces every seed of the real input, takes hours
+#[cfg(feature = "slow")]
+aoc_common::bench_stages!(day_05::part2_btree);
 
-#[divan::bench]
-fn part1_btree() {
-    part1_btree::process(divan::black_box(include_str!("../input.txt",))).unwrap();
+/// 16 seed ranges of `seeds` seeds each.
+fn almanac(seeds: usize) -> String {
+    let shape = Shape {
+        width: Some(seeds),
+        ..Shape::new(16)
+    };
+    generate_seeded(5, shape, 0).unwrap().input
 }
 
-#[divan::bench]
-fn part2_binary_search() {
-    part2_binary_search::process(divan::black_box(include_str!("../input.txt",))).unwrap();
-}
-#[divan::bench]
-fn part2_btree() {
-    part2_btree::process(divan::black_box(include_str!("../input.txt",))).unwrap();
-}
+aoc_common::bench_scaling!(seeds_per_range: [1, 10, 100, 1_000, 10_000] => almanac;
+    day_05::part2_binary_search,
+    day_05::part2_btree,
+    #[cfg(feature = "parallel")]
+    day_05::part2_parallel,
+);
@@ -0,0 +1,95 @@
+use aoc_common::check::Violations;
+
+use crate::{errors::AocError, parser};
+
+/// The seeds and the 7 maps parse, the maps lead from `seed` to `location`
+/// one after the other, and the source ranges of a map do not overlap, since
+/// every seed is looked up in a single range of each map.
+pub fn check(input: &str) -> Vec<AocError> {
+    let mut violations = Violations::new(input);
+    let Some(almanac) = violations.parse(parser::almanac) else {
+        return violations.into_errors();
+    };
+
+    let mut expected = "seed";
+    for &(source, destination) in &almanac.categories {
+        if source != expected {
+            violations.report(
+                source,
+                format!(
+                    "expected a map from `{}`, found one from `{}`",
+                    expected, source
+                ),
+            );
+        }
+        expected = destination;
+    }
+    if let Some(&(_, last)) = almanac.categories.last() {
+        if last != "location" {
+            violations.report(last, "the last map does not lead to `location`");
+        }
+    }
+
+    for (&(source, destination), ranges) in almanac.categories.iter().zip(&almanac.maps) {
+        let mut ranges = ranges.clone();
+        ranges.sort_by_key(|&(_, src, _)| src);
+        for pair in ranges.windows(2) {
+            let ((_, src, len), (_, next, _)) = (pair[0], pair[1]);
+            if src + len > next {
+                violations.report(
+                    source,
+                    format!(
+                        "source ranges starting at {} and {} of the {}-to-{} map overlap",
+                        src, next, source, destination
+                    ),
+                );
+            }
+        }
+    }
+    violations.into_errors()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn almanac(maps: &[(&str, &str, &str)]) -> String {
+        let mut input = "seeds: 79 14".to_string();
+        for (source, destination, ranges) in maps {
+            input += &format!("\n\n{}-to-{} map:\n{}", source, destination, ranges);
+        }
+        input
+    }
+
+    #[test]
+    fn test_check() {
+        let maps = [
+            ("seed", "soil", "50 98 2\n52 50 48"),
+            ("soil", "fertilizer", "0 15 37"),
+            ("fertilizer", "water", "49 53 8"),
+            ("water", "light", "88 18 7"),
+            ("light", "temperature", "45 77 23"),
+            ("temperature", "humidity", "0 69 1"),
+            ("humidity", "location", "60 56 37"),
+        ];
+        assert!(check(&almanac(&maps)).is_empty());
+
+        let mut broken = maps;
+        broken[0].2 = "50 98 2\n52 90 48";
+        broken[3] = ("light", "water", "88 18 7");
+        broken[6].1 = "place";
+        let errors = check(&almanac(&broken))
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                "invalid puzzle input: expected a map from `water`, found one from `light`",
+                "invalid puzzle input: expected a map from `water`, found one from `light`",
+                "invalid puzzle input: the last map does not lead to `location`",
+                "invalid puzzle input: source ranges starting at 90 and 98 of the seed-to-soil map overlap",
+            ],
+            errors
+        );
+    }
+}
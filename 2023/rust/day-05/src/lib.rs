@@ -1,6 +1,67 @@
-pub mod errors;
+use aoc_common::{
+    parse::finish,
+    solution::{Answer, Solution},
+};
+use errors::AocError;
+
+pub use aoc_common::errors;
+pub mod check;
+pub mod input;
+pub mod parser;
 
 pub mod part1_binary_search;
 pub mod part1_btree;
+#[cfg(feature = "parallel")]
+pub mod part1_parallel;
 pub mod part2_binary_search;
 pub mod part2_btree;
+#[cfg(feature = "parallel")]
+pub mod part2_parallel;
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+    /// The input and its almanac as written, each part builds its own maps
+    /// from it, and only part 2 needs the seeds to pair up.
+    type Input<'a> = (&'a str, parser::Almanac<'a>);
+    type Params = ();
+
+    fn parse(input: &str) -> miette::Result<Self::Input<'_>, AocError> {
+        Ok((input, finish(input, parser::almanac)?))
+    }
+
+    fn part1((_, almanac): &Self::Input<'_>, _: &()) -> miette::Result<Answer, AocError> {
+        part1_binary_search::solve(&part1_binary_search::from_almanac(almanac.clone()))
+            .map(Answer::from)
+    }
+
+    fn part2((input, almanac): &Self::Input<'_>, _: &()) -> miette::Result<Answer, AocError> {
+        part2_binary_search::solve(&part2_binary_search::from_almanac(input, almanac.clone())?)
+            .map(Answer::from)
+    }
+
+    fn check(input: &str) -> Vec<AocError> {
+        check::check(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = include_str!("../examples/example.txt");
+
+    #[test]
+    fn test_odd_seeds() -> miette::Result<()> {
+        // without seed 13 at 35, and three seeds are fine for part 1, which
+        // does not pair them up
+        let input = INPUT.replacen("79 14 55 13", "79 14 55", 1);
+        assert_eq!(Answer::from(43u64), Day05::solve(&input, 1)?);
+        assert!(matches!(
+            Day05::solve(&input, 2),
+            Err(AocError::InvalidInput { .. })
+        ));
+        Ok(())
+    }
+}
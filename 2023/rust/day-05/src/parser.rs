@@ -0,0 +1,183 @@
+use aoc_common::{
+    errors::AocError,
+    parse::{blank_line, lines, number, numbers, tag, Error, Expected, IResult},
+};
+use nom::{
+    character::complete::{alpha1, line_ending, space1},
+    combinator::{consumed, cut, not, opt},
+    error::context,
+    multi::count,
+    sequence::{pair, preceded, separated_pair, terminated, tuple},
+};
+
+/// The seeds and the maps from seed to location, as written.
+#[derive(Debug, Clone)]
+pub struct Almanac<'a> {
+    /// The seed numbers, for diagnostics.
+    pub seeds_token: &'a str,
+    pub seeds: Vec<u64>,
+    /// `(source, destination)` categories of every map.
+    pub categories: Vec<(&'a str, &'a str)>,
+    /// `(dst, src, len)` ranges of every map.
+    pub maps: Vec<Vec<Range>>,
+}
+
+impl Almanac<'_> {
+    /// The seeds as `(start, length)` pairs, for part 2. `input` is the whole
+    /// input, which the seed numbers are a slice of.
+    pub fn seed_ranges(&self, input: &str) -> Result<Vec<(u64, u64)>, AocError> {
+        if !self.seeds.len().is_multiple_of(2) {
+            return Err(AocError::invalid_input(
+                input,
+                self.seeds_token,
+                "seeds must come in `<start> <length>` pairs",
+            ));
+        }
+        self.seeds
+            .chunks(2)
+            .map(|chunk| match (chunk[0], chunk[1]) {
+                (start, 0) => Err(AocError::invalid_input(
+                    input,
+                    self.seeds_token,
+                    format!("the seed range starting at {} is empty", start),
+                )),
+                (start, len) if start.checked_add(len).is_none() => Err(AocError::invalid_input(
+                    input,
+                    self.seeds_token,
+                    format!("the seed range starting at {} ends past u64::MAX", start),
+                )),
+                range => Ok(range),
+            })
+            .collect()
+    }
+}
+
+/// `(dst, src, len)` of a range of a map.
+pub type Range = (u64, u64, u64);
+
+/// `seeds: <numbers>` and the 7 maps, each after a blank line.
+pub fn almanac(input: &str) -> IResult<'_, Almanac<'_>> {
+    let (rest, (seeds_token, seeds)) = preceded(tag("seeds: "), consumed(numbers))(input)?;
+    let (rest, maps) = count(
+        preceded(context("another of the 7 maps", blank_line), cut(map)),
+        7,
+    )(rest)?;
+    let (categories, maps) = maps.into_iter().unzip();
+    Ok((
+        rest,
+        Almanac {
+            seeds_token,
+            seeds,
+            categories,
+            maps,
+        },
+    ))
+}
+
+/// `<source>-to-<destination> map:` and a `<dst> <src> <len>` line per range,
+/// if there are any.
+fn map(input: &str) -> IResult<'_, ((&str, &str), Vec<Range>)> {
+    let header = terminated(separated_pair(alpha1, tag("-to-"), alpha1), tag(" map:"));
+    let ranges = lines(range);
+    let (rest, (categories, ranges)) = pair(
+        header,
+        opt(preceded(terminated(line_ending, not(line_ending)), ranges)),
+    )(input)?;
+    Ok((rest, (categories, ranges.unwrap_or_default())))
+}
+
+/// `<dst> <src> <len>`, where neither range ends past `u64::MAX`.
+fn range(input: &str) -> IResult<'_, Range> {
+    let (rest, (dst, src, len)) = tuple((
+        number::<u64>,
+        preceded(space1, number),
+        preceded(space1, number),
+    ))(input)?;
+    if dst.max(src).checked_add(len).is_none() {
+        return Err(nom::Err::Failure(Error::new(
+            input,
+            Expected::Text("a range that ends before u64::MAX"),
+        )));
+    }
+    Ok((rest, (dst, src, len)))
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{errors::AocError, parse::finish};
+    use rstest::rstest;
+
+    use super::*;
+
+    const MAPS: &str = "
+
+seed-to-soil map:
+50 98 2
+
+soil-to-fertilizer map:
+0 15 37
+
+fertilizer-to-water map:
+49 53 8
+
+water-to-light map:
+88 18 7
+
+light-to-temperature map:
+45 77 23
+
+temperature-to-humidity map:
+0 69 1
+
+humidity-to-location map:
+60 56 37
+56 93 4
+";
+
+    #[test]
+    fn test_almanac() -> miette::Result<()> {
+        let input = format!("seeds: 79 14 55 13{}", MAPS);
+        let almanac = finish(&input, almanac)?;
+        assert_eq!(vec![79, 14, 55, 13], almanac.seeds);
+        assert_eq!("79 14 55 13", almanac.seeds_token);
+        assert_eq!(7, almanac.maps.len());
+        assert_eq!(("seed", "soil"), almanac.categories[0]);
+        assert_eq!(vec![(60, 56, 37), (56, 93, 4)], almanac.maps[6]);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("seeds 79 14", "expected `seeds: ` at line 1, column 1")]
+    #[case(
+        "seeds: 79 14\n\nseed-to-soil map:\n50 98",
+        "expected a space at line 4, column 6"
+    )]
+    #[case(
+        "seeds: 79 14\n\nseed-to-soil map:\n50 98 2",
+        "expected another of the 7 maps at line 4, column 8"
+    )]
+    #[case(
+        "seeds: 79 14\n\nseed-to-soil:\n50 98 2",
+        "expected ` map:` at line 3, column 13"
+    )]
+    fn test_almanac_error(#[case] input: &str, #[case] expected: &str) {
+        match finish(input, almanac) {
+            Err(AocError::ParseError { message, .. }) => assert_eq!(expected, message),
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_almanac_too_many_maps() {
+        let input = format!("seeds: 79 14{}\nseed-to-soil map:\n50 98 2", MAPS);
+        match finish(&input, almanac) {
+            Err(AocError::ParseError { message, .. }) => {
+                assert_eq!(
+                    "expected the end of the input at line 23, column 8",
+                    message
+                )
+            }
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+}
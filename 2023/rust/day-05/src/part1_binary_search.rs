@@ -1,4 +1,6 @@
-use crate::errors::AocError;
+use aoc_common::parse::finish;
+
+use crate::{errors::AocError, parser};
 
 #[derive(Debug, PartialEq)]
 struct Range {
@@ -83,13 +85,13 @@ impl Map {
 }
 
 #[derive(Debug)]
-struct Maps(Vec<Map>);
+pub(crate) struct Maps(Vec<Map>);
 impl Maps {
     fn new(maps: Vec<Map>) -> Self {
         Self(maps)
     }
 
-    fn get(&self, target: u64) -> u64 {
+    pub(crate) fn get(&self, target: u64) -> u64 {
         self.0.iter().fold(target, |target, map| map.get(target))
     }
 }
@@ -103,86 +105,131 @@ impl std::fmt::Display for Maps {
     }
 }
 
-#[tracing::instrument]
+/// Seeds to plant and the maps from seed to location.
+#[derive(Debug)]
+pub struct Almanac {
+    pub seeds: Vec<u64>,
+    pub(crate) maps: Maps,
+}
+
+#[tracing::instrument(skip(input), fields(input_len = input.len()))]
 pub fn process(input: &str) -> miette::Result<u64, AocError> {
-    let mut input = input.split("\n\n");
-    assert_eq!(input.clone().count(), 8);
-
-    let seeds = input
-        .next()
-        .expect("should be seeds")
-        .split(": ")
-        .last()
-        .expect("should be seeds")
-        .split_whitespace()
-        .map(|n| n.parse::<u64>().expect("should be a number"))
-        .collect::<Vec<_>>();
+    solve(&parse(input)?)
+}
 
+#[tracing::instrument(skip_all)]
+pub fn parse(input: &str) -> miette::Result<Almanac, AocError> {
+    Ok(from_almanac(finish(input, parser::almanac)?))
+}
+
+/// The seeds and sorted maps of an almanac.
+pub(crate) fn from_almanac(almanac: parser::Almanac) -> Almanac {
+    let build_maps = tracing::info_span!("build_maps").entered();
     let maps = Maps::new(
-        input
-            .map(|section| {
-                let mut map = section
-                    .lines()
-                    .skip(1)
-                    .map(|line| {
-                        let nums = line
-                            .split_whitespace()
-                            .map(|n| n.parse::<u64>().expect("should be a number"))
-                            .collect::<Vec<_>>();
-                        Range::new(nums[1], nums[0], nums[2])
-                    })
+        almanac
+            .maps
+            .into_iter()
+            .map(|ranges| {
+                let mut map = ranges
+                    .into_iter()
+                    .map(|(dst, src, len)| Range::new(src, dst, len))
                     .collect::<Vec<_>>();
                 map.sort_unstable_by_key(|range| range.src);
                 Map::new(map)
             })
-            .collect::<Vec<_>>(),
+            .collect(),
     );
+    drop(build_maps);
+
+    Almanac {
+        seeds: almanac.seeds,
+        maps,
+    }
+}
 
-    let result = seeds.iter().map(|&seed| maps.get(seed)).min().unwrap();
+#[tracing::instrument(skip_all)]
+pub fn solve(almanac: &Almanac) -> miette::Result<u64, AocError> {
+    let Almanac { seeds, maps } = almanac;
+    let result = seeds
+        .iter()
+        .map(|&seed| maps.get(seed))
+        .min()
+        .expect("seeds are checked while parsing");
 
     Ok(result)
 }
 
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::*;
 
-    #[test]
-    fn test_process() -> miette::Result<()> {
-        let input = "seeds: 79 14 55 13
-
-seed-to-soil map:
-50 98 2
-52 50 48
-
-soil-to-fertilizer map:
-0 15 37
-37 52 2
-39 0 15
-
-fertilizer-to-water map:
-49 53 8
-0 11 42
-42 0 7
-57 7 4
-
-water-to-light map:
-88 18 7
-18 25 70
-
-light-to-temperature map:
-45 77 23
-81 45 19
-68 64 13
-
-temperature-to-humidity map:
-0 69 1
-1 0 69
-
-humidity-to-location map:
-60 56 37
-56 93 4";
-        assert_eq!(35, process(input)?);
-        Ok(())
+    const CATEGORIES: [&str; 8] = [
+        "seed",
+        "soil",
+        "fertilizer",
+        "water",
+        "light",
+        "temperature",
+        "humidity",
+        "location",
+    ];
+
+    /// An almanac of `seeds` and the `(dst, src, len)` ranges of 7 maps.
+    fn almanac(seeds: &[u64], maps: &[Vec<(u64, u64, u64)>]) -> String {
+        let seeds = seeds.iter().map(u64::to_string).collect::<Vec<_>>();
+        let mut almanac = format!("seeds: {}", seeds.join(" "));
+        for (categories, ranges) in CATEGORIES.windows(2).zip(maps) {
+            almanac.push_str(&format!("\n\n{}-to-{} map:", categories[0], categories[1]));
+            for (dst, src, len) in ranges {
+                almanac.push_str(&format!("\n{} {} {}", dst, src, len));
+            }
+        }
+        almanac
+    }
+
+    /// 7 maps of up to 5 ranges below 1000, overlapping ones included.
+    fn maps() -> impl Strategy<Value = Vec<Vec<(u64, u64, u64)>>> {
+        let range = (0..1000u64, 0..1000u64, 1..50u64);
+        prop::collection::vec(prop::collection::vec(range, 0..5), 7)
+    }
+
+    /// 7 maps of up to 5 ranges below 1000 that do not overlap.
+    fn disjoint_maps() -> impl Strategy<Value = Vec<Vec<(u64, u64, u64)>>> {
+        let bounds = prop::collection::btree_set(0..1000u64, 0..10);
+        let map = (bounds, prop::collection::vec(0..1000u64, 5)).prop_map(|(bounds, dsts)| {
+            let bounds = bounds.into_iter().collect::<Vec<_>>();
+            bounds
+                .chunks_exact(2)
+                .zip(dsts)
+                .map(|(range, dst)| (dst, range[0], range[1] - range[0]))
+                .collect()
+        });
+        prop::collection::vec(map, 7)
+    }
+
+    proptest! {
+        #[test]
+        fn test_get_is_the_identity_outside_the_ranges(maps in maps(), seed in 0..1500u64) {
+            prop_assume!(maps
+                .iter()
+                .flatten()
+                .all(|&(_, src, len)| !(src..src + len).contains(&seed)));
+            let almanac = parse(&almanac(&[seed], &maps))?;
+            prop_assert_eq!(seed, almanac.maps.get(seed));
+        }
+
+        #[test]
+        fn test_get_maps_through_every_range(maps in disjoint_maps(), seed in 0..1000u64) {
+            let expected = maps.iter().fold(seed, |n, ranges| {
+                ranges
+                    .iter()
+                    .find(|&&(_, src, len)| (src..src + len).contains(&n))
+                    .map_or(n, |&(dst, src, _)| dst + (n - src))
+            });
+            let almanac = parse(&almanac(&[seed], &maps))?;
+            prop_assert_eq!(expected, almanac.maps.get(seed));
+        }
     }
 }
@@ -1,36 +1,45 @@
 use std::collections::BTreeMap;
 use std::ops::Bound::{Included, Unbounded};
 
-use crate::errors::AocError;
+use aoc_common::parse::finish;
 
-#[tracing::instrument]
+use crate::{errors::AocError, parser};
+
+/// Seeds to plant and the maps from seed to location.
+#[derive(Debug)]
+pub struct Almanac {
+    pub seeds: Vec<u64>,
+    maps: Vec<BTreeMap<u64, (u64, u64)>>,
+}
+
+#[tracing::instrument(skip(input), fields(input_len = input.len()))]
 pub fn process(input: &str) -> miette::Result<u64, AocError> {
-    let mut input = input.split("\n\n");
-    assert_eq!(input.clone().count(), 8);
-    let seeds = input
-        .next()
-        .expect("should be seeds")
-        .split(": ")
-        .last()
-        .expect("should be seeds")
-        .split_whitespace()
-        .map(|n| n.parse::<u64>().expect("should be a number"))
-        .collect::<Vec<_>>();
+    solve(&parse(input)?)
+}
 
-    let maps = input
-        .map(|section| {
-            let mut map: BTreeMap<u64, (u64, u64)> = BTreeMap::new(); // Key: source start, Value: (dest start, len)
-            section.lines().skip(1).for_each(|line| {
-                let nums = line
-                    .split_whitespace()
-                    .map(|n| n.parse::<u64>().expect("should be a number"))
-                    .collect::<Vec<_>>();
-                map.insert(nums[1], (nums[0], nums[2]));
-            });
-            map
+#[tracing::instrument(skip_all)]
+pub fn parse(input: &str) -> miette::Result<Almanac, AocError> {
+    let parser::Almanac { seeds, maps, .. } = finish(input, parser::almanac)?;
+
+    let build_maps = tracing::info_span!("build_maps").entered();
+    let maps = maps
+        .into_iter()
+        .map(|ranges| {
+            // Key: source start, Value: (dest start, len)
+            ranges
+                .into_iter()
+                .map(|(dst, src, len)| (src, (dst, len)))
+                .collect::<BTreeMap<_, _>>()
         })
-        .collect::<Vec<_>>();
+        .collect();
+    drop(build_maps);
 
+    Ok(Almanac { seeds, maps })
+}
+
+#[tracing::instrument(skip_all)]
+pub fn solve(almanac: &Almanac) -> miette::Result<u64, AocError> {
+    let Almanac { seeds, maps } = almanac;
     let result = seeds
         .iter()
         .map(|seed| {
@@ -39,7 +48,7 @@ pub fn process(input: &str) -> miette::Result<u64, AocError> {
                 if let Some((&src_start, &(dst_start, len))) =
                     map.range((Unbounded, Included(&source))).next_back()
                 {
-                    if src_start <= source && source <= src_start + len {
+                    if src_start <= source && source < src_start + len {
                         source = dst_start + (source - src_start);
                     }
                 }
@@ -47,51 +56,7 @@ pub fn process(input: &str) -> miette::Result<u64, AocError> {
             source
         })
         .min()
-        .unwrap();
+        .expect("seeds are checked while parsing");
 
     Ok(result)
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_process() -> miette::Result<()> {
-        let input = "seeds: 79 14 55 13
-
-seed-to-soil map:
-50 98 2
-52 50 48
-
-soil-to-fertilizer map:
-0 15 37
-37 52 2
-39 0 15
-
-fertilizer-to-water map:
-49 53 8
-0 11 42
-42 0 7
-57 7 4
-
-water-to-light map:
-88 18 7
-18 25 70
-
-light-to-temperature map:
-45 77 23
-81 45 19
-68 64 13
-
-temperature-to-humidity map:
-0 69 1
-1 0 69
-
-humidity-to-location map:
-60 56 37
-56 93 4";
-        assert_eq!(35, process(input)?);
-        Ok(())
-    }
-}
@@ -0,0 +1,24 @@
+use rayon::prelude::*;
+
+use crate::errors::AocError;
+
+pub use crate::part1_binary_search::{parse, Almanac};
+
+#[tracing::instrument(skip(input), fields(input_len = input.len()))]
+pub fn process(input: &str) -> miette::Result<u64, AocError> {
+    solve(&parse(input)?)
+}
+
+/// [`crate::part1_binary_search::solve`] with the seeds split across the
+/// rayon thread pool.
+#[tracing::instrument(skip_all)]
+pub fn solve(almanac: &Almanac) -> miette::Result<u64, AocError> {
+    let Almanac { seeds, maps } = almanac;
+    let result = seeds
+        .par_iter()
+        .map(|&seed| maps.get(seed))
+        .min()
+        .expect("seeds are checked while parsing");
+
+    Ok(result)
+}
@@ -1,4 +1,6 @@
-use crate::errors::AocError;
+use aoc_common::parse::finish;
+
+use crate::{errors::AocError, parser};
 
 #[derive(Debug, PartialEq)]
 struct Range {
@@ -58,127 +60,130 @@ impl Map {
         }
         target
     }
+
+    /// Index of the range `target` is in, or `Err` with the index of the
+    /// range after the gap it is in.
+    fn piece(&self, target: u64) -> Result<usize, usize> {
+        self.0.binary_search_by(|range| {
+            if range.contains(target) {
+                std::cmp::Ordering::Equal
+            } else if target < range.src {
+                std::cmp::Ordering::Greater
+            } else {
+                std::cmp::Ordering::Less
+            }
+        })
+    }
+}
+
+/// Runs the searches of both halves of a seed range and returns their
+/// results, one after the other like [`sequential`] or in parallel.
+pub(crate) type Join = fn(&Half, &Half) -> (u64, u64);
+
+/// The search of one half of a seed range.
+pub(crate) type Half<'a> = dyn Fn() -> u64 + Sync + 'a;
+
+pub(crate) fn sequential(left: &Half, right: &Half) -> (u64, u64) {
+    (left(), right())
 }
 
 #[derive(Debug)]
-struct Maps(Vec<Map>);
+pub(crate) struct Maps(Vec<Map>);
 impl Maps {
     fn new(maps: Vec<Map>) -> Self {
         Self(maps)
     }
 
-    fn get(&self, target: u64) -> u64 {
+    pub(crate) fn get(&self, target: u64) -> u64 {
         self.0.iter().fold(target, |target, map| map.get(target))
     }
 
+    /// Lowest location of the seeds `lo..=hi`, halving the range until each
+    /// piece is shifted as a whole. `join` runs the two halves of a split.
+    ///
     /// * `lo`: start of the seed range (inclusive)
     /// * `hi`: end of the seed range (inclusive)
-    fn get_range(&self, lo: u64, hi: u64) -> u64 {
+    #[tracing::instrument(level = "debug", skip(self, join))]
+    pub(crate) fn get_range(&self, lo: u64, hi: u64, join: Join) -> u64 {
         assert!(lo <= hi, "Seed range must be non-empty");
-        if hi == lo {
+        if hi == lo || self.is_continuous(lo, hi) {
             return self.get(lo);
         }
 
-        let lo_val = self.get(lo);
-        let hi_val = self.get(hi);
+        let mid = (hi + lo) / 2;
+        let (left, right) = join(&|| self.get_range(lo, mid, join), &|| {
+            self.get_range(mid + 1, hi, join)
+        });
+        u64::min(left, right)
+    }
 
-        if lo_val < hi_val && hi_val - lo_val == hi - lo {
-            // the destination range is continuous
-            lo_val
-        } else {
-            let mid = (hi + lo) / 2;
-            u64::min(self.get_range(lo, mid), self.get_range(mid + 1, hi))
+    /// Whether `lo..=hi` stays within one range or gap of every map, so that
+    /// it is shifted as a whole. Checking that only its ends are shifted by
+    /// the same amount misses ranges mapped from somewhere in between.
+    pub(crate) fn is_continuous(&self, mut lo: u64, mut hi: u64) -> bool {
+        for map in self.0.iter() {
+            if map.piece(lo) != map.piece(hi) {
+                return false;
+            }
+            (lo, hi) = (map.get(lo), map.get(hi));
         }
+        true
     }
 }
 
-#[tracing::instrument]
+/// Seeds to plant and the maps from seed to location.
+#[derive(Debug)]
+pub struct Almanac {
+    /// `(start, length)` ranges of seeds.
+    pub seeds: Vec<(u64, u64)>,
+    pub(crate) maps: Maps,
+}
+
+#[tracing::instrument(skip(input), fields(input_len = input.len()))]
 pub fn process(input: &str) -> miette::Result<u64, AocError> {
-    let mut input = input.split("\n\n");
-    assert_eq!(input.clone().count(), 8);
-
-    let seeds = input
-        .next()
-        .expect("should be seeds")
-        .split(": ")
-        .last()
-        .expect("should be seeds")
-        .split_whitespace()
-        .map(|n| n.parse::<u64>().expect("should be a number"))
-        .collect::<Vec<_>>()
-        .chunks(2)
-        .map(|chunk| (chunk[0], chunk[1]))
-        .collect::<Vec<_>>();
+    solve(&parse(input)?)
+}
+
+#[tracing::instrument(skip_all)]
+pub fn parse(input: &str) -> miette::Result<Almanac, AocError> {
+    from_almanac(input, finish(input, parser::almanac)?)
+}
 
+/// The seed ranges and sorted maps of an almanac parsed from `input`.
+pub(crate) fn from_almanac(
+    input: &str,
+    almanac: parser::Almanac,
+) -> miette::Result<Almanac, AocError> {
+    let seeds = almanac.seed_ranges(input)?;
+
+    let build_maps = tracing::info_span!("build_maps").entered();
     let maps = Maps::new(
-        input
-            .map(|section| {
-                let mut map = section
-                    .lines()
-                    .skip(1)
-                    .map(|line| {
-                        let nums = line
-                            .split_whitespace()
-                            .map(|n| n.parse::<u64>().expect("should be a number"))
-                            .collect::<Vec<_>>();
-                        Range::new(nums[1], nums[0], nums[2])
-                    })
+        almanac
+            .maps
+            .into_iter()
+            .map(|ranges| {
+                let mut map = ranges
+                    .into_iter()
+                    .map(|(dst, src, len)| Range::new(src, dst, len))
                     .collect::<Vec<_>>();
                 map.sort_unstable_by_key(|range| range.src);
                 Map::new(map)
             })
-            .collect::<Vec<_>>(),
+            .collect(),
     );
+    drop(build_maps);
+
+    Ok(Almanac { seeds, maps })
+}
 
+#[tracing::instrument(skip_all)]
+pub fn solve(almanac: &Almanac) -> miette::Result<u64, AocError> {
+    let Almanac { seeds, maps } = almanac;
     let result = seeds
         .iter()
-        .map(|&(seed, len)| maps.get_range(seed, seed + len - 1))
+        .map(|&(seed, len)| maps.get_range(seed, seed + len - 1, sequential))
         .min()
-        .unwrap();
+        .expect("seeds are checked while parsing");
 
     Ok(result)
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_process() -> miette::Result<()> {
-        let input = "seeds: 79 14 55 13
-
-seed-to-soil map:
-50 98 2
-52 50 48
-
-soil-to-fertilizer map:
-0 15 37
-37 52 2
-39 0 15
-
-fertilizer-to-water map:
-49 53 8
-0 11 42
-42 0 7
-57 7 4
-
-water-to-light map:
-88 18 7
-18 25 70
-
-light-to-temperature map:
-45 77 23
-81 45 19
-68 64 13
-
-temperature-to-humidity map:
-0 69 1
-1 0 69
-
-humidity-to-location map:
-60 56 37
-56 93 4";
-        assert_eq!(46, process(input)?);
-        Ok(())
-    }
-}
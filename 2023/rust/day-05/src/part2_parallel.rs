@@ -0,0 +1,27 @@
+use rayon::prelude::*;
+
+use crate::errors::AocError;
+
+pub use crate::part2_binary_search::{parse, Almanac};
+
+#[tracing::instrument(skip(input), fields(input_len = input.len()))]
+pub fn process(input: &str) -> miette::Result<u64, AocError> {
+    solve(&parse(input)?)
+}
+
+/// [`crate::part2_binary_search::solve`] with the seed ranges split across
+/// the rayon thread pool, and the halves of every range that is not shifted
+/// as a whole searched in parallel too.
+#[tracing::instrument(skip_all)]
+pub fn solve(almanac: &Almanac) -> miette::Result<u64, AocError> {
+    let Almanac { seeds, maps } = almanac;
+    let result = seeds
+        .par_iter()
+        .map(|&(seed, len)| {
+            maps.get_range(seed, seed + len - 1, |left, right| rayon::join(left, right))
+        })
+        .min()
+        .expect("seeds are checked while parsing");
+
+    Ok(result)
+}
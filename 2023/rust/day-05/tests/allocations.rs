@@ -0,0 +1,37 @@
+//! Allocation budgets of every variant on the real input. dhat counts every
+//! allocation of the process, so this file must hold a single test.
+
+use aoc_common::budget::{assert_within, Budget};
+
+#[global_allocator]
+static ALLOC: dhat::Alloc = dhat::Alloc;
+
+#[test]
+fn allocations() -> miette::Result<()> {
+    let input = day_05::input::read_input(None)?;
+    assert_within(
+        "part1_binary_search",
+        Budget {
+            blocks: 300,
+            max_bytes: 9_400,
+        },
+        || day_05::part1_binary_search::process(&input),
+    )?;
+    assert_within(
+        "part1_btree",
+        Budget {
+            blocks: 310,
+            max_bytes: 12_000,
+        },
+        || day_05::part1_btree::process(&input),
+    )?;
+    assert_within(
+        "part2_binary_search",
+        Budget {
+            blocks: 310,
+            max_bytes: 9_600,
+        },
+        || day_05::part2_binary_search::process(&input),
+    )?;
+    Ok(())
+}
@@ -0,0 +1,13 @@
+//! Every variant on every example in `examples/`, checked against the
+//! answers in `answers.txt`.
+
+aoc_common::example_tests!(
+    1 => day_05::part1_binary_search,
+    1 => day_05::part1_btree,
+    #[cfg(feature = "parallel")]
+    1 => day_05::part1_parallel,
+    2 => day_05::part2_binary_search,
+    2 => day_05::part2_btree,
+    #[cfg(feature = "parallel")]
+    2 => day_05::part2_parallel,
+);
@@ -0,0 +1,55 @@
+//! Inputs the fuzz targets in `fuzz/` found a panic on, minimized.
+
+use day_05::errors::AocError;
+
+/// The seeds and 7 maps of the single range `ranges`.
+fn almanac(seeds: &str, ranges: &str) -> String {
+    let mut input = format!("seeds: {}", seeds);
+    for (source, destination) in [
+        ("seed", "soil"),
+        ("soil", "fertilizer"),
+        ("fertilizer", "water"),
+        ("water", "light"),
+        ("light", "temperature"),
+        ("temperature", "humidity"),
+        ("humidity", "location"),
+    ] {
+        input += &format!("\n\n{}-to-{} map:\n{}", source, destination, ranges);
+    }
+    input
+}
+
+#[test]
+fn empty_seed_range() {
+    let input = almanac("7 0", "50 98 2");
+    assert!(matches!(
+        day_05::part2_binary_search::process(&input),
+        Err(AocError::InvalidInput { .. })
+    ));
+    assert!(matches!(
+        day_05::part2_btree::process(&input),
+        Err(AocError::InvalidInput { .. })
+    ));
+}
+
+#[test]
+fn seed_range_overflows() {
+    let input = almanac("18446744073709551615 2", "50 98 2");
+    assert!(matches!(
+        day_05::part2_binary_search::process(&input),
+        Err(AocError::InvalidInput { .. })
+    ));
+}
+
+#[test]
+fn map_range_overflows() {
+    let input = almanac("79 14", "18446744073709551615 0 2");
+    assert!(matches!(
+        day_05::part1_binary_search::process(&input),
+        Err(AocError::ParseError { .. })
+    ));
+    assert!(matches!(
+        day_05::part1_btree::process(&input),
+        Err(AocError::ParseError { .. })
+    ));
+}
@@ -0,0 +1,53 @@
+use aoc_common::check::Violations;
+
+use crate::{errors::AocError, parser};
+
+/// Every race has a record distance, and lasts at least 2 milliseconds so
+/// that there is a button press to search for.
+pub fn check(input: &str) -> Vec<AocError> {
+    let mut violations = Violations::new(input);
+    let Some((times, distances)) = violations.parse(parser::races) else {
+        return violations.into_errors();
+    };
+
+    let mut lines = input.lines();
+    let (time_line, distance_line) = (lines.next().unwrap_or(input), lines.next().unwrap_or(input));
+    if times.len() != distances.len() {
+        violations.report(
+            distance_line,
+            format!(
+                "there are {} times but {} record distances",
+                times.len(),
+                distances.len()
+            ),
+        );
+    }
+    // the number tokens, after the `Time:` label
+    for (token, &time) in time_line.split_whitespace().skip(1).zip(&times) {
+        if time < 2 {
+            violations.report(token, "a race must last at least 2 milliseconds");
+        }
+    }
+    violations.into_errors()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check() {
+        assert!(check("Time:      7  15   30\nDistance:  9  40  200\n").is_empty());
+        let errors = check("Time:      7  1   30\nDistance:  9  40\n")
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                "invalid puzzle input: there are 3 times but 2 record distances",
+                "invalid puzzle input: a race must last at least 2 milliseconds",
+            ],
+            errors
+        );
+    }
+}
@@ -0,0 +1,96 @@
+use aoc_common::parse::{end_of_line, numbers, tag, Error, Expected, IResult};
+use nom::{
+    character::complete::{digit1, line_ending, space1},
+    combinator::{cut, recognize},
+    multi::many0_count,
+    sequence::{pair, preceded, separated_pair, terminated},
+};
+
+/// Times and record distances of the races, one column each.
+pub fn races(input: &str) -> IResult<'_, (Vec<usize>, Vec<usize>)> {
+    sheet(numbers)(input)
+}
+
+/// Time and record distance of the single race, ignoring the spaces.
+pub fn race(input: &str) -> IResult<'_, (usize, usize)> {
+    sheet(kerned_number)(input)
+}
+
+/// A `Time:` and a `Distance:` line, each holding a `value`.
+fn sheet<'a, T>(
+    value: impl FnMut(&'a str) -> IResult<'a, T> + Copy,
+) -> impl FnMut(&'a str) -> IResult<'a, (T, T)> {
+    separated_pair(row("Time:", value), line_ending, row("Distance:", value))
+}
+
+/// A line of the sheet: `label` and the `value` filling the rest of it.
+pub fn row<'a, T>(
+    label: &'static str,
+    value: impl FnMut(&'a str) -> IResult<'a, T>,
+) -> impl FnMut(&'a str) -> IResult<'a, T> {
+    preceded(
+        pair(tag(label), space1),
+        cut(terminated(value, end_of_line)),
+    )
+}
+
+/// Digits with spaces between them, read as a single number.
+pub fn kerned_number(input: &str) -> IResult<'_, usize> {
+    let (rest, digits) = recognize(pair(digit1, many0_count(pair(space1, digit1))))(input)?;
+    let number = digits
+        .bytes()
+        .filter(u8::is_ascii_digit)
+        .try_fold(0_usize, |number, digit| {
+            number.checked_mul(10)?.checked_add((digit - b'0') as usize)
+        });
+    match number {
+        Some(number) => Ok((rest, number)),
+        None => Err(nom::Err::Failure(Error::new(
+            input,
+            Expected::Fits("usize"),
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{errors::AocError, parse::finish};
+    use rstest::rstest;
+
+    use super::*;
+
+    const INPUT: &str = "Time:      7  15   30\nDistance:  9  40  200\n";
+
+    #[test]
+    fn test_races() -> miette::Result<()> {
+        assert_eq!((vec![7, 15, 30], vec![9, 40, 200]), finish(INPUT, races)?);
+        assert_eq!((71530, 940200), finish(INPUT, race)?);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("Time: 7 15 30", "expected a line break at line 1, column 14")]
+    #[case(
+        "Time: 7 15\nDistance: 9 4o",
+        "expected the end of the line at line 2, column 14"
+    )]
+    #[case("Time: 7 15\nRecord: 9 40", "expected `Distance:` at line 2, column 1")]
+    #[case("Time 7 15\nDistance: 9 40", "expected `Time:` at line 1, column 1")]
+    fn test_races_error(#[case] input: &str, #[case] expected: &str) {
+        match finish(input, races) {
+            Err(AocError::ParseError { message, .. }) => assert_eq!(expected, message),
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_race_overflow() {
+        match finish("Time: 7 15\nDistance: 9 40000 0000000 00000000", race) {
+            Err(AocError::ParseError { message, .. }) => assert_eq!(
+                "expected a number that fits in usize at line 2, column 11",
+                message
+            ),
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+}
@@ -1,30 +1,66 @@
-use crate::errors::AocError;
+use std::io::BufRead;
 
-#[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<usize, AocError> {
-    let input = input
-        .lines()
-        .map(|line| {
-            line.split(':')
-                .last()
-                .expect("should be numbers")
-                .replace(' ', "")
-                .parse::<usize>()
-                .expect("should be a number")
-        })
-        .collect::<Vec<_>>();
+use aoc_common::{
+    parse::{finish, finish_line},
+    stream::for_each_line,
+};
 
-    let time = input[0];
-    let dist = input[1];
+use crate::{errors::AocError, parser};
 
-    let result = match binary_search(0, time / 2, time, dist) {
-        Some(i) => {
-            if time % 2 == 0 {
-                ((time / 2) - i) * 2 - 1
-            } else {
-                ((time / 2) - i) * 2
+#[tracing::instrument(skip(input), fields(input_len = input.len()))]
+pub fn process(input: &str) -> miette::Result<usize, AocError> {
+    solve(&parse(input)?)
+}
+
+/// Like [`process`], but reads the input from `reader` a line at a time with
+/// [`for_each_line`].
+#[tracing::instrument(skip_all)]
+pub fn process_reader(reader: impl BufRead) -> miette::Result<usize, AocError> {
+    let (mut times, mut distances) = (None, None);
+    for_each_line(reader, |number, line| {
+        match number {
+            1 => {
+                times = Some(finish_line(
+                    line,
+                    number,
+                    parser::row("Time:", parser::kerned_number),
+                )?)
+            }
+            2 => {
+                distances = Some(finish_line(
+                    line,
+                    number,
+                    parser::row("Distance:", parser::kerned_number),
+                )?)
+            }
+            _ => {
+                return Err(AocError::parse_error(
+                    line,
+                    line,
+                    format!("expected the end of the input at line {}", number),
+                ))
             }
         }
+        Ok(())
+    })?;
+    let (Some(times), Some(distances)) = (times, distances) else {
+        return Err(AocError::parse_error("", "", "expected a `Distance:` line"));
+    };
+    solve(&(times, distances))
+}
+
+/// `(time, record distance)` of the single race, ignoring the spaces.
+#[tracing::instrument(skip_all)]
+pub fn parse(input: &str) -> miette::Result<(usize, usize), AocError> {
+    finish(input, parser::race)
+}
+
+#[tracing::instrument(skip_all)]
+pub fn solve(&(time, dist): &(usize, usize)) -> miette::Result<usize, AocError> {
+    // holding longer than the longest losing hold up to `time / 2` wins, and
+    // so does the same number of holds after it
+    let result = match binary_search(0, time / 2 + 1, time, dist) {
+        Some(i) => (time - 2 * i).saturating_sub(1),
         None => 0,
     };
 
@@ -33,14 +69,12 @@ pub fn process(input: &str) -> miette::Result<usize, AocError> {
 
 /// Binary search for the largest value in the range that is <= the min_dist.
 fn binary_search(mut lo: usize, mut hi: usize, time: usize, min_dist: usize) -> Option<usize> {
-    assert!(lo < hi, "Range must be non-empty");
-
     let mut result = None;
 
     while lo < hi {
         let mid = (hi + lo) / 2;
-        let dist = mid * (time - mid);
-        if dist <= min_dist {
+        let dist = mid.checked_mul(time - mid);
+        if dist.is_some_and(|dist| dist <= min_dist) {
             lo = mid + 1;
             result = Some(usize::max(result.unwrap_or(usize::MIN), mid));
         } else {
@@ -52,22 +86,26 @@ fn binary_search(mut lo: usize, mut hi: usize, time: usize, min_dist: usize) ->
 
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::*;
 
-    #[test]
-    fn test_process() -> miette::Result<()> {
-        let input = "Time:      7  15   30
-Distance:  9  40  200";
-        assert_eq!(71503, process(input)?);
-        Ok(())
+    /// A race and a record up to just past the furthest distance, that of
+    /// holding the button for half of the race.
+    fn race() -> impl Strategy<Value = (usize, usize)> {
+        (0..2000usize).prop_flat_map(|time| (Just(time), 0..=(time / 2) * (time - time / 2) + 1))
     }
 
-    #[test]
-    fn test_process_input() -> miette::Result<()> {
-        let input = "Time:        59     68     82     74
-Distance:   543   1020   1664   1022";
-        assert_eq!(37286485, process(input)?);
-        Ok(())
+    fn ways_to_win(time: usize, record: usize) -> usize {
+        (0..=time)
+            .filter(|hold| hold * (time - hold) > record)
+            .count()
     }
-}
 
+    proptest! {
+        #[test]
+        fn test_ways_to_win((time, record) in race()) {
+            prop_assert_eq!(ways_to_win(time, record), solve(&(time, record))?);
+        }
+    }
+}
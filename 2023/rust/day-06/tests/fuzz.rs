@@ -0,0 +1,37 @@
+//! Inputs the fuzz targets in `fuzz/` found a panic on, minimized.
+
+use day_06::errors::AocError;
+
+#[test]
+fn race_too_short_to_move() -> miette::Result<()> {
+    for input in ["Time: 0\nDistance: 0", "Time: 1\nDistance: 0"] {
+        assert_eq!(0, day_06::part1::process(input)?);
+        assert_eq!(0, day_06::part2::process(input)?);
+        assert_eq!(0, day_06::part1::process_reader(input.as_bytes())?);
+    }
+    Ok(())
+}
+
+#[test]
+fn record_that_cannot_be_beaten() -> miette::Result<()> {
+    let input = "Time: 4\nDistance: 4";
+    assert_eq!(0, day_06::part1::process(input)?);
+    assert_eq!(0, day_06::part2::process(input)?);
+    Ok(())
+}
+
+#[test]
+fn distance_overflows() -> miette::Result<()> {
+    let input = "Time: 18446744073709551615\nDistance: 4";
+    assert_eq!(18446744073709551614, day_06::part2::process(input)?);
+    Ok(())
+}
+
+#[test]
+fn product_overflows() {
+    let input = "Time: 8589934592 8589934592\nDistance: 0 0";
+    assert!(matches!(
+        day_06::part1::process(input),
+        Err(AocError::Overflow(_))
+    ));
+}
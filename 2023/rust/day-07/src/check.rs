@@ -0,0 +1,43 @@
+use std::collections::HashMap;
+
+use aoc_common::check::Violations;
+
+use crate::{errors::AocError, parser};
+
+/// Every line is a hand of exactly 5 valid cards and a bid, and no hand is
+/// dealt twice, since equal hands could be ranked either way.
+pub fn check(input: &str) -> Vec<AocError> {
+    let mut violations = Violations::new(input);
+    let mut dealt = HashMap::new();
+    for (line, (cards, _)) in violations.lines(parser::bid) {
+        if let Some(first) = dealt.insert(cards, line) {
+            violations.report(
+                line,
+                format!("hand `{}` was already dealt on `{}`", cards, first),
+            );
+        }
+    }
+    violations.into_errors()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check() {
+        assert!(check("32T3K 765\nT55J5 684\n").is_empty());
+        let errors = check("32T3K 765\nT55X5 684\n32T3 28\n32T3K 1\n")
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                "failed to parse input: expected one of `AKQJT98765432` at line 2, column 4",
+                "failed to parse input: expected one of `AKQJT98765432` at line 3, column 5",
+                "invalid puzzle input: hand `32T3K` was already dealt on `32T3K 765`",
+            ],
+            errors
+        );
+    }
+}
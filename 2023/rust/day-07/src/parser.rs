@@ -0,0 +1,62 @@
+use aoc_common::parse::{lines, number, one_of, IResult};
+use nom::{
+    character::complete::space1,
+    combinator::{cut, map},
+    sequence::separated_pair,
+};
+
+/// The labels of the cards, from the highest.
+const CARDS: &str = "AKQJT98765432";
+
+/// Every hand, made by `hand` from its cards and bid as it is parsed.
+pub fn hands<'a, H>(
+    hand: impl Fn(&'a str, u32) -> H,
+) -> impl FnMut(&'a str) -> IResult<'a, Vec<H>> {
+    lines(map(bid, move |(cards, bid)| hand(cards, bid)))
+}
+
+/// `<cards> <bid>`
+pub fn bid(input: &str) -> IResult<'_, (&str, u32)> {
+    separated_pair(cards, space1, cut(number))(input)
+}
+
+/// Exactly 5 cards.
+fn cards(input: &str) -> IResult<'_, &str> {
+    let mut rest = input;
+    for _ in 0..5 {
+        (rest, _) = one_of(CARDS)(rest)?;
+    }
+    Ok((rest, &input[..input.len() - rest.len()]))
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{errors::AocError, parse::finish};
+    use rstest::rstest;
+
+    use super::*;
+
+    #[test]
+    fn test_hands() -> miette::Result<()> {
+        assert_eq!(
+            vec![("32T3K", 765), ("T55J5", 684)],
+            finish("32T3K 765\nT55J5 684\n", hands(|cards, bid| (cards, bid)))?
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    #[case(
+        "32T3K 765\nT55X5 684",
+        "expected one of `AKQJT98765432` at line 2, column 4"
+    )]
+    #[case("32T3 765", "expected one of `AKQJT98765432` at line 1, column 5")]
+    #[case("32T3KK 765", "expected a space at line 1, column 6")]
+    #[case("32T3K bid", "expected a number at line 1, column 7")]
+    fn test_hands_error(#[case] input: &str, #[case] expected: &str) {
+        match finish(input, hands(|cards, bid| (cards, bid))) {
+            Err(AocError::ParseError { message, .. }) => assert_eq!(expected, message),
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+}
@@ -1,4 +1,6 @@
-use crate::errors::AocError;
+use aoc_common::parse::finish;
+
+use crate::{errors::AocError, parser};
 
 #[derive(Debug, Eq, PartialEq, PartialOrd, Ord)]
 enum Card {
@@ -70,7 +72,7 @@ impl HandType {
 }
 
 #[derive(Debug, Eq, PartialEq)]
-struct Hand {
+pub struct Hand {
     cards: Vec<Card>,
     hand_type: HandType,
     bid: u32,
@@ -91,11 +93,9 @@ impl std::fmt::Display for Hand {
 }
 
 impl Hand {
-    fn from_str(line: &str) -> Hand {
-        let mut line = line.split_whitespace();
-        let cards = line
-            .next()
-            .unwrap()
+    /// `cards` are 5 of the labels checked by the parser.
+    fn new(cards: &str, bid: u32) -> Hand {
+        let cards = cards
             .chars()
             .map(|ch| match ch {
                 'A' => Card::Ace,
@@ -103,10 +103,9 @@ impl Hand {
                 'Q' => Card::Queen,
                 'J' => Card::Jack,
                 'T' => Card::Number(10),
-                _ => Card::Number(ch.to_digit(10).expect("Invalid card") as u8),
+                _ => Card::Number(ch as u8 - b'0'),
             })
             .collect::<Vec<_>>();
-        let bid = line.next().unwrap().parse::<u32>().expect("Invalid bid");
         let hand_type = HandType::from_cards(&cards);
         Hand {
             cards,
@@ -131,25 +130,40 @@ impl PartialOrd for Hand {
     }
 }
 
-#[tracing::instrument]
+#[tracing::instrument(skip(input), fields(input_len = input.len()))]
 pub fn process(input: &str) -> miette::Result<u32, AocError> {
-    let mut hands = input.lines().map(Hand::from_str).collect::<Vec<_>>();
+    solve(&parse(input)?)
+}
+
+#[tracing::instrument(skip_all)]
+pub fn parse(input: &str) -> miette::Result<Vec<Hand>, AocError> {
+    finish(input, parser::hands(Hand::new))
+}
+
+#[tracing::instrument(skip_all)]
+pub fn solve(hands: &[Hand]) -> miette::Result<u32, AocError> {
+    let mut hands = hands.iter().collect::<Vec<_>>();
     hands.sort_unstable();
 
-    let result = hands
+    hands
         .iter()
         .enumerate()
         // .inspect(|(r, h)| {
         //     println!("{:4} {} - {}", r + 1, h, h.bid * (*r as u32 + 1));
         // })
-        .map(|(rank, hand)| hand.bid * (rank as u32 + 1))
-        .sum::<u32>();
-
-    Ok(result)
+        .try_fold(0u32, |total, (rank, hand)| {
+            u32::try_from(rank + 1)
+                .ok()
+                .and_then(|rank| hand.bid.checked_mul(rank))
+                .and_then(|winnings| total.checked_add(winnings))
+                .ok_or_else(|| AocError::overflow("the total winnings are more than u32::MAX"))
+        })
 }
 
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::*;
 
     #[test]
@@ -190,15 +204,22 @@ mod tests {
         );
     }
 
-    #[test]
-    fn test_process() -> miette::Result<()> {
-        let input = "32T3K 765
-T55J5 684
-KK677 28
-KTJJT 220
-QQQJA 483";
-        assert_eq!(6440, process(input)?);
-        Ok(())
+    /// 5 cards and the same cards in another order.
+    fn shuffled_hands() -> impl Strategy<Value = (String, String)> {
+        let card = prop::sample::select(b"AKQJT98765432".to_vec());
+        prop::collection::vec(card, 5).prop_flat_map(|cards| {
+            let hand = String::from_utf8(cards.clone()).unwrap();
+            let shuffled = Just(cards)
+                .prop_shuffle()
+                .prop_map(|cards| String::from_utf8(cards).unwrap());
+            (Just(hand), shuffled)
+        })
     }
-}
 
+    proptest! {
+        #[test]
+        fn test_hand_type_ignores_the_order((hand, shuffled) in shuffled_hands()) {
+            prop_assert_eq!(Hand::new(&hand, 0).hand_type, Hand::new(&shuffled, 0).hand_type);
+        }
+    }
+}
@@ -1,4 +1,6 @@
-use crate::errors::AocError;
+use aoc_common::parse::finish;
+
+use crate::{errors::AocError, parser};
 
 #[derive(Debug, Eq, PartialEq, PartialOrd, Ord)]
 enum Card {
@@ -83,7 +85,7 @@ impl HandType {
 }
 
 #[derive(Debug, Eq, PartialEq)]
-struct Hand {
+pub struct Hand {
     cards: Vec<Card>,
     hand_type: HandType,
     bid: u32,
@@ -104,11 +106,9 @@ impl std::fmt::Display for Hand {
 }
 
 impl Hand {
-    fn from_str(line: &str) -> Hand {
-        let mut line = line.split_whitespace();
-        let cards = line
-            .next()
-            .unwrap()
+    /// `cards` are 5 of the labels checked by the parser.
+    fn new(cards: &str, bid: u32) -> Hand {
+        let cards = cards
             .chars()
             .map(|ch| match ch {
                 'A' => Card::Ace,
@@ -116,10 +116,9 @@ impl Hand {
                 'Q' => Card::Queen,
                 'J' => Card::Joker,
                 'T' => Card::Number(10),
-                _ => Card::Number(ch.to_digit(10).expect("Invalid card") as u8),
+                _ => Card::Number(ch as u8 - b'0'),
             })
             .collect::<Vec<_>>();
-        let bid = line.next().unwrap().parse::<u32>().expect("Invalid bid");
         let hand_type = HandType::from_cards(&cards);
         Hand {
             cards,
@@ -144,9 +143,19 @@ impl PartialOrd for Hand {
     }
 }
 
-#[tracing::instrument]
+#[tracing::instrument(skip(input), fields(input_len = input.len()))]
 pub fn process(input: &str) -> miette::Result<u64, AocError> {
-    let mut hands = input.lines().map(Hand::from_str).collect::<Vec<_>>();
+    solve(&parse(input)?)
+}
+
+#[tracing::instrument(skip_all)]
+pub fn parse(input: &str) -> miette::Result<Vec<Hand>, AocError> {
+    finish(input, parser::hands(Hand::new))
+}
+
+#[tracing::instrument(skip_all)]
+pub fn solve(hands: &[Hand]) -> miette::Result<u64, AocError> {
+    let mut hands = hands.iter().collect::<Vec<_>>();
     hands.sort_unstable();
 
     let result = hands
@@ -155,7 +164,7 @@ pub fn process(input: &str) -> miette::Result<u64, AocError> {
         // .inspect(|(r, h)| {
         //     println!("{:4} {} - {}", r + 1, h, h.bid * (*r as u32 + 1));
         // })
-        .map(|(rank, hand)| (hand.bid * (rank as u32 + 1)) as u64)
+        .map(|(rank, hand)| hand.bid as u64 * (rank as u64 + 1))
         .sum::<u64>();
 
     Ok(result)
@@ -163,6 +172,8 @@ pub fn process(input: &str) -> miette::Result<u64, AocError> {
 
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::*;
 
     #[test]
@@ -203,31 +214,35 @@ mod tests {
         );
     }
 
-    #[test]
-    fn test_process_jokers() -> miette::Result<()> {
-        let input = "2JJJJ 6
-JJJ2J 3
-JJ2JJ 4
-JJJJJ 1
-J2JJJ 5
-JJJJ2 2";
-
-        assert_eq!(36 + 25 + 16 + 9 + 4 + 1, process(input)?);
-        Ok(())
+    /// 5 cards and the same cards in another order.
+    fn shuffled_hands() -> impl Strategy<Value = (String, String)> {
+        let card = prop::sample::select(b"AKQJT98765432".to_vec());
+        prop::collection::vec(card, 5).prop_flat_map(|cards| {
+            let hand = String::from_utf8(cards.clone()).unwrap();
+            let shuffled = Just(cards)
+                .prop_shuffle()
+                .prop_map(|cards| String::from_utf8(cards).unwrap());
+            (Just(hand), shuffled)
+        })
     }
 
-    #[test]
-    fn test_process() -> miette::Result<()> {
-        let input = "32T3K 765
-T55J5 684
-KK677 28
-KTJJT 220
-QQQJA 483";
-        assert_eq!(5905, process(input)?);
-        Ok(())
+    proptest! {
+        #[test]
+        fn test_hand_type_ignores_the_order((hand, shuffled) in shuffled_hands()) {
+            prop_assert_eq!(Hand::new(&hand, 0).hand_type, Hand::new(&shuffled, 0).hand_type);
+        }
+
+        #[test]
+        fn test_jokers_copy_the_best_card((hand, _) in shuffled_hands()) {
+            let best = "AKQT98765432"
+                .chars()
+                .map(|card| Hand::new(&hand.replace('J', &card.to_string()), 0).hand_type)
+                .max();
+            prop_assert_eq!(best, Some(Hand::new(&hand, 0).hand_type));
+        }
     }
 }
 
 // 248426052 is too high
 // 248494576 if JJJJJ is HighCard
-// 247899149 handling jokers
+// 247899149 handling joker
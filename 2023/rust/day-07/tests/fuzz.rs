@@ -0,0 +1,14 @@
+//! Inputs the fuzz targets in `fuzz/` found a panic on, minimized.
+
+use day_07::errors::AocError;
+
+#[test]
+fn winnings_overflow() -> miette::Result<()> {
+    let input = "T55J5 6\nT55J5 2876568684";
+    assert!(matches!(
+        day_07::part1::process(input),
+        Err(AocError::Overflow(_))
+    ));
+    assert_eq!(5753137374, day_07::part2::process(input)?);
+    Ok(())
+}
@@ -0,0 +1,53 @@
+use std::collections::HashSet;
+
+use aoc_common::check::Violations;
+
+use crate::{errors::AocError, parser};
+
+/// Every node the network leads to is defined, and there is a node to
+/// start from: `AAA`, or nodes ending in `A` for the ghosts of part 2.
+pub fn check(input: &str) -> Vec<AocError> {
+    let mut violations = Violations::new(input);
+    let Some((_, network)) = violations.parse(parser::documents) else {
+        return violations.into_errors();
+    };
+
+    let mut undefined = network
+        .values()
+        .flat_map(|&(left, right)| [left, right])
+        .filter(|node| !network.contains_key(node))
+        .collect::<Vec<_>>();
+    // in the order they appear in, each one once
+    undefined.sort_by_key(|node| node.as_ptr());
+    let mut reported = HashSet::new();
+    for node in undefined {
+        if reported.insert(node) {
+            violations.report(node, format!("node `{}` is not defined", node));
+        }
+    }
+    if !network.keys().any(|node| node.ends_with('A')) {
+        violations.report(input, "there is no node ending in `A` to start from");
+    }
+    violations.into_errors()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check() {
+        assert!(check("LR\n\nAAA = (BBB, ZZZ)\nBBB = (AAA, ZZZ)\nZZZ = (ZZZ, ZZZ)\n").is_empty());
+        let errors = check("LR\n\nBBB = (CCC, CCC)\nZZZ = (ZZZ, ZZZ)\n")
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                "invalid puzzle input: node `CCC` is not defined",
+                "invalid puzzle input: there is no node ending in `A` to start from",
+            ],
+            errors
+        );
+    }
+}
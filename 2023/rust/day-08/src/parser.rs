@@ -0,0 +1,74 @@
+use std::collections::HashMap;
+
+use aoc_common::parse::{blank_line, fold_lines, row, tag, IResult};
+use nom::{
+    character::complete::alphanumeric1,
+    combinator::cut,
+    sequence::{delimited, pair, separated_pair, terminated},
+};
+
+/// A node and its left and right successors.
+pub type Node<'a> = (&'a str, (&'a str, &'a str));
+
+/// The left and right successors of every node.
+pub type Network<'a> = HashMap<&'a str, (&'a str, &'a str)>;
+
+/// The `L`/`R` instructions and, after a blank line, the nodes.
+pub fn documents(input: &str) -> IResult<'_, (&str, Network<'_>)> {
+    separated_pair(row("LR"), cut(blank_line), network)(input)
+}
+
+/// Every node, keyed by name, collected as they are parsed.
+fn network(input: &str) -> IResult<'_, Network<'_>> {
+    fold_lines(node, HashMap::new, |mut network, (name, next)| {
+        network.insert(name, next);
+        network
+    })(input)
+}
+
+/// `<node> = (<left>, <right>)`
+fn node(input: &str) -> IResult<'_, Node<'_>> {
+    pair(
+        terminated(alphanumeric1, tag(" = ")),
+        delimited(
+            tag("("),
+            separated_pair(alphanumeric1, tag(", "), alphanumeric1),
+            tag(")"),
+        ),
+    )(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{errors::AocError, parse::finish};
+    use rstest::rstest;
+
+    use super::*;
+
+    #[test]
+    fn test_documents() -> miette::Result<()> {
+        assert_eq!(
+            (
+                "LR",
+                HashMap::from([("11A", ("11B", "XXX")), ("11B", ("XXX", "11Z"))])
+            ),
+            finish("LR\n\n11A = (11B, XXX)\n11B = (XXX, 11Z)\n", documents)?
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("LRX\n\nAAA = (BBB, BBB)", "expected one of `LR` at line 1, column 3")]
+    #[case("LR\nAAA = (BBB, BBB)", "expected a line break at line 2, column 1")]
+    #[case("LR\n\nAAA = (BBB BBB)", "expected `, ` at line 3, column 11")]
+    #[case(
+        "LR\n\nAAA = (BBB, BBB)\nBBB -> (AAA, AAA)",
+        "expected ` = ` at line 4, column 4"
+    )]
+    fn test_documents_error(#[case] input: &str, #[case] expected: &str) {
+        match finish(input, documents) {
+            Err(AocError::ParseError { message, .. }) => assert_eq!(expected, message),
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+}
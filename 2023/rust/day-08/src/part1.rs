@@ -1,18 +1,31 @@
-use std::collections::HashMap;
+use aoc_common::parse::finish;
 
-use crate::errors::AocError;
+use crate::{errors::AocError, parser};
 
-#[tracing::instrument]
+#[tracing::instrument(skip(input), fields(input_len = input.len()))]
 pub fn process(input: &str) -> miette::Result<usize, AocError> {
-    let (instructions, nodes) = parse_input(input);
+    solve(&parse(input)?)
+}
 
+#[tracing::instrument(skip_all)]
+pub fn solve((instructions, nodes): &(Vec<char>, Network<'_>)) -> miette::Result<usize, AocError> {
+    // past a step for every node and instruction, a state repeated and the
+    // walk goes round in a loop without `ZZZ`
+    let limit = nodes.len().saturating_mul(instructions.len());
     let mut step = 0;
     let mut current = "AAA";
     while current != "ZZZ" {
+        if step == limit {
+            return Err(AocError::no_solution(
+                "",
+                "",
+                "`ZZZ` is never reached from `AAA`",
+            ));
+        }
         current = match instructions[step % instructions.len()] {
             'L' => nodes[current].0,
             'R' => nodes[current].1,
-            _ => unreachable!(),
+            _ => unreachable!("instructions are validated while parsing"),
         };
         step += 1;
     }
@@ -20,52 +33,25 @@ pub fn process(input: &str) -> miette::Result<usize, AocError> {
     Ok(step)
 }
 
-fn parse_input(input: &str) -> (Vec<char>, HashMap<&str, (&str, &str)>) {
-    let (instructions, nodes) = input.split_once("\n\n").unwrap();
-    let instructions = instructions.chars().collect::<Vec<_>>();
-    let nodes = nodes
-        .lines()
-        .map(parse_node)
-        .collect::<HashMap<&str, (&str, &str)>>();
-    (instructions, nodes)
-}
-
-fn parse_node(line: &str) -> (&str, (&str, &str)) {
-    let (node, direction) = line.split_once(" = ").unwrap();
-    let direction = direction
-        .trim_matches(|c| c == '(' || c == ')')
-        .split_once(", ")
-        .unwrap();
-    (node, direction)
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_process_1() -> miette::Result<()> {
-        let input = "RL
+pub use crate::parser::Network;
 
-AAA = (BBB, CCC)
-BBB = (DDD, EEE)
-CCC = (ZZZ, GGG)
-DDD = (DDD, DDD)
-EEE = (EEE, EEE)
-GGG = (GGG, GGG)
-ZZZ = (ZZZ, ZZZ)";
-        assert_eq!(2, process(input)?);
-        Ok(())
+#[tracing::instrument(skip_all)]
+pub fn parse(input: &str) -> miette::Result<(Vec<char>, Network<'_>), AocError> {
+    let (instructions, nodes) = finish(input, parser::documents)?;
+    let instructions = instructions.chars().collect::<Vec<_>>();
+    if !nodes.contains_key("AAA") {
+        return Err(AocError::invalid_input(
+            input,
+            input,
+            "there is no node `AAA` to start from",
+        ));
     }
-
-    #[test]
-    fn test_process_2() -> miette::Result<()> {
-        let input = "LLR
-
-AAA = (BBB, BBB)
-BBB = (AAA, ZZZ)
-ZZZ = (ZZZ, ZZZ)";
-        assert_eq!(6, process(input)?);
-        Ok(())
+    if let Some(node) = nodes
+        .values()
+        .flat_map(|&(left, right)| [left, right])
+        .find(|node| !nodes.contains_key(node))
+    {
+        return Err(AocError::invalid_input(input, node, "node is not defined"));
     }
+    Ok((instructions, nodes))
 }
@@ -1,20 +1,22 @@
-use std::collections::HashMap;
+use aoc_common::parse::finish;
 
-use crate::errors::AocError;
+use crate::{errors::AocError, parser};
 
-#[tracing::instrument]
+#[tracing::instrument(skip(input), fields(input_len = input.len()))]
 pub fn process(input: &str) -> miette::Result<usize, AocError> {
-    optimized(input)
+    solve(&parse(input)?)
 }
 
 /// # Optimized solution using LCM
 ///
 /// The path for each starting node will result in a cycle.
 /// The length of the cycle is the least common multiple of the lengths of the individual paths.
-fn optimized(input: &str) -> miette::Result<usize, AocError> {
-    let (instructions, nodes) = parse_input(input);
-
-    let result = nodes
+#[tracing::instrument(skip_all)]
+pub fn solve((instructions, nodes): &(Vec<char>, Network<'_>)) -> miette::Result<usize, AocError> {
+    // past a step for every node and instruction, a state repeated and the
+    // walk goes round in a loop without a node ending in `Z`
+    let limit = nodes.len().saturating_mul(instructions.len());
+    nodes
         .keys()
         .filter(|k| k.ends_with('A'))
         .copied()
@@ -22,23 +24,31 @@ fn optimized(input: &str) -> miette::Result<usize, AocError> {
             let mut current = node;
             let mut step = 0;
             while !current.ends_with('Z') {
+                if step == limit {
+                    return Err(AocError::no_solution(
+                        "",
+                        "",
+                        format!("no node ending in `Z` is reached from `{}`", node),
+                    ));
+                }
                 current = match instructions[step % instructions.len()] {
                     'L' => nodes[current].0,
                     'R' => nodes[current].1,
-                    _ => unreachable!(),
+                    _ => unreachable!("instructions are validated while parsing"),
                 };
                 step += 1;
             }
-            step
+            Ok(step)
+        })
+        .try_fold(1, |result, step| {
+            lcm(result, step?)
+                .ok_or_else(|| AocError::overflow("the steps are more than usize::MAX"))
         })
-        .fold(1, lcm);
-
-    Ok(result)
 }
 
-/// least common multiple
-fn lcm(a: usize, b: usize) -> usize {
-    a * b / gcd(a, b)
+/// least common multiple, `None` if it overflows
+fn lcm(a: usize, b: usize) -> Option<usize> {
+    (a / gcd(a, b)).checked_mul(b)
 }
 
 /// greatest common divisor
@@ -52,7 +62,7 @@ fn gcd(mut a: usize, mut b: usize) -> usize {
 #[allow(dead_code)]
 /// Brute force solution - Takes too long to run
 fn brute_force(input: &str) -> miette::Result<usize, AocError> {
-    let (instructions, nodes) = parse_input(input);
+    let (instructions, nodes) = parse(input)?;
 
     let mut step = 0;
     let mut current = nodes
@@ -75,27 +85,26 @@ fn brute_force(input: &str) -> miette::Result<usize, AocError> {
     Ok(step)
 }
 
-fn parse_input(input: &str) -> (Vec<char>, HashMap<&str, (&str, &str)>) {
-    let (instructions, nodes) = input.split_once("\n\n").unwrap();
-    let instructions = instructions.chars().collect::<Vec<_>>();
-    let nodes = nodes
-        .lines()
-        .map(parse_node)
-        .collect::<HashMap<&str, (&str, &str)>>();
-    (instructions, nodes)
-}
+pub use crate::parser::Network;
 
-fn parse_node(line: &str) -> (&str, (&str, &str)) {
-    let (node, direction) = line.split_once(" = ").unwrap();
-    let direction = direction
-        .trim_matches(|c| c == '(' || c == ')')
-        .split_once(", ")
-        .unwrap();
-    (node, direction)
+#[tracing::instrument(skip_all)]
+pub fn parse(input: &str) -> miette::Result<(Vec<char>, Network<'_>), AocError> {
+    let (instructions, nodes) = finish(input, parser::documents)?;
+    let instructions = instructions.chars().collect::<Vec<_>>();
+    if let Some(node) = nodes
+        .values()
+        .flat_map(|&(left, right)| [left, right])
+        .find(|node| !nodes.contains_key(node))
+    {
+        return Err(AocError::invalid_input(input, node, "node is not defined"));
+    }
+    Ok((instructions, nodes))
 }
 
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::*;
 
     #[test]
@@ -126,7 +135,50 @@ XXX = (XXX, XXX)";
 22C = (22Z, 22Z)
 22Z = (22B, 22B)
 XXX = (XXX, XXX)";
-        assert_eq!(6, optimized(input)?);
+        assert_eq!(6, solve(&parse(input)?)?);
         Ok(())
     }
+
+    /// Ghosts `<i>A` that walk a cycle of `lengths[i]` nodes through `<i>Z`.
+    fn ghosts(lengths: &[usize]) -> String {
+        let mut input = String::from("L\n");
+        for (ghost, &length) in lengths.iter().enumerate() {
+            let name = |step: usize| match step {
+                0 => format!("{}A", ghost),
+                step if step == length => format!("{}Z", ghost),
+                step => format!("{}N{}", ghost, step),
+            };
+            for step in 0..=length {
+                let next = name(step % length + 1);
+                input.push_str(&format!("\n{} = ({}, {})", name(step), next, next));
+            }
+        }
+        input
+    }
+
+    proptest! {
+        #[test]
+        fn test_gcd_divides_both(a in 1..10_000usize, b in 1..10_000usize) {
+            let gcd = gcd(a, b);
+            prop_assert_eq!(a % gcd, 0);
+            prop_assert_eq!(b % gcd, 0);
+            prop_assert_eq!(lcm(a, b), Some(a * b / gcd));
+        }
+
+        #[test]
+        fn test_lcm_is_the_smallest_common_multiple(a in 1..100usize, b in 1..100usize) {
+            let lcm = lcm(a, b).unwrap();
+            prop_assert_eq!(lcm % a, 0);
+            prop_assert_eq!(lcm % b, 0);
+            prop_assert!((1..lcm).all(|n| n % a != 0 || n % b != 0));
+        }
+
+        #[test]
+        fn test_ghosts_meet_at_the_lcm(lengths in prop::collection::vec(1..12usize, 1..4)) {
+            let input = ghosts(&lengths);
+            let expected = lengths.iter().fold(1, |result, &length| lcm(result, length).unwrap());
+            prop_assert_eq!(expected, process(&input)?);
+            prop_assert_eq!(expected, brute_force(&input)?);
+        }
+    }
 }
@@ -0,0 +1,21 @@
+//! Inputs the fuzz targets in `fuzz/` found a hang on, minimized.
+
+use day_08::errors::AocError;
+
+#[test]
+fn zzz_never_reached() {
+    let input = "R\n\nAAA = (DDD, EEE)\nDDD = (DDD, DDD)\nEEE = (EEE, EEE)";
+    assert!(matches!(
+        day_08::part1::process(input),
+        Err(AocError::NoSolution { .. })
+    ));
+}
+
+#[test]
+fn no_node_ending_in_z_reached() {
+    let input = "LR\n\n11A = (XXX, XXX)\n11Z = (11Z, 11Z)\nXXX = (XXX, XXX)";
+    assert!(matches!(
+        day_08::part2::process(input),
+        Err(AocError::NoSolution { .. })
+    ));
+}
@@ -1,22 +1,16 @@
-use day_09::*;
+#[global_allocator]
+static ALLOC: divan::AllocProfiler = divan::AllocProfiler::system();
 
 fn main() {
     // Run registered benchmarks.
     divan::main();
 }
 
-#[divan::bench]
-fn part1() {
-    part1::process(divan::black_box(include_str!(
-        "../input.txt",
-    )))
-    .unwrap();
-}
+aoc_common::bench_stages!(day_09::part1);
+#[cfg(feature = "parallel")]
+aoc_common::bench_stages!(day_09::part1_parallel);
+aoc_common::bench_stages!(day_09::part2);
+#[cfg(feature = "parallel")]
+aoc_common::bench_stages!(day_09::part2_parallel);
 
-#[divan::bench]
-fn part2() {
-    part2::process(divan::black_box(include_str!(
-        "../input.txt",
-    )))
-    .unwrap();
-}
+aoc_common::bench_throughput!(day_09::part1, day_09::part2);
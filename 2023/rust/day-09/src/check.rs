@@ -0,0 +1,42 @@
+use aoc_common::check::Violations;
+use aoc_common::parse::numbers;
+
+use crate::errors::AocError;
+
+/// Every line is a history whose differences reach all zeros before they
+/// run out, so that it can be extrapolated.
+pub fn check(input: &str) -> Vec<AocError> {
+    let mut violations = Violations::new(input);
+    for (line, history) in violations.lines(numbers::<i32>) {
+        let mut history = history.into_iter().map(i64::from).collect::<Vec<_>>();
+        while history.len() > 1 && !history.iter().all(|&n| n == 0) {
+            history = history.windows(2).map(|pair| pair[1] - pair[0]).collect();
+        }
+        if !history.iter().all(|&n| n == 0) {
+            violations.report(line, "the differences never reach all zeros");
+        }
+    }
+    violations.into_errors()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check() {
+        assert!(check("0 3 6 9 12 15\n10 13 16 21 30 45\n").is_empty());
+        let errors = check("0 3 6 x\n1 5 4\n7\n")
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                "failed to parse input: expected the end of the line at line 1, column 6",
+                "invalid puzzle input: the differences never reach all zeros",
+                "invalid puzzle input: the differences never reach all zeros",
+            ],
+            errors
+        );
+    }
+}
@@ -0,0 +1,12 @@
+use std::path::Path;
+
+use crate::errors::AocError;
+
+/// The puzzle input bundled with this crate.
+pub const BUNDLED_INPUT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/input.txt");
+
+/// Reads the puzzle input from `path` (`-` for stdin), falling back to
+/// `$AOC_INPUT` and then to the bundled `input.txt`.
+pub fn read_input(path: Option<String>) -> miette::Result<String, AocError> {
+    aoc_common::input::read_input(path, Path::new(BUNDLED_INPUT))
+}
@@ -1,4 +1,47 @@
-pub mod errors;
+use aoc_common::{
+    parse::finish,
+    solution::{Answer, Solution},
+};
+use errors::AocError;
+
+pub use aoc_common::errors;
+pub mod check;
+pub mod input;
+pub mod parser;
 
 pub mod part1;
+#[cfg(feature = "parallel")]
+pub mod part1_parallel;
 pub mod part2;
+#[cfg(feature = "parallel")]
+pub mod part2_parallel;
+
+pub struct Day09;
+
+/// The histories of the report, which both parts read.
+#[tracing::instrument(skip_all)]
+pub fn parse(input: &str) -> miette::Result<Vec<Vec<i32>>, AocError> {
+    finish(input, parser::report)
+}
+
+impl Solution for Day09 {
+    const DAY: u8 = 9;
+    type Input<'a> = Vec<Vec<i32>>;
+    type Params = ();
+
+    fn parse(input: &str) -> miette::Result<Self::Input<'_>, AocError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _: &()) -> miette::Result<Answer, AocError> {
+        part1::solve(input).map(Answer::from)
+    }
+
+    fn part2(input: &Self::Input<'_>, _: &()) -> miette::Result<Answer, AocError> {
+        part2::solve(input).map(Answer::from)
+    }
+
+    fn check(input: &str) -> Vec<AocError> {
+        check::check(input)
+    }
+}
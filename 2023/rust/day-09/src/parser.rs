@@ -0,0 +1,37 @@
+use aoc_common::parse::{lines, numbers, IResult};
+
+/// The report: a history of values per line.
+pub fn report(input: &str) -> IResult<'_, Vec<Vec<i32>>> {
+    lines(numbers)(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{errors::AocError, parse::finish};
+    use rstest::rstest;
+
+    use super::*;
+
+    #[test]
+    fn test_report() -> miette::Result<()> {
+        assert_eq!(
+            vec![vec![0, 3, 6], vec![10, -3, 7]],
+            finish("0 3 6\n10 -3 7\n", report)?
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("0 3 6\n1 3 x", "expected the end of the line at line 2, column 4")]
+    #[case("0 3 6\n\n1 3 6", "expected the end of the input at line 1, column 6")]
+    #[case(
+        "0 3 99999999999",
+        "expected a number that fits in i32 at line 1, column 5"
+    )]
+    fn test_report_error(#[case] input: &str, #[case] expected: &str) {
+        match finish(input, report) {
+            Err(AocError::ParseError { message, .. }) => assert_eq!(expected, message),
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,27 @@
+use aoc_common::{parallel::finish_lines, parse::numbers};
+use rayon::prelude::*;
+
+use crate::{
+    errors::AocError,
+    part1::{add_prediction, predict_history},
+};
+
+#[tracing::instrument(skip(input), fields(input_len = input.len()))]
+pub fn process(input: &str) -> miette::Result<i32, AocError> {
+    solve(&parse(input)?)
+}
+
+#[tracing::instrument(skip_all)]
+pub fn parse(input: &str) -> miette::Result<Vec<Vec<i32>>, AocError> {
+    finish_lines(input, numbers)
+}
+
+/// [`crate::part1::solve`] with the histories split across the rayon thread
+/// pool.
+#[tracing::instrument(skip_all)]
+pub fn solve(histories: &[Vec<i32>]) -> miette::Result<i32, AocError> {
+    histories
+        .par_iter()
+        .map_init(Vec::new, |buf, history| predict_history(history, buf))
+        .try_reduce(|| 0, add_prediction)
+}
@@ -1,55 +1,82 @@
-use crate::errors::AocError;
+use std::io::BufRead;
 
-#[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<i32, AocError> {
-    let input = parse_input(input);
+use aoc_common::{
+    parse::{finish_line, numbers},
+    stream::for_each_line,
+};
+
+use crate::{errors::AocError, part1::add_prediction};
+
+pub use crate::parse;
 
-    let result = input.into_iter().map(predict_history).sum::<i32>();
+#[tracing::instrument(skip(input), fields(input_len = input.len()))]
+pub fn process(input: &str) -> miette::Result<i32, AocError> {
+    solve(&parse(input)?)
+}
 
+/// Like [`process`], but reads the input from `reader` a line at a time with
+/// [`for_each_line`].
+#[tracing::instrument(skip_all)]
+pub fn process_reader(reader: impl BufRead) -> miette::Result<i32, AocError> {
+    let (mut result, mut buf) = (0, Vec::new());
+    for_each_line(reader, |number, line| {
+        let history = finish_line(line, number, numbers)?;
+        result = add_prediction(result, predict_history(&history, &mut buf)?)?;
+        Ok(())
+    })?;
     Ok(result)
 }
 
-fn parse_input(input: &str) -> Vec<Vec<i32>> {
-    input
-        .lines()
-        .map(|line| {
-            line.split_whitespace()
-                .map(|n| n.parse::<i32>().expect("should be a number"))
-                .collect()
-        })
-        .collect()
+#[tracing::instrument(skip_all)]
+pub fn solve(histories: &[Vec<i32>]) -> miette::Result<i32, AocError> {
+    let mut buf = Vec::new();
+    histories.iter().try_fold(0, |sum, history| {
+        add_prediction(sum, predict_history(history, &mut buf)?)
+    })
 }
 
-fn predict_history(mut history: Vec<i32>) -> i32 {
-    let mut evidences = vec![history.first().copied().unwrap()];
-    while !history.iter().all(|&n| n == 0) {
-        history = history
-            .windows(2)
-            .map(|window| window[1] - window[0])
-            .collect::<Vec<_>>();
-        evidences.push(history.first().copied().unwrap());
+/// The value before `history`. A single value left in the differences is
+/// taken as constant, so that every history has a prediction. The
+/// differences are taken in `buf`, which is reused from one history to the
+/// next.
+pub(crate) fn predict_history(history: &[i32], buf: &mut Vec<i32>) -> Result<i32, AocError> {
+    let overflow = || AocError::overflow("a difference or prediction is out of the range of i32");
+    buf.clear();
+    buf.extend_from_slice(history);
+    // the differences take the place of the values they are taken from, the
+    // first value of every row stays in front of them
+    let mut start = 0;
+    while buf.len() - start > 1 && !buf[start..].iter().all(|&n| n == 0) {
+        for i in (start + 1..buf.len()).rev() {
+            buf[i] = buf[i].checked_sub(buf[i - 1]).ok_or_else(overflow)?;
+        }
+        start += 1;
     }
 
-    let result = evidences
-        .into_iter()
+    buf[..=start]
+        .iter()
         .rev()
-        .skip(1)
-        .reduce(|acc, n| n - acc)
-        .unwrap();
-
-    result
+        .try_fold(0i32, |acc, &n| n.checked_sub(acc))
+        .ok_or_else(overflow)
 }
 
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::*;
 
-    #[test]
-    fn test_process() -> miette::Result<()> {
-        let input = "0 3 6 9 12 15
-1 3 6 10 15 21
-10 13 16 21 30 45";
-        assert_eq!(2, process(input)?);
-        Ok(())
+    proptest! {
+        #[test]
+        fn test_predict_history_of_a_polynomial(
+            coefficients in prop::collection::vec(-5..=5i32, 1..=5),
+            extra in 1..15usize,
+        ) {
+            let value = |x: i32| coefficients.iter().rev().fold(0, |value, c| value * x + c);
+            // a polynomial of degree d needs d + 2 values for its differences to reach 0
+            let len = coefficients.len() + extra;
+            let history = (0..len as i32).map(value).collect::<Vec<_>>();
+            prop_assert_eq!(value(-1), predict_history(&history, &mut Vec::new())?);
+        }
     }
 }
@@ -0,0 +1,20 @@
+use rayon::prelude::*;
+
+use crate::{errors::AocError, part1::add_prediction, part2::predict_history};
+
+pub use crate::part1_parallel::parse;
+
+#[tracing::instrument(skip(input), fields(input_len = input.len()))]
+pub fn process(input: &str) -> miette::Result<i32, AocError> {
+    solve(&parse(input)?)
+}
+
+/// [`crate::part2::solve`] with the histories split across the rayon thread
+/// pool.
+#[tracing::instrument(skip_all)]
+pub fn solve(histories: &[Vec<i32>]) -> miette::Result<i32, AocError> {
+    histories
+        .par_iter()
+        .map_init(Vec::new, |buf, history| predict_history(history, buf))
+        .try_reduce(|| 0, add_prediction)
+}
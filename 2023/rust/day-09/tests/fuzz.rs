@@ -0,0 +1,29 @@
+//! Inputs the fuzz targets in `fuzz/` found a panic on, minimized.
+
+use day_09::errors::AocError;
+
+#[test]
+fn differences_never_reach_zero() -> miette::Result<()> {
+    assert_eq!(1, day_09::part1::process("1")?);
+    assert_eq!(1, day_09::part2::process("1")?);
+
+    // the quadratic through 10, -3 and 7
+    let input = "0 3 6\n10 -3 7";
+    assert_eq!(9 + 40, day_09::part1::process(input)?);
+    assert_eq!(-3 + 46, day_09::part2::process(input)?);
+    assert_eq!(9 + 40, day_09::part1::process_reader(input.as_bytes())?);
+    Ok(())
+}
+
+#[test]
+fn difference_overflows() {
+    let input = "-2147483648 2147483647";
+    assert!(matches!(
+        day_09::part1::process(input),
+        Err(AocError::Overflow(_))
+    ));
+    assert!(matches!(
+        day_09::part2::process(input),
+        Err(AocError::Overflow(_))
+    ));
+}
@@ -0,0 +1,65 @@
+use aoc_common::check::Violations;
+
+use crate::{errors::AocError, parse, parser, part1};
+
+/// The sketch is a rectangle of tiles with exactly one start `S`, from which
+/// the pipes form a closed loop.
+pub fn check(input: &str) -> Vec<AocError> {
+    let mut violations = Violations::new(input);
+    let rows = input.lines().collect::<Vec<_>>();
+    violations.grid(&rows, parser::TILES);
+
+    let starts = input.match_indices('S').collect::<Vec<_>>();
+    match &starts[..] {
+        [] => violations.report(input, "there is no start pipe `S`"),
+        [_] => {}
+        [_, others @ ..] => {
+            for &(i, start) in others {
+                violations.report(
+                    &input[i..i + start.len()],
+                    "there is a second start pipe `S`",
+                );
+            }
+        }
+    }
+
+    // the loop can only be followed on an otherwise valid sketch
+    if violations.is_empty() {
+        if let Err(err) = parse(input).and_then(|sketch| part1::solve(&sketch)) {
+            violations.push(err);
+        }
+    }
+    violations.into_errors()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check() {
+        assert!(check(".....\n.S-7.\n.|.|.\n.L-J.\n.....\n").is_empty());
+        let errors = check(".....\n.S-7.\n.|.|.\n.L-JS\n.*..\n")
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                "invalid puzzle input: `*` is not one of `|-LJ7FS.`",
+                "invalid puzzle input: row is 4 wide, the first row is 5 wide",
+                "invalid puzzle input: there is a second start pipe `S`",
+            ],
+            errors
+        );
+    }
+
+    #[test]
+    fn test_check_open_loop() {
+        let errors = check(".....\n.S-7.\n.|.|.\n.L-..\n.....\n");
+        assert_eq!(1, errors.len());
+        assert_eq!(
+            "invalid puzzle input: the pipes do not form a loop",
+            errors[0].to_string()
+        );
+    }
+}
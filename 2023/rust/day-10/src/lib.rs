@@ -1,4 +1,72 @@
-pub mod errors;
+use aoc_common::{
+    check::rectangle,
+    parse::finish,
+    solution::{Answer, Solution},
+};
+use errors::AocError;
+use part1::Pipe;
+
+pub use aoc_common::errors;
+pub mod check;
+pub mod input;
+pub mod parser;
 
 pub mod part1;
 pub mod part2;
+
+pub struct Day10;
+
+/// The grid of pipes, along with the raw sketch for diagnostics.
+#[derive(Debug)]
+pub struct Sketch<'a> {
+    pub(crate) input: &'a str,
+    pub(crate) grid: Vec<Vec<Pipe>>,
+}
+
+/// The sketch, which both parts follow the loop of.
+#[tracing::instrument(skip_all)]
+pub fn parse(input: &str) -> miette::Result<Sketch<'_>, AocError> {
+    let rows = finish(input, parser::sketch)?;
+    rectangle(input, &rows)?;
+    // a second `S` connects to the first whichever way it goes
+    if let Some((i, start)) = input.match_indices('S').nth(1) {
+        return Err(AocError::invalid_input(
+            input,
+            &input[i..i + start.len()],
+            "there is a second start pipe `S`",
+        ));
+    }
+    let grid = rows
+        .into_iter()
+        .enumerate()
+        .map(|(r, line)| {
+            line.chars()
+                .enumerate()
+                .map(|(c, ch)| Pipe::new(ch, r, c))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    Ok(Sketch { input, grid })
+}
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+    type Input<'a> = Sketch<'a>;
+    type Params = ();
+
+    fn parse(input: &str) -> miette::Result<Self::Input<'_>, AocError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _: &()) -> miette::Result<Answer, AocError> {
+        part1::solve(input).map(Answer::from)
+    }
+
+    fn part2(input: &Self::Input<'_>, _: &()) -> miette::Result<Answer, AocError> {
+        part2::solve(input).map(Answer::from)
+    }
+
+    fn check(input: &str) -> Vec<AocError> {
+        check::check(input)
+    }
+}
@@ -0,0 +1,33 @@
+use aoc_common::parse::{grid, IResult};
+
+/// The tiles of a pipe, the start and the ground.
+pub const TILES: &str = "|-LJ7FS.";
+
+/// Rows of the sketch of the pipes.
+pub fn sketch(input: &str) -> IResult<'_, Vec<&str>> {
+    grid(TILES)(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{errors::AocError, parse::finish};
+
+    use super::*;
+
+    #[test]
+    fn test_sketch() -> miette::Result<()> {
+        assert_eq!(vec![".S-7", ".L-J"], finish(".S-7\n.L-J\n", sketch)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_sketch_error() {
+        match finish(".S-7\n.L+J\n", sketch) {
+            Err(AocError::ParseError { message, span, .. }) => {
+                assert_eq!("expected one of `|-LJ7FS.` at line 2, column 3", message);
+                assert_eq!(7, span.offset());
+            }
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+}
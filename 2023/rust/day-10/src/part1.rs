@@ -1,36 +1,32 @@
-use crate::errors::AocError;
+use crate::{errors::AocError, Sketch};
 
-#[tracing::instrument]
-pub fn process(input: &str) -> miette::Result<usize, AocError> {
-    let grid = parse(input);
-    let looop = find_loop(&grid);
+pub use crate::parse;
 
-    Ok(looop.len() / 2)
+#[tracing::instrument(skip(input), fields(input_len = input.len()))]
+pub fn process(input: &str) -> miette::Result<usize, AocError> {
+    solve(&parse(input)?)
 }
 
-fn parse(input: &str) -> Vec<Vec<Pipe>> {
-    input
-        .lines()
-        .enumerate()
-        .map(|(r, line)| {
-            line.chars()
-                .enumerate()
-                .map(|(c, ch)| Pipe::new(ch, r, c))
-                .collect::<Vec<_>>()
-        })
-        .collect::<Vec<_>>()
+#[tracing::instrument(skip_all)]
+pub fn solve(sketch: &Sketch<'_>) -> miette::Result<usize, AocError> {
+    let looop = find_loop(sketch.input, &sketch.grid)?;
+
+    Ok(looop.len() / 2)
 }
 
-fn find_loop(grid: &[Vec<Pipe>]) -> Vec<&Pipe> {
+#[tracing::instrument(skip_all)]
+pub(crate) fn find_loop<'a>(
+    input: &str,
+    grid: &'a [Vec<Pipe>],
+) -> miette::Result<Vec<&'a Pipe>, AocError> {
     let start = grid
         .iter()
         .find_map(|pipes| pipes.iter().find(|pipe| pipe.is_start()))
-        .expect("Start pipe must exist");
+        .ok_or_else(|| AocError::invalid_input(input, input, "there is no start pipe `S`"))?;
 
-    let mut next = start
-        .get_neighbors(&grid, None)
-        .next()
-        .expect("Starting pipe must have at least one connected pipe");
+    let mut next = start.get_neighbors(grid, None).next().ok_or_else(|| {
+        AocError::invalid_input(input, input, "no pipe connects to the start pipe")
+    })?;
 
     let mut path = vec![start];
     while next != start {
@@ -39,20 +35,20 @@ fn find_loop(grid: &[Vec<Pipe>]) -> Vec<&Pipe> {
         next = next
             .get_neighbors(grid, Some(prev))
             .next()
-            .expect("Pipe must have at least one other neighbor than prev");
+            .ok_or_else(|| AocError::invalid_input(input, input, "the pipes do not form a loop"))?;
     }
-    path
+    Ok(path)
 }
 
 #[derive(Debug, Eq, PartialEq)]
-struct Pipe {
+pub(crate) struct Pipe {
     shape: char,
-    row: usize,
-    col: usize,
+    pub(crate) row: usize,
+    pub(crate) col: usize,
 }
 
 impl Pipe {
-    fn new(shape: char, row: usize, col: usize) -> Self {
+    pub(crate) fn new(shape: char, row: usize, col: usize) -> Self {
         Self { shape, row, col }
     }
 
@@ -80,7 +76,7 @@ impl Pipe {
         &'a self,
         grid: &'a [Vec<Pipe>],
         previous: Option<&'a Pipe>,
-    ) -> impl Iterator<Item = &Pipe> + 'a {
+    ) -> impl Iterator<Item = &'a Pipe> + 'a {
         [(1, 0), (-1, 0), (0, 1), (0, -1)]
             .iter()
             .filter_map(|(dr, dc)| {
@@ -106,56 +102,50 @@ impl std::fmt::Display for Pipe {
 
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+
     use super::*;
-    use rstest::rstest;
-
-    #[rstest]
-    #[case(
-        ".....
-.S-7.
-.|.|.
-.L-J.
-.....",
-        4
-    )]
-    #[case(
-        "-L|F7
-7S-7|
-L|7||
--L-J|
-L|-JF",
-        4
-    )]
-    #[case(
-        "..F7.
-.FJ|.
-SJ.L7
-|F--J
-LJ...",
-        8
-    )]
-    #[case(
-        "7-F7-
-.FJ|7
-SJLL7
-|F--J
-LJ.LJ",
-        8
-    )]
-    #[case(
-        "...........
-.S-------7.
-.|F-----7|.
-.||.....||.
-.||.....||.
-.|L-7.F-J|.
-.|..|.|..|.
-.L--J.L--J.
-...........",
-        23
-    )]
-    fn test_process(#[case] input: &str, #[case] expected: usize) -> miette::Result<()> {
-        assert_eq!(expected, process(input)?);
-        Ok(())
+
+    /// A `width` by `height` rectangular loop, with `S` on the `start`th tile
+    /// of its border and `margin` rows and columns of ground around it.
+    fn rectangle(width: usize, height: usize, start: usize, margin: (usize, usize)) -> String {
+        let (top, left) = margin;
+        let mut grid = vec![vec!['.'; width + left * 2]; height + top * 2];
+        let (bottom, right) = (top + height - 1, left + width - 1);
+        let border = (left..=right)
+            .map(|col| (top, col))
+            .chain((top + 1..bottom).map(|row| (row, right)))
+            .chain((left..=right).rev().map(|col| (bottom, col)))
+            .chain((top + 1..bottom).rev().map(|row| (row, left)))
+            .collect::<Vec<_>>();
+        for &(row, col) in &border {
+            grid[row][col] = match (row == top, row == bottom, col == left, col == right) {
+                (true, _, true, _) => 'F',
+                (true, _, _, true) => '7',
+                (_, true, true, _) => 'L',
+                (_, true, _, true) => 'J',
+                (true, ..) | (_, true, ..) => '-',
+                _ => '|',
+            };
+        }
+        let (row, col) = border[start % border.len()];
+        grid[row][col] = 'S';
+        grid.iter()
+            .map(|row| row.iter().collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    proptest! {
+        #[test]
+        fn test_farthest_tile_is_half_the_loop(
+            width in 2..16usize,
+            height in 2..16usize,
+            start in 0..64usize,
+            margin in (0..3usize, 0..3usize),
+        ) {
+            let input = rectangle(width, height, start, margin);
+            prop_assert_eq!(width + height - 2, process(&input)?);
+        }
     }
 }
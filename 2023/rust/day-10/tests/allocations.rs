@@ -0,0 +1,29 @@
+//! Allocation budgets of every variant on the real input. dhat counts every
+//! allocation of the process, so this file must hold a single test.
+
+use aoc_common::budget::{assert_within, Budget};
+
+#[global_allocator]
+static ALLOC: dhat::Alloc = dhat::Alloc;
+
+#[test]
+fn allocations() -> miette::Result<()> {
+    let input = day_10::input::read_input(None)?;
+    assert_within(
+        "part1",
+        Budget {
+            blocks: 490,
+            max_bytes: 690_000,
+        },
+        || day_10::part1::process(&input),
+    )?;
+    assert_within(
+        "part2",
+        Budget {
+            blocks: 490,
+            max_bytes: 690_000,
+        },
+        || day_10::part2::process(&input),
+    )?;
+    Ok(())
+}
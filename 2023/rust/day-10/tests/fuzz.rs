@@ -0,0 +1,30 @@
+//! Inputs the fuzz targets in `fuzz/` found a panic or hang on, minimized.
+
+use day_10::errors::AocError;
+
+#[test]
+fn ragged_rows() {
+    for input in ["S-\n-", ".S\nJ"] {
+        assert!(matches!(
+            day_10::part1::process(input),
+            Err(AocError::InvalidInput { .. })
+        ));
+        assert!(matches!(
+            day_10::part2::process(input),
+            Err(AocError::InvalidInput { .. })
+        ));
+    }
+}
+
+#[test]
+fn second_start_pipe() {
+    let input = "FS...\n.S-7.\n-L-J.";
+    assert!(matches!(
+        day_10::part1::process(input),
+        Err(AocError::InvalidInput { .. })
+    ));
+    assert!(matches!(
+        day_10::part2::process(input),
+        Err(AocError::InvalidInput { .. })
+    ));
+}
@@ -1,31 +1,47 @@
-use day_11::*;
+use aoc_common::generate::{generate_seeded, Shape};
+
+#[global_allocator]
+static ALLOC: divan::AllocProfiler = divan::AllocProfiler::system();
 
 fn main() {
     // Run registered benchmarks.
     divan::main();
 }
 
-// #[divan::bench]
-// fn part1_expand_bfs() {
-//     part1_expand_bfs::process(divan::black_box(include_str!("../input.txt",))).unwrap();
-// }
-
-#[divan::bench]
-fn part1_expand() {
-    part1_expand::process(divan::black_box(include_str!("../input.txt",))).unwrap();
-}
-
-#[divan::bench]
-fn part1_no_expand() {
-    part1_no_expand::process(divan::black_box(include_str!("../input.txt",))).unwrap();
-}
+// a BFS per galaxy, over a second on the real input
+#[cfg(feature = "slow")]
+aoc_common::bench_stages!(day_11::part1_expand_bfs);
+aoc_common::bench_stages!(day_11::part1_expand);
+aoc_common::bench_stages!(day_11::part1_no_expand);
+aoc_common::bench_stages!(day_11::part1_no_expand_opt);
+#[cfg(feature = "parallel")]
+aoc_common::bench_stages!(day_11::part1_parallel);
+aoc_common::bench_stages!(day_11::part2, day_11::Params::default().expansion_factor);
+#[cfg(feature = "parallel")]
+aoc_common::bench_stages!(
+    day_11::part2_parallel,
+    day_11::Params::default().expansion_factor
+);
 
-#[divan::bench]
-fn part1_no_expand_opt() {
-    part1_no_expand_opt::process(divan::black_box(include_str!("../input.txt",))).unwrap();
+/// About `galaxies` galaxies in a square image with 3 of them a row, so
+/// that like in the real input about 1 in 20 rows and columns is empty.
+fn image(galaxies: usize) -> String {
+    let side = galaxies / 3;
+    let shape = Shape {
+        density: Some(3.0 / side as f64),
+        width: Some(side),
+        ..Shape::new(side)
+    };
+    generate_seeded(11, shape, 0).unwrap().input
 }
 
-#[divan::bench]
-fn part2() {
-    part2::process(divan::black_box(include_str!("../input.txt",)), 1e6 as usize).unwrap();
-}
+aoc_common::bench_scaling!(galaxies: [64, 128, 256, 512, 1024] => image;
+    day_11::part1_expand,
+    day_11::part1_no_expand,
+    day_11::part1_no_expand_opt,
+    #[cfg(feature = "parallel")]
+    day_11::part1_parallel,
+    day_11::part2(day_11::Params::default().expansion_factor),
+    #[cfg(feature = "parallel")]
+    day_11::part2_parallel(day_11::Params::default().expansion_factor),
+);
@@ -1,3 +1,4 @@
+use day_11::input::read_input;
 use day_11::part2::process;
 use miette::Context;
 
@@ -11,10 +12,11 @@ fn main() -> miette::Result<()> {
     let _profiler = dhat::Profiler::new_heap();
 
     #[cfg(not(feature = "dhat-heap"))]
-    tracing_subscriber::fmt::init();
+    let _trace = aoc_common::trace::init();
 
-    let file = include_str!("../../input.txt");
-    let result = process(file, 1e6 as usize).context("process part 2")?;
+    let file = read_input(std::env::args().nth(1)).context("read input")?;
+    let result =
+        aoc_common::trace::frame(|| process(&file, 1e6 as usize)).context("process part 2")?;
     println!("{}", result);
     Ok(())
 }
@@ -0,0 +1,22 @@
+use day_11::input::read_input;
+use day_11::part2_parallel::process;
+use miette::Context;
+
+#[cfg(feature = "dhat-heap")]
+#[global_allocator]
+static ALLOC: dhat::Alloc = dhat::Alloc;
+
+#[tracing::instrument]
+fn main() -> miette::Result<()> {
+    #[cfg(feature = "dhat-heap")]
+    let _profiler = dhat::Profiler::new_heap();
+
+    #[cfg(not(feature = "dhat-heap"))]
+    let _trace = aoc_common::trace::init();
+
+    let file = read_input(std::env::args().nth(1)).context("read input")?;
+    let result =
+        aoc_common::trace::frame(|| process(&file, 1e6 as usize)).context("process part 2")?;
+    println!("{}", result);
+    Ok(())
+}
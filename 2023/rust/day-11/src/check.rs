@@ -0,0 +1,32 @@
+use aoc_common::check::Violations;
+
+use crate::errors::AocError;
+
+/// The image is a rectangle of galaxies `#` and empty space `.`.
+pub fn check(input: &str) -> Vec<AocError> {
+    let mut violations = Violations::new(input);
+    let rows = input.lines().collect::<Vec<_>>();
+    violations.grid(&rows, ".#");
+    violations.into_errors()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check() {
+        assert!(check("...#\n#...\n").is_empty());
+        let errors = check("...#\n#..*\n..\n")
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                "invalid puzzle input: `*` is not one of `.#`",
+                "invalid puzzle input: row is 2 wide, the first row is 4 wide",
+            ],
+            errors
+        );
+    }
+}
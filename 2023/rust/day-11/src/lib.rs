@@ -1,7 +1,100 @@
-pub mod errors;
+use aoc_common::{
+    check::rectangle,
+    parse::finish,
+    solution::{Answer, Solution},
+};
+use errors::AocError;
+
+pub use aoc_common::errors;
+pub mod check;
+pub mod input;
+pub mod parser;
 
 pub mod part1_expand;
 pub mod part1_expand_bfs;
 pub mod part1_no_expand;
 pub mod part1_no_expand_opt;
+#[cfg(feature = "parallel")]
+pub mod part1_parallel;
 pub mod part2;
+#[cfg(feature = "parallel")]
+pub mod part2_parallel;
+
+pub struct Day11;
+
+#[derive(Debug, Clone)]
+pub struct Params {
+    /// How many rows or columns every empty row or column is worth in part 2.
+    pub expansion_factor: usize,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Self {
+            expansion_factor: 1_000_000,
+        }
+    }
+}
+
+/// The image as a grid of characters, which every variant reads.
+#[tracing::instrument(skip_all)]
+pub fn parse(input: &str) -> miette::Result<Vec<Vec<char>>, AocError> {
+    let rows = finish(input, parser::image)?;
+    rectangle(input, &rows)?;
+    Ok(rows
+        .into_iter()
+        .map(|line| line.chars().collect::<Vec<_>>())
+        .collect::<Vec<_>>())
+}
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+    type Input<'a> = Vec<Vec<char>>;
+    type Params = Params;
+
+    fn parse(input: &str) -> miette::Result<Self::Input<'_>, AocError> {
+        parse(input)
+    }
+
+    fn part1(input: &Self::Input<'_>, _: &Params) -> miette::Result<Answer, AocError> {
+        part1_expand::solve(input).map(Answer::from)
+    }
+
+    fn part2(input: &Self::Input<'_>, params: &Params) -> miette::Result<Answer, AocError> {
+        part2::solve(input, params.expansion_factor).map(Answer::from)
+    }
+
+    fn check(input: &str) -> Vec<AocError> {
+        check::check(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    const INPUT: &str = include_str!("../examples/example.txt");
+
+    #[rstest]
+    #[case(10, 1030)]
+    #[case(100, 8410)]
+    fn test_expansion_factor(
+        #[case] expansion_factor: usize,
+        #[case] expected: usize,
+    ) -> miette::Result<()> {
+        let params = Params { expansion_factor };
+        assert_eq!(
+            Answer::from(expected),
+            Day11::solve_with(INPUT, 2, &params)?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_expansion_factor_out_of_range() {
+        let solve = |expansion_factor| Day11::solve_with(INPUT, 2, &Params { expansion_factor });
+        assert!(matches!(solve(0), Err(AocError::InvalidInput { .. })));
+        assert!(matches!(solve(usize::MAX), Err(AocError::Overflow(_))));
+    }
+}
@@ -0,0 +1,29 @@
+use aoc_common::parse::{grid, IResult};
+
+/// Rows of the image: `#` for a galaxy and `.` for empty space.
+pub fn image(input: &str) -> IResult<'_, Vec<&str>> {
+    grid(".#")(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{errors::AocError, parse::finish};
+
+    use super::*;
+
+    #[test]
+    fn test_image() -> miette::Result<()> {
+        assert_eq!(vec!["...#", "#..."], finish("...#\n#...\n", image)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_image_error() {
+        match finish("...#\n#..*\n", image) {
+            Err(AocError::ParseError { message, .. }) => {
+                assert_eq!("expected one of `.#` at line 2, column 4", message)
+            }
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+}
@@ -3,14 +3,21 @@ use std::{cmp::Reverse, collections::BinaryHeap};
 use crate::errors::AocError;
 use itertools::Itertools;
 
-#[tracing::instrument]
+pub use crate::parse;
+
+#[tracing::instrument(skip(input), fields(input_len = input.len()))]
 pub fn process(input: &str) -> miette::Result<usize, AocError> {
-    let mut grid = parse(input);
-    grid = expand_universe(grid);
+    solve(&parse(input)?)
+}
+
+#[tracing::instrument(skip_all)]
+pub fn solve(grid: &[Vec<char>]) -> miette::Result<usize, AocError> {
+    let grid = expand_universe(grid.to_vec());
     let galaxies = get_galaxies(&grid);
 
     let mut result = 0;
     for (i, &galaxy1) in galaxies.iter().enumerate() {
+        let _pairs = tracing::debug_span!("pairs", galaxy = i).entered();
         for &galaxy2 in galaxies.iter().skip(i + 1) {
             result += distance(galaxy1, galaxy2);
         }
@@ -20,13 +27,6 @@ pub fn process(input: &str) -> miette::Result<usize, AocError> {
 
 type Coordinate = (usize, usize);
 
-fn parse(input: &str) -> Vec<Vec<char>> {
-    input
-        .lines()
-        .map(|line| line.chars().collect_vec())
-        .collect_vec()
-}
-
 fn expand_universe(grid: Vec<Vec<char>>) -> Vec<Vec<char>> {
     let (m, n) = (grid.len(), grid[0].len());
     let mut present_rows = vec![false; m];
@@ -122,29 +122,11 @@ mod tests {
         #....#......."}
     )]
     fn test_expand(#[case] input: &str, #[case] expected: &str) -> miette::Result<()> {
-        let actual = expand_universe(parse(input))
+        let actual = expand_universe(parse(input)?)
             .iter()
             .map(|line| line.iter().collect::<String>())
             .join("\n");
         assert_eq!(expected, actual);
         Ok(())
     }
-    #[rstest]
-    #[case(indoc! {"
-        ...#......
-        .......#..
-        #.........
-        ..........
-        ......#...
-        .#........
-        .........#
-        ..........
-        .......#..
-        #...#.....
-    "}, 374
-    )]
-    fn test_process(#[case] input: &str, #[case] expected: usize) -> miette::Result<()> {
-        assert_eq!(expected, process(input)?);
-        Ok(())
-    }
 }
@@ -6,10 +6,16 @@ use std::{
 use crate::errors::AocError;
 use itertools::Itertools;
 
-#[tracing::instrument]
+pub use crate::parse;
+
+#[tracing::instrument(skip(input), fields(input_len = input.len()))]
 pub fn process(input: &str) -> miette::Result<usize, AocError> {
-    let mut grid = parse(input);
-    grid = expand_universe(grid);
+    solve(&parse(input)?)
+}
+
+#[tracing::instrument(skip_all)]
+pub fn solve(grid: &[Vec<char>]) -> miette::Result<usize, AocError> {
+    let grid = expand_universe(grid.to_vec());
     let galaxies = get_galaxies(&grid);
 
     let mut found_pairs = HashSet::new();
@@ -22,13 +28,6 @@ pub fn process(input: &str) -> miette::Result<usize, AocError> {
 
 type Coordinate = (usize, usize);
 
-fn parse(input: &str) -> Vec<Vec<char>> {
-    input
-        .lines()
-        .map(|line| line.chars().collect_vec())
-        .collect_vec()
-}
-
 fn expand_universe(grid: Vec<Vec<char>>) -> Vec<Vec<char>> {
     let (m, n) = (grid.len(), grid[0].len());
     let mut present_rows = vec![false; m];
@@ -87,6 +86,7 @@ fn get_galaxies(grid: &[Vec<char>]) -> Vec<Coordinate> {
         .collect_vec()
 }
 
+#[tracing::instrument(level = "debug", skip(grid, found_pairs))]
 fn sum_distance_to_others(
     grid: &[Vec<char>],
     found_pairs: &mut HashSet<(Coordinate, Coordinate)>,
@@ -166,29 +166,11 @@ mod tests {
         #....#......."}
     )]
     fn test_expand(#[case] input: &str, #[case] expected: &str) -> miette::Result<()> {
-        let actual = expand_universe(parse(input))
+        let actual = expand_universe(parse(input)?)
             .iter()
             .map(|line| line.iter().collect::<String>())
             .join("\n");
         assert_eq!(expected, actual);
         Ok(())
     }
-    #[rstest]
-    #[case(indoc! {"
-        ...#......
-        .......#..
-        #.........
-        ..........
-        ......#...
-        .#........
-        .........#
-        ..........
-        .......#..
-        #...#.....
-    "}, 374
-    )]
-    fn test_process(#[case] input: &str, #[case] expected: usize) -> miette::Result<()> {
-        assert_eq!(expected, process(input)?);
-        Ok(())
-    }
 }
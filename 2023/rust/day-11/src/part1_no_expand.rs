@@ -4,14 +4,21 @@ use itertools::Itertools;
 
 use crate::errors::AocError;
 
-#[tracing::instrument]
+pub use crate::parse;
+
+#[tracing::instrument(skip(input), fields(input_len = input.len()))]
 pub fn process(input: &str) -> miette::Result<usize, AocError> {
-    let grid = parse(input);
-    let galaxies = get_galaxies(&grid);
-    let (rows, cols) = to_expand(&grid);
+    solve(&parse(input)?)
+}
+
+#[tracing::instrument(skip_all)]
+pub fn solve(grid: &[Vec<char>]) -> miette::Result<usize, AocError> {
+    let galaxies = get_galaxies(grid);
+    let (rows, cols) = to_expand(grid);
 
     let mut result = 0;
     for (i, &galaxy1) in galaxies.iter().enumerate() {
+        let _pairs = tracing::debug_span!("pairs", galaxy = i).entered();
         for &galaxy2 in galaxies.iter().skip(i + 1) {
             let mut dist = distance(galaxy1, galaxy2);
             let (xrange, yrange) = range(galaxy1, galaxy2);
@@ -25,13 +32,6 @@ pub fn process(input: &str) -> miette::Result<usize, AocError> {
 
 type Coordinate = (usize, usize);
 
-fn parse(input: &str) -> Vec<Vec<char>> {
-    input
-        .lines()
-        .map(|line| line.chars().collect_vec())
-        .collect_vec()
-}
-
 fn get_galaxies(grid: &[Vec<char>]) -> Vec<Coordinate> {
     grid.iter()
         .enumerate()
@@ -79,29 +79,3 @@ fn range(p: Coordinate, q: Coordinate) -> (RangeInclusive<usize>, RangeInclusive
     let (ymin, ymax) = (p.1.min(q.1), p.1.max(q.1));
     (xmin..=xmax, ymin..=ymax)
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use indoc::indoc;
-    use rstest::rstest;
-
-    #[rstest]
-    #[case(indoc! {"
-        ...#......
-        .......#..
-        #.........
-        ..........
-        ......#...
-        .#........
-        .........#
-        ..........
-        .......#..
-        #...#.....
-    "}, 374
-    )]
-    fn test_process(#[case] input: &str, #[case] expected: usize) -> miette::Result<()> {
-        assert_eq!(expected, process(input)?);
-        Ok(())
-    }
-}
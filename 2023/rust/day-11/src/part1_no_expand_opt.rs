@@ -1,41 +1,31 @@
 use crate::errors::AocError;
 use itertools::Itertools;
 
-#[tracing::instrument]
+pub use crate::parse;
+
+#[tracing::instrument(skip(input), fields(input_len = input.len()))]
 pub fn process(input: &str) -> miette::Result<usize, AocError> {
-    let grid = parse(input);
-    let galaxies = get_galaxies(&grid);
-    let (rows, cols) = to_expand(&grid);
+    solve(&parse(input)?)
+}
+
+#[tracing::instrument(skip_all)]
+pub fn solve(grid: &[Vec<char>]) -> miette::Result<usize, AocError> {
+    let galaxies = get_galaxies(grid);
+    let (rows, cols) = to_expand(grid);
 
     let mut result = 0;
     for (i, &galaxy1) in galaxies.iter().enumerate() {
+        let _pairs = tracing::debug_span!("pairs", galaxy = i).entered();
         for &galaxy2 in galaxies.iter().skip(i + 1) {
-            let mut dist = distance(galaxy1, galaxy2);
-            let (xrange, yrange) = range(galaxy1, galaxy2);
-            dist += rows
-                .iter()
-                .filter(|&&r| xrange.0 <= r && r <= xrange.1)
-                .count();
-            dist += cols
-                .iter()
-                .filter(|&&c| yrange.0 <= c && c <= yrange.1)
-                .count();
-            result += dist;
+            result += expanded_distance(galaxy1, galaxy2, &rows, &cols);
         }
     }
     Ok(result)
 }
 
-type Coordinate = (usize, usize);
+pub(crate) type Coordinate = (usize, usize);
 
-fn parse(input: &str) -> Vec<Vec<char>> {
-    input
-        .lines()
-        .map(|line| line.chars().collect_vec())
-        .collect_vec()
-}
-
-fn get_galaxies(grid: &[Vec<char>]) -> Vec<Coordinate> {
+pub(crate) fn get_galaxies(grid: &[Vec<char>]) -> Vec<Coordinate> {
     grid.iter()
         .enumerate()
         .flat_map(|(i, row)| {
@@ -46,7 +36,7 @@ fn get_galaxies(grid: &[Vec<char>]) -> Vec<Coordinate> {
         .collect_vec()
 }
 
-fn to_expand(grid: &[Vec<char>]) -> (Vec<usize>, Vec<usize>) {
+pub(crate) fn to_expand(grid: &[Vec<char>]) -> (Vec<usize>, Vec<usize>) {
     let (m, n) = (grid.len(), grid[0].len());
     let mut present_rows = vec![false; m];
     let mut present_cols = vec![false; n];
@@ -73,40 +63,35 @@ fn to_expand(grid: &[Vec<char>]) -> (Vec<usize>, Vec<usize>) {
     (rows_to_add, cols_to_add)
 }
 
-fn distance(p: Coordinate, q: Coordinate) -> usize {
+/// Distance between `galaxy1` and `galaxy2` once the empty `rows` and `cols`
+/// between them are doubled.
+pub(crate) fn expanded_distance(
+    galaxy1: Coordinate,
+    galaxy2: Coordinate,
+    rows: &[usize],
+    cols: &[usize],
+) -> usize {
+    let mut dist = distance(galaxy1, galaxy2);
+    let (xrange, yrange) = range(galaxy1, galaxy2);
+    dist += rows
+        .iter()
+        .filter(|&&r| xrange.0 <= r && r <= xrange.1)
+        .count();
+    dist += cols
+        .iter()
+        .filter(|&&c| yrange.0 <= c && c <= yrange.1)
+        .count();
+    dist
+}
+
+pub(crate) fn distance(p: Coordinate, q: Coordinate) -> usize {
     p.0.abs_diff(q.0) + p.1.abs_diff(q.1)
 }
 
-type RangeInclusive = (usize, usize);
+pub(crate) type RangeInclusive = (usize, usize);
 
-fn range(p: Coordinate, q: Coordinate) -> (RangeInclusive, RangeInclusive) {
+pub(crate) fn range(p: Coordinate, q: Coordinate) -> (RangeInclusive, RangeInclusive) {
     let (xmin, xmax) = (p.0.min(q.0), p.0.max(q.0));
     let (ymin, ymax) = (p.1.min(q.1), p.1.max(q.1));
     ((xmin, xmax), (ymin, ymax))
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use indoc::indoc;
-    use rstest::rstest;
-
-    #[rstest]
-    #[case(indoc! {"
-        ...#......
-        .......#..
-        #.........
-        ..........
-        ......#...
-        .#........
-        .........#
-        ..........
-        .......#..
-        #...#.....
-    "}, 374
-    )]
-    fn test_process(#[case] input: &str, #[case] expected: usize) -> miette::Result<()> {
-        assert_eq!(expected, process(input)?);
-        Ok(())
-    }
-}
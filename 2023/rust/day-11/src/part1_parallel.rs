@@ -0,0 +1,34 @@
+use rayon::prelude::*;
+
+use crate::{
+    errors::AocError,
+    part1_no_expand_opt::{expanded_distance, get_galaxies, to_expand},
+};
+
+pub use crate::parse;
+
+#[tracing::instrument(skip(input), fields(input_len = input.len()))]
+pub fn process(input: &str) -> miette::Result<usize, AocError> {
+    solve(&parse(input)?)
+}
+
+/// [`crate::part1_no_expand_opt::solve`] with the pairs of each galaxy split
+/// across the rayon thread pool.
+#[tracing::instrument(skip_all)]
+pub fn solve(grid: &[Vec<char>]) -> miette::Result<usize, AocError> {
+    let galaxies = get_galaxies(grid);
+    let (rows, cols) = to_expand(grid);
+
+    let result = galaxies
+        .par_iter()
+        .enumerate()
+        .map(|(i, &galaxy1)| {
+            let _pairs = tracing::debug_span!("pairs", galaxy = i).entered();
+            galaxies[i + 1..]
+                .iter()
+                .map(|&galaxy2| expanded_distance(galaxy1, galaxy2, &rows, &cols))
+                .sum::<usize>()
+        })
+        .sum();
+    Ok(result)
+}
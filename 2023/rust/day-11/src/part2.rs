@@ -1,143 +1,114 @@
-use crate::errors::AocError;
-use itertools::Itertools;
+use crate::{
+    errors::AocError,
+    part1_no_expand_opt::{distance, get_galaxies, range, to_expand, Coordinate},
+};
 
-#[tracing::instrument]
+pub use crate::parse;
+
+#[tracing::instrument(skip(input), fields(input_len = input.len()))]
 pub fn process(input: &str, expansion_factor: usize) -> miette::Result<usize, AocError> {
-    let grid = parse(input);
-    let galaxies = get_galaxies(&grid);
-    let (rows, cols) = to_expand(&grid);
+    solve(&parse(input)?, expansion_factor)
+}
+
+#[tracing::instrument(skip_all)]
+pub fn solve(grid: &[Vec<char>], expansion_factor: usize) -> miette::Result<usize, AocError> {
+    let extra = extra_per_empty(expansion_factor)?;
+    let galaxies = get_galaxies(grid);
+    let (rows, cols) = to_expand(grid);
 
     let mut result = 0;
     for (i, &galaxy1) in galaxies.iter().enumerate() {
+        let _pairs = tracing::debug_span!("pairs", galaxy = i).entered();
         for &galaxy2 in galaxies.iter().skip(i + 1) {
-            let dist = distance(galaxy1, galaxy2);
-            let (xrange, yrange) = range(galaxy1, galaxy2);
-            let x_expand = rows
-                .iter()
-                .filter(|&&r| xrange.0 <= r && r < xrange.1)
-                .count();
-            let y_expand = cols
-                .iter()
-                .filter(|&&c| yrange.0 <= c && c <= yrange.1)
-                .count();
-            result += dist + (x_expand + y_expand) * (expansion_factor - 1);
+            result = add_distance(
+                result,
+                expanded_distance(galaxy1, galaxy2, &rows, &cols, extra)?,
+            )?;
         }
     }
     Ok(result)
 }
 
-type Coordinate = (usize, usize);
-
-fn parse(input: &str) -> Vec<Vec<char>> {
-    input
-        .lines()
-        .map(|line| line.chars().collect_vec())
-        .collect_vec()
+/// How many rows or columns each empty one adds once it is worth
+/// `expansion_factor` of them, which counts the empty one itself.
+pub(crate) fn extra_per_empty(expansion_factor: usize) -> miette::Result<usize, AocError> {
+    expansion_factor
+        .checked_sub(1)
+        .ok_or_else(|| AocError::invalid_input("", "", "the expansion factor must be at least 1"))
 }
 
-fn get_galaxies(grid: &[Vec<char>]) -> Vec<Coordinate> {
-    grid.iter()
-        .enumerate()
-        .flat_map(|(i, row)| {
-            row.iter()
-                .enumerate()
-                .filter_map(move |(j, &space)| if space == '#' { Some((i, j)) } else { None })
-        })
-        .collect_vec()
+pub(crate) fn add_distance(sum: usize, distance: usize) -> Result<usize, AocError> {
+    sum.checked_add(distance)
+        .ok_or_else(|| AocError::overflow("the sum of the distances is more than usize::MAX"))
 }
 
-fn to_expand(grid: &[Vec<char>]) -> (Vec<usize>, Vec<usize>) {
-    let (m, n) = (grid.len(), grid[0].len());
-    let mut present_rows = vec![false; m];
-    let mut present_cols = vec![false; n];
-
-    for i in 0..m {
-        for (j, &ch) in grid[i].iter().enumerate() {
-            if ch == '#' {
-                present_rows[i] = true;
-                present_cols[j] = true;
-            }
-        }
-    }
-
-    let rows_to_add = present_rows
+/// Distance between `galaxy1` and `galaxy2` once each of the empty `rows`
+/// and `cols` between them adds `extra` of them.
+pub(crate) fn expanded_distance(
+    galaxy1: Coordinate,
+    galaxy2: Coordinate,
+    rows: &[usize],
+    cols: &[usize],
+    extra: usize,
+) -> miette::Result<usize, AocError> {
+    let dist = distance(galaxy1, galaxy2);
+    let (xrange, yrange) = range(galaxy1, galaxy2);
+    let x_expand = rows
         .iter()
-        .enumerate()
-        .filter_map(|(i, x)| if !x { Some(i) } else { None })
-        .collect_vec();
-    let cols_to_add = present_cols
+        .filter(|&&r| xrange.0 <= r && r < xrange.1)
+        .count();
+    let y_expand = cols
         .iter()
-        .enumerate()
-        .filter_map(|(i, x)| if !x { Some(i) } else { None })
-        .collect_vec();
-    (rows_to_add, cols_to_add)
-}
-
-fn distance(p: Coordinate, q: Coordinate) -> usize {
-    p.0.abs_diff(q.0) + p.1.abs_diff(q.1)
-}
-
-type RangeInclusive = (usize, usize);
-
-fn range(p: Coordinate, q: Coordinate) -> (RangeInclusive, RangeInclusive) {
-    let (xmin, xmax) = (p.0.min(q.0), p.0.max(q.0));
-    let (ymin, ymax) = (p.1.min(q.1), p.1.max(q.1));
-    ((xmin, xmax), (ymin, ymax))
+        .filter(|&&c| yrange.0 <= c && c <= yrange.1)
+        .count();
+    (x_expand + y_expand)
+        .checked_mul(extra)
+        .and_then(|expansion| dist.checked_add(expansion))
+        .ok_or_else(|| AocError::overflow("a distance is more than usize::MAX"))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use indoc::indoc;
+    use proptest::prelude::*;
     use rstest::rstest;
 
+    const EXAMPLE: &str = include_str!("../examples/example.txt");
+
     #[rstest]
-    #[case(indoc! {"
-        ...#......
-        .......#..
-        #.........
-        ..........
-        ......#...
-        .#........
-        .........#
-        ..........
-        .......#..
-        #...#.....
-    "},2, 374
-    )]
-    #[case(indoc! {"
-        ...#......
-        .......#..
-        #.........
-        ..........
-        ......#...
-        .#........
-        .........#
-        ..........
-        .......#..
-        #...#.....
-    "},10, 1030
-    )]
-    #[case(indoc! {"
-        ...#......
-        .......#..
-        #.........
-        ..........
-        ......#...
-        .#........
-        .........#
-        ..........
-        .......#..
-        #...#.....
-    "},100, 8410
-    )]
+    #[case(2, 374)]
+    #[case(10, 1030)]
+    #[case(100, 8410)]
     fn test_process(
-        #[case] input: &str,
         #[case] expansion_factor: usize,
         #[case] expected: usize,
     ) -> miette::Result<()> {
-        assert_eq!(expected, process(input, expansion_factor)?);
+        assert_eq!(expected, process(EXAMPLE, expansion_factor)?);
         Ok(())
     }
-}
 
+    /// Images of up to 12 by 12 cells, about 1 in 5 of them galaxies.
+    fn image() -> impl Strategy<Value = String> {
+        (1..12usize, 1..12usize).prop_flat_map(|(rows, cols)| {
+            let row = prop::collection::vec(prop::bool::weighted(0.2), cols).prop_map(|row| {
+                row.into_iter()
+                    .map(|galaxy| if galaxy { '#' } else { '.' })
+                    .collect::<String>()
+            });
+            prop::collection::vec(row, rows).prop_map(|rows| rows.join("\n"))
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn test_doubling_is_part1(input in image()) {
+            let doubled = process(&input, 2)?;
+            prop_assert_eq!(doubled, crate::part1_expand::process(&input)?);
+            prop_assert_eq!(doubled, crate::part1_expand_bfs::process(&input)?);
+            prop_assert_eq!(doubled, crate::part1_no_expand::process(&input)?);
+            prop_assert_eq!(doubled, crate::part1_no_expand_opt::process(&input)?);
+            #[cfg(feature = "parallel")]
+            prop_assert_eq!(doubled, crate::part1_parallel::process(&input)?);
+        }
+    }
+}
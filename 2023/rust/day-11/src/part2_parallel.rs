@@ -0,0 +1,57 @@
+use rayon::prelude::*;
+
+use crate::{
+    errors::AocError,
+    part1_no_expand_opt::{get_galaxies, to_expand},
+    part2::{add_distance, expanded_distance, extra_per_empty},
+};
+
+pub use crate::parse;
+
+#[tracing::instrument(skip(input), fields(input_len = input.len()))]
+pub fn process(input: &str, expansion_factor: usize) -> miette::Result<usize, AocError> {
+    solve(&parse(input)?, expansion_factor)
+}
+
+/// [`crate::part2::solve`] with the pairs of each galaxy split across the
+/// rayon thread pool.
+#[tracing::instrument(skip_all)]
+pub fn solve(grid: &[Vec<char>], expansion_factor: usize) -> miette::Result<usize, AocError> {
+    let extra = extra_per_empty(expansion_factor)?;
+    let galaxies = get_galaxies(grid);
+    let (rows, cols) = to_expand(grid);
+
+    galaxies
+        .par_iter()
+        .enumerate()
+        .map(|(i, &galaxy1)| {
+            let _pairs = tracing::debug_span!("pairs", galaxy = i).entered();
+            galaxies[i + 1..].iter().try_fold(0, |sum, &galaxy2| {
+                add_distance(
+                    sum,
+                    expanded_distance(galaxy1, galaxy2, &rows, &cols, extra)?,
+                )
+            })
+        })
+        .try_reduce(|| 0, add_distance)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    const EXAMPLE: &str = include_str!("../examples/example.txt");
+
+    #[rstest]
+    #[case(2, 374)]
+    #[case(10, 1030)]
+    #[case(100, 8410)]
+    fn test_process(
+        #[case] expansion_factor: usize,
+        #[case] expected: usize,
+    ) -> miette::Result<()> {
+        assert_eq!(expected, process(EXAMPLE, expansion_factor)?);
+        Ok(())
+    }
+}
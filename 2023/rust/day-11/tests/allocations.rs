@@ -0,0 +1,53 @@
+//! Allocation budgets of every variant on the real input. dhat counts every
+//! allocation of the process, so this file must hold a single test.
+
+use aoc_common::budget::{assert_within, Budget};
+
+#[global_allocator]
+static ALLOC: dhat::Alloc = dhat::Alloc;
+
+#[test]
+fn allocations() -> miette::Result<()> {
+    let input = day_11::input::read_input(None)?;
+    assert_within(
+        "part1_expand",
+        Budget {
+            blocks: 2_100,
+            max_bytes: 290_000,
+        },
+        || day_11::part1_expand::process(&input),
+    )?;
+    assert_within(
+        "part1_expand_bfs",
+        Budget {
+            blocks: 12_000,
+            max_bytes: 7_800_000,
+        },
+        || day_11::part1_expand_bfs::process(&input),
+    )?;
+    assert_within(
+        "part1_no_expand",
+        Budget {
+            blocks: 490,
+            max_bytes: 110_000,
+        },
+        || day_11::part1_no_expand::process(&input),
+    )?;
+    assert_within(
+        "part1_no_expand_opt",
+        Budget {
+            blocks: 490,
+            max_bytes: 110_000,
+        },
+        || day_11::part1_no_expand_opt::process(&input),
+    )?;
+    assert_within(
+        "part2",
+        Budget {
+            blocks: 490,
+            max_bytes: 110_000,
+        },
+        || day_11::part2::process(&input, day_11::Params::default().expansion_factor),
+    )?;
+    Ok(())
+}
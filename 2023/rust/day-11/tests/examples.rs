@@ -0,0 +1,14 @@
+//! Every variant on every example in `examples/`, checked against the
+//! answers in `answers.txt`.
+
+aoc_common::example_tests!(
+    1 => day_11::part1_expand,
+    1 => day_11::part1_expand_bfs,
+    1 => day_11::part1_no_expand,
+    1 => day_11::part1_no_expand_opt,
+    #[cfg(feature = "parallel")]
+    1 => day_11::part1_parallel,
+    2 => day_11::part2(day_11::Params::default().expansion_factor),
+    #[cfg(feature = "parallel")]
+    2 => day_11::part2_parallel(day_11::Params::default().expansion_factor),
+);
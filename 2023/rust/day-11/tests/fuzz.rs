@@ -0,0 +1,29 @@
+//! Inputs the fuzz targets in `fuzz/` found a panic on, minimized.
+
+use day_11::errors::AocError;
+
+#[test]
+fn ragged_rows() {
+    for input in ["..\n#", ".\n##"] {
+        assert!(matches!(
+            day_11::part1_expand::process(input),
+            Err(AocError::InvalidInput { .. })
+        ));
+        assert!(matches!(
+            day_11::part1_expand_bfs::process(input),
+            Err(AocError::InvalidInput { .. })
+        ));
+        assert!(matches!(
+            day_11::part1_no_expand::process(input),
+            Err(AocError::InvalidInput { .. })
+        ));
+        assert!(matches!(
+            day_11::part1_no_expand_opt::process(input),
+            Err(AocError::InvalidInput { .. })
+        ));
+        assert!(matches!(
+            day_11::part2::process(input, 2),
+            Err(AocError::InvalidInput { .. })
+        ));
+    }
+}
@@ -0,0 +1,43 @@
+use aoc_common::check::Violations;
+
+use crate::errors::AocError;
+
+/// Every pattern, between blank lines, is a rectangle of ash `.` and rocks
+/// `#`.
+pub fn check(input: &str) -> Vec<AocError> {
+    let mut violations = Violations::new(input);
+    let mut pattern = Vec::new();
+    for line in input.lines().chain([""]) {
+        if !line.is_empty() {
+            pattern.push(line);
+        } else if !pattern.is_empty() {
+            violations.grid(&pattern, ".#");
+            pattern.clear();
+        }
+    }
+    if input.trim().is_empty() {
+        violations.report(input, "there are no patterns");
+    }
+    violations.into_errors()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check() {
+        assert!(check("#.\n.#\n\n###\n...\n").is_empty());
+        let errors = check("#.\n.#.\n\n#O#\n...\n")
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                "invalid puzzle input: row is 3 wide, the first row is 2 wide",
+                "invalid puzzle input: `O` is not one of `.#`",
+            ],
+            errors
+        );
+    }
+}
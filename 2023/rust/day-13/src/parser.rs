@@ -0,0 +1,34 @@
+use aoc_common::parse::{blank_line, grid, IResult};
+use nom::{combinator::consumed, multi::separated_list1};
+
+/// Every pattern as written, with its rows, separated by blank lines.
+pub fn patterns(input: &str) -> IResult<'_, Vec<(&str, Vec<&str>)>> {
+    separated_list1(blank_line, consumed(grid(".#")))(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{errors::AocError, parse::finish};
+    use rstest::rstest;
+
+    use super::*;
+
+    #[test]
+    fn test_patterns() -> miette::Result<()> {
+        assert_eq!(
+            vec![("#.\n.#", vec!["#.", ".#"]), ("##", vec!["##"])],
+            finish("#.\n.#\n\n##\n", patterns)?
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("#.\n.O\n\n##", "expected one of `.#` at line 2, column 2")]
+    #[case("#.\n.#\n\n\n##", "expected one of `.#` at line 4, column 1")]
+    fn test_patterns_error(#[case] input: &str, #[case] expected: &str) {
+        match finish(input, patterns) {
+            Err(AocError::ParseError { message, .. }) => assert_eq!(expected, message),
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+}
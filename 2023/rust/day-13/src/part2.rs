@@ -1,27 +1,50 @@
+use aoc_common::{check::rectangle, parse::finish};
 use itertools::Itertools;
 
-use crate::errors::AocError;
+use crate::{errors::AocError, parser};
 
-#[tracing::instrument]
+#[tracing::instrument(skip(input), fields(input_len = input.len()))]
 pub fn process(input: &str) -> miette::Result<usize, AocError> {
-    let res = input
-        .split("\n\n")
-        .map(|pat| {
-            let mat = pattern_to_matrix(pat);
-            if let Some(i) = find_reflection(&mat) {
-                i * 100
-            } else {
-                let mat = transpose(mat);
-                find_reflection(&mat).expect("tranposed pattern must have reflection")
-            }
+    solve(&parse(input)?)
+}
+
+/// A pattern as written, for diagnostics, and as a matrix of rocks.
+pub type Pattern<'a> = (&'a str, Vec<Vec<bool>>);
+
+#[tracing::instrument(skip_all)]
+pub fn parse(input: &str) -> miette::Result<Vec<Pattern<'_>>, AocError> {
+    finish(input, parser::patterns)?
+        .into_iter()
+        .map(|(pat, rows)| {
+            rectangle(input, &rows)?;
+            Ok((pat, pattern_to_matrix(&rows)))
         })
-        .sum::<usize>();
+        .collect()
+}
+
+#[tracing::instrument(skip_all)]
+pub fn solve(patterns: &[Pattern<'_>]) -> miette::Result<usize, AocError> {
+    let res = patterns
+        .iter()
+        .map(|(pat, mat)| summarize(pat, mat))
+        .sum::<Result<usize, AocError>>()?;
     Ok(res)
 }
 
-fn pattern_to_matrix(pattern: &str) -> Vec<Vec<bool>> {
-    pattern
-        .lines()
+/// 100 times the rows above the line of reflection of `mat`, or else the
+/// columns left of it. `pat` is the pattern as written.
+pub(crate) fn summarize(pat: &str, mat: &[Vec<bool>]) -> miette::Result<usize, AocError> {
+    if let Some(i) = find_reflection(mat) {
+        Ok(i * 100)
+    } else {
+        let mat = transpose(mat.to_vec());
+        find_reflection(&mat)
+            .ok_or_else(|| AocError::no_solution(pat, pat, "pattern has no line of reflection"))
+    }
+}
+
+pub(crate) fn pattern_to_matrix(rows: &[&str]) -> Vec<Vec<bool>> {
+    rows.iter()
         .map(|line| line.chars().map(|ch| ch == '#').collect_vec())
         .collect_vec()
 }
@@ -61,6 +84,7 @@ fn difference(a: &[bool], b: &[bool]) -> usize {
 mod tests {
     use super::*;
     use indoc::indoc;
+    use proptest::prelude::*;
     use rstest::rstest;
 
     #[rstest]
@@ -81,7 +105,7 @@ mod tests {
         ..##..###
         #....#..#" }, 1)]
     fn test_pattern(#[case] input: &str, #[case] expected: usize) {
-        let input = pattern_to_matrix(input);
+        let input = pattern_to_matrix(&input.lines().collect_vec());
         if let Some(actual) = find_reflection(&input) {
             assert_eq!(expected, actual);
         } else {
@@ -90,27 +114,14 @@ mod tests {
         }
     }
 
-    #[test]
-    fn test_process() -> miette::Result<()> {
-        let input = indoc! {
-            "#.##..##.
-            ..#.##.#.
-            ##......#
-            ##......#
-            ..#.##.#.
-            ..##..##.
-            #.#.##.#.
-
-            #...##..#
-            #....#..#
-            ..##..###
-            #####.##.
-            #####.##.
-            ..##..###
-            #....#..#"
-        };
-        assert_eq!(400, process(input)?);
-        Ok(())
+    proptest! {
+        #[test]
+        fn test_transpose_is_an_involution(
+            mat in (1..16usize, 1..16usize).prop_flat_map(|(rows, cols)| {
+                prop::collection::vec(prop::collection::vec(any::<bool>(), cols), rows)
+            })
+        ) {
+            prop_assert_eq!(&mat, &transpose(transpose(mat.clone())));
+        }
     }
 }
-
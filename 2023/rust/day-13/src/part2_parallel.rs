@@ -0,0 +1,36 @@
+use aoc_common::{check::rectangle, parse::finish};
+use rayon::prelude::*;
+
+use crate::{
+    errors::AocError,
+    parser,
+    part2::{pattern_to_matrix, summarize, Pattern},
+};
+
+#[tracing::instrument(skip(input), fields(input_len = input.len()))]
+pub fn process(input: &str) -> miette::Result<usize, AocError> {
+    solve(&parse(input)?)
+}
+
+/// [`crate::part2::parse`] with the patterns turned into matrices across the
+/// rayon thread pool.
+#[tracing::instrument(skip_all)]
+pub fn parse(input: &str) -> miette::Result<Vec<Pattern<'_>>, AocError> {
+    finish(input, parser::patterns)?
+        .into_par_iter()
+        .map(|(pat, rows)| {
+            rectangle(input, &rows)?;
+            Ok((pat, pattern_to_matrix(&rows)))
+        })
+        .collect()
+}
+
+/// [`crate::part2::solve`] with the patterns split across the rayon thread
+/// pool.
+#[tracing::instrument(skip_all)]
+pub fn solve(patterns: &[Pattern<'_>]) -> miette::Result<usize, AocError> {
+    patterns
+        .par_iter()
+        .map(|(pat, mat)| summarize(pat, mat))
+        .sum::<Result<usize, AocError>>()
+}
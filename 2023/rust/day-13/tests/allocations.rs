@@ -0,0 +1,29 @@
+//! Allocation budgets of every variant on the real input. dhat counts every
+//! allocation of the process, so this file must hold a single test.
+
+use aoc_common::budget::{assert_within, Budget};
+
+#[global_allocator]
+static ALLOC: dhat::Alloc = dhat::Alloc;
+
+#[test]
+fn allocations() -> miette::Result<()> {
+    let input = day_13::input::read_input(None)?;
+    assert_within(
+        "part1",
+        Budget {
+            blocks: 5_200,
+            max_bytes: 86_000,
+        },
+        || day_13::part1::process(&input),
+    )?;
+    assert_within(
+        "part2",
+        Budget {
+            blocks: 5_000,
+            max_bytes: 86_000,
+        },
+        || day_13::part2::process(&input),
+    )?;
+    Ok(())
+}
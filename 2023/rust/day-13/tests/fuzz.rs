@@ -0,0 +1,17 @@
+//! Inputs the fuzz targets in `fuzz/` found a panic on, minimized.
+
+use day_13::errors::AocError;
+
+#[test]
+fn ragged_rows() {
+    for input in ["#.\n#", "#.\n.#\n\n##\n#"] {
+        assert!(matches!(
+            day_13::part1::process(input),
+            Err(AocError::InvalidInput { .. })
+        ));
+        assert!(matches!(
+            day_13::part2::process(input),
+            Err(AocError::InvalidInput { .. })
+        ));
+    }
+}
@@ -1,22 +1,77 @@
-use day_14::*;
+use aoc_common::generate::{generate_seeded, Shape};
+
+#[global_allocator]
+static ALLOC: divan::AllocProfiler = divan::AllocProfiler::system();
 
 fn main() {
     // Run registered benchmarks.
     divan::main();
 }
 
-#[divan::bench]
-fn part1() {
-    part1::process(divan::black_box(include_str!(
-        "../input.txt",
-    )))
-    .unwrap();
+aoc_common::bench_stages!(day_14::part1);
+aoc_common::bench_stages!(day_14::part2);
+
+/// A square platform `side` cells wide, 40% of them rocks. Part 2 spins it
+/// until it repeats, which takes more spins on bigger platforms too, so
+/// `spinning` below keeps the size and sweeps the spins.
+fn platform(side: usize) -> String {
+    let shape = Shape {
+        density: Some(0.4),
+        width: Some(side),
+        ..Shape::new(side)
+    };
+    generate_seeded(14, shape, 0).unwrap().input
+}
+
+aoc_common::bench_scaling!(side: [12, 25, 50, 100] => platform;
+    day_14::part1,
+    day_14::part2,
+);
+
+/// A 10 by 10 platform that repeats every `cycle` spins once it settles,
+/// the first generated one.
+fn tile(cycle: usize) -> Vec<String> {
+    (0..)
+        .map(|seed| {
+            let shape = Shape {
+                width: Some(10),
+                ..Shape::new(10)
+            };
+            generate_seeded(14, shape, seed).unwrap().input
+        })
+        .find(|input| {
+            let mut mat = day_14::part2::parse(input).unwrap();
+            day_14::part2::find_cycle(&mut mat).1 == cycle
+        })
+        .unwrap()
+        .lines()
+        .map(str::to_string)
+        .collect()
 }
 
-#[divan::bench]
-fn part2() {
-    part2::process(divan::black_box(include_str!(
-        "../input.txt",
-    )))
-    .unwrap();
+/// A 21 by 21 platform that repeats every `cycle` spins, for `cycle` of 1,
+/// 2, 3, 4, 6 or 12: four tiles walled off from each other by cube rocks,
+/// which spin on their own, so the platform repeats when all of them do.
+fn spinning(cycle: usize) -> String {
+    let cycles = match cycle {
+        6 => [2, 3, 1, 1],
+        12 => [4, 3, 1, 1],
+        cycle => [cycle, 1, 1, 1],
+    };
+    let [a, b, c, d] = cycles.map(tile);
+    let wall = "#".repeat(21);
+    let rows = |left: &[String], right: &[String]| {
+        left.iter()
+            .zip(right)
+            .map(|(left, right)| format!("{}#{}", left, right))
+            .collect::<Vec<_>>()
+    };
+    let input = [rows(&a, &b), vec![wall], rows(&c, &d)].concat().join("\n");
+    let mut mat = day_14::part2::parse(&input).unwrap();
+    assert_eq!(cycle, day_14::part2::find_cycle(&mut mat).1);
+    input
 }
+
+aoc_common::bench_scaling!(cycle: [1, 2, 3, 4, 6, 12] => spinning;
+    day_14::part2,
+);
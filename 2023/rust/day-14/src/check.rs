@@ -0,0 +1,34 @@
+use aoc_common::check::Violations;
+
+use crate::errors::AocError;
+
+/// The platform is a rectangle of rounded rocks `O`, cube-shaped rocks `#`
+/// and empty space `.`.
+pub fn check(input: &str) -> Vec<AocError> {
+    let mut violations = Violations::new(input);
+    let rows = input.lines().collect::<Vec<_>>();
+    violations.grid(&rows, "O#.");
+    violations.into_errors()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check() {
+        assert!(check("O..#\n.#O.\n").is_empty());
+        let errors = check("O..#\n.#o.\n0.#\n")
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                "invalid puzzle input: `o` is not one of `O#.`",
+                "invalid puzzle input: `0` is not one of `O#.`",
+                "invalid puzzle input: row is 3 wide, the first row is 4 wide",
+            ],
+            errors
+        );
+    }
+}
@@ -0,0 +1,30 @@
+use aoc_common::parse::{grid, IResult};
+
+/// Rows of the platform: `O` for a rounded rock, `#` for a cube-shaped rock
+/// and `.` for empty space.
+pub fn platform(input: &str) -> IResult<'_, Vec<&str>> {
+    grid("O#.")(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::{errors::AocError, parse::finish};
+
+    use super::*;
+
+    #[test]
+    fn test_platform() -> miette::Result<()> {
+        assert_eq!(vec!["O..#", ".#O."], finish("O..#\n.#O.\n", platform)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_platform_error() {
+        match finish("O..#\n.#o.\n", platform) {
+            Err(AocError::ParseError { message, .. }) => {
+                assert_eq!("expected one of `O#.` at line 2, column 3", message)
+            }
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+}
@@ -1,19 +1,24 @@
+use aoc_common::{check::rectangle, parse::finish};
 use itertools::Itertools;
 
-use crate::errors::AocError;
+use crate::{errors::AocError, parser};
 
-#[tracing::instrument]
+#[tracing::instrument(skip(input), fields(input_len = input.len()))]
 pub fn process(input: &str) -> miette::Result<usize, AocError> {
-    let mat = parse_input(input);
+    solve(&parse(input)?)
+}
+
+#[tracing::instrument(skip_all)]
+pub fn solve(mat: &[Vec<char>]) -> miette::Result<usize, AocError> {
     let res = total_load(mat);
     Ok(res)
 }
 
-fn total_load(mat: Vec<Vec<char>>) -> usize {
-    mat.iter().map(row_load).sum::<usize>()
+fn total_load(mat: &[Vec<char>]) -> usize {
+    mat.iter().map(|row| row_load(row)).sum::<usize>()
 }
 
-fn row_load(row: &Vec<char>) -> usize {
+fn row_load(row: &[char]) -> usize {
     let n = row.len();
     let mut ptr = 0_usize;
     row.iter()
@@ -34,43 +39,22 @@ fn row_load(row: &Vec<char>) -> usize {
         .sum::<usize>()
 }
 
-fn parse_input(input: &str) -> Vec<Vec<char>> {
-    let mat = input
-        .lines()
+/// The platform transposed, so that north is at the start of each row.
+#[tracing::instrument(skip_all)]
+pub fn parse(input: &str) -> miette::Result<Vec<Vec<char>>, AocError> {
+    let rows = finish(input, parser::platform)?;
+    rectangle(input, &rows)?;
+    let mat = rows
+        .into_iter()
         .map(|line| line.chars().collect_vec())
         .collect_vec();
-    transpose(mat)
+    Ok(transpose(mat))
 }
 
-fn transpose(mat: Vec<Vec<char>>) -> Vec<Vec<char>> {
+pub(crate) fn transpose(mat: Vec<Vec<char>>) -> Vec<Vec<char>> {
     assert!(!mat.is_empty());
     let (_, n) = (mat.len(), mat[0].len());
     (0..n)
         .map(|c| mat.iter().map(|r| r[c]).collect_vec())
         .collect_vec()
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use indoc::indoc;
-    use rstest::rstest;
-
-    #[rstest]
-    #[case(indoc!{"
-        O....#....
-        O.OO#....#
-        .....##...
-        OO.#O....O
-        .O.....O#.
-        O.#..O.#.#
-        ..O..#O..O
-        .......O..
-        #....###..
-        #OO..#....
-        "}, 136)]
-    fn test_process(#[case] input: &str, #[case] expected: usize) -> miette::Result<()> {
-        assert_eq!(expected, process(input)?);
-        Ok(())
-    }
-}
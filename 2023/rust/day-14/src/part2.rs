@@ -1,36 +1,53 @@
 use std::collections::HashMap;
 
+use aoc_common::{check::rectangle, parse::finish};
 use itertools::Itertools;
 
-use crate::errors::AocError;
+use crate::{errors::AocError, parser};
 
-#[tracing::instrument]
+#[tracing::instrument(skip(input), fields(input_len = input.len()))]
 pub fn process(input: &str) -> miette::Result<usize, AocError> {
-    let mut mat = parse_input(input);
+    solve(&parse(input)?)
+}
 
-    let mut cache: HashMap<String, usize> = HashMap::new();
-    let mut cycle_start = 0_usize;
-    let mut cycle_len = 0_usize;
-
-    for i in 0..1_000_000_000 {
-        let encoded = encode(&mat);
-        let memo = cache.entry(encoded).or_insert(i);
-        if *memo != i {
-            cycle_start = *memo;
-            cycle_len = i - cycle_start;
-            break;
-        }
-        spin(&mut mat);
-    }
+#[tracing::instrument(skip_all)]
+pub fn solve(mat: &[Vec<char>]) -> miette::Result<usize, AocError> {
+    let mut mat = mat.to_vec();
+
+    let span = tracing::info_span!(
+        "cycle_search",
+        cycle_start = tracing::field::Empty,
+        cycle_len = tracing::field::Empty
+    );
+    let (cycle_start, cycle_len) = span.in_scope(|| find_cycle(&mut mat));
+    span.record("cycle_start", cycle_start);
+    span.record("cycle_len", cycle_len);
 
     let num_to_spin = (1_000_000_000 - cycle_start) % cycle_len;
-    for _ in 0..num_to_spin {
-        spin(&mut mat);
-    }
+    tracing::info_span!("spin_replay", spins = num_to_spin).in_scope(|| {
+        for _ in 0..num_to_spin {
+            spin(&mut mat);
+        }
+    });
     let res = calc_load(&mat);
     Ok(res)
 }
 
+/// Spins `mat` until it comes back to a state it was in before, and returns
+/// after how many spins that state first came up and how many more it took
+/// to come back to it.
+pub fn find_cycle(mat: &mut Vec<Vec<char>>) -> (usize, usize) {
+    let mut cache: HashMap<String, usize> = HashMap::new();
+    for i in 0.. {
+        let memo = *cache.entry(encode(mat)).or_insert(i);
+        if memo != i {
+            return (memo, i - memo);
+        }
+        spin(mat);
+    }
+    unreachable!("a platform has finitely many states")
+}
+
 fn encode(mat: &[Vec<char>]) -> String {
     mat.iter()
         .map(|row| row.iter().collect::<String>())
@@ -48,6 +65,7 @@ fn calc_load(mat: &[Vec<char>]) -> usize {
         .sum::<usize>()
 }
 
+#[derive(Debug, Clone, Copy)]
 enum Direction {
     North,
     East,
@@ -87,7 +105,7 @@ fn tilt_to(mat: &mut Vec<Vec<char>>, direction: Direction) {
     }
 }
 
-fn tilt(mat: &mut Vec<Vec<char>>) {
+fn tilt(mat: &mut [Vec<char>]) {
     assert!(!mat.is_empty());
     let n = mat[0].len();
     mat.iter_mut().for_each(|row| {
@@ -106,11 +124,15 @@ fn tilt(mat: &mut Vec<Vec<char>>) {
     })
 }
 
-fn parse_input(input: &str) -> Vec<Vec<char>> {
-    input
-        .lines()
+#[tracing::instrument(skip_all)]
+pub fn parse(input: &str) -> miette::Result<Vec<Vec<char>>, AocError> {
+    let rows = finish(input, parser::platform)?;
+    rectangle(input, &rows)?;
+    let mat = rows
+        .into_iter()
         .map(|line| line.chars().collect_vec())
-        .collect_vec()
+        .collect_vec();
+    Ok(mat)
 }
 
 fn transposed(mat: &mut Vec<Vec<char>>) {
@@ -132,25 +154,7 @@ fn reversed(mat: &mut Vec<Vec<char>>) {
 mod tests {
     use super::*;
     use indoc::indoc;
-    use rstest::rstest;
-
-    #[rstest]
-    #[case(indoc!{"
-        O....#....
-        O.OO#....#
-        .....##...
-        OO.#O....O
-        .O.....O#.
-        O.#..O.#.#
-        ..O..#O..O
-        .......O..
-        #....###..
-        #OO..#....
-        "}, 64)]
-    fn test_process(#[case] input: &str, #[case] expected: usize) -> miette::Result<()> {
-        assert_eq!(expected, process(input)?);
-        Ok(())
-    }
+    use proptest::prelude::*;
 
     #[test]
     fn test_tilt_to() -> miette::Result<()> {
@@ -165,7 +169,7 @@ mod tests {
             .......O..
             #....###..
             #OO..#...."};
-        let mut mat = parse_input(input);
+        let mut mat = parse(input)?;
         tilt_to(&mut mat, Direction::North);
         let north_actual = mat
             .iter()
@@ -254,7 +258,7 @@ mod tests {
             .......O..
             #....###..
             #OO..#...."};
-        let mut mat = parse_input(input);
+        let mut mat = parse(input)?;
         spin(&mut mat);
         let actual = mat
             .iter()
@@ -310,6 +314,48 @@ mod tests {
         Ok(())
     }
 
+    /// Rectangular platforms of up to 12 by 12 cells.
+    fn platform() -> impl Strategy<Value = Vec<Vec<char>>> {
+        (1..12usize, 1..12usize).prop_flat_map(|(rows, cols)| {
+            let cell = prop::sample::select(vec!['.', 'O', '#']);
+            prop::collection::vec(prop::collection::vec(cell, cols), rows)
+        })
+    }
+
+    fn cells(mat: &[Vec<char>], shape: char) -> Vec<(usize, usize)> {
+        (0..mat.len())
+            .flat_map(|r| (0..mat[r].len()).map(move |c| (r, c)))
+            .filter(|&(r, c)| mat[r][c] == shape)
+            .collect()
+    }
+
+    proptest! {
+        #[test]
+        fn test_spin_moves_only_rounded_rocks(mut mat in platform()) {
+            let (rounded, cubes) = (cells(&mat, 'O').len(), cells(&mat, '#'));
+            spin(&mut mat);
+            prop_assert_eq!(rounded, cells(&mat, 'O').len());
+            prop_assert_eq!(cubes, cells(&mat, '#'));
+        }
+
+        #[test]
+        fn test_tilt_to_is_idempotent(mut mat in platform()) {
+            for direction in [Direction::North, Direction::West, Direction::South, Direction::East] {
+                tilt_to(&mut mat, direction);
+                let once = mat.clone();
+                tilt_to(&mut mat, direction);
+                prop_assert_eq!(&once, &mat, "tilting {:?} again moved rocks", direction);
+            }
+        }
+
+        #[test]
+        fn test_north_load(mut mat in platform()) {
+            let input = mat.iter().map(|row| row.iter().collect::<String>()).join("\n");
+            tilt_to(&mut mat, Direction::North);
+            prop_assert_eq!(crate::part1::process(&input)?, calc_load(&mat));
+        }
+    }
+
     /*
         OOOO.#.O..
         OO..#....#
@@ -357,4 +403,3 @@ mod tests {
 
     */
 }
-
@@ -0,0 +1,8 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = day_01::part2::process(input);
+    let _ = day_01::part2_aho::process(input);
+});
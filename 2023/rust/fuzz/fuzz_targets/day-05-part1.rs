@@ -0,0 +1,8 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = day_05::part1_binary_search::process(input);
+    let _ = day_05::part1_btree::process(input);
+});
@@ -0,0 +1,10 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = day_11::part1_expand::process(input);
+    let _ = day_11::part1_expand_bfs::process(input);
+    let _ = day_11::part1_no_expand::process(input);
+    let _ = day_11::part1_no_expand_opt::process(input);
+});